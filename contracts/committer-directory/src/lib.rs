@@ -0,0 +1,168 @@
+//! # CommitterDirectory - Stella Protocol
+//!
+//! An **opt-in companion** to RouteIntegrityRegistry: committers list
+//! themselves here with a hash of their public metadata, and verifiers that
+//! only trust listed committers check commitments through
+//! `verify_commit_from_listed` on the registry.
+//!
+//! - ❌ Does NOT gate `commit_route`; the registry stays permissionless
+//! - ❌ Does NOT vet anyone; listing is self-service
+//! - ✅ ONLY stores (committer → metadata hash) for read-side filtering
+//!
+//! A wallet that wants a vetted set deploys (or picks) a directory whose
+//! listings it trusts and passes that directory's address when verifying.
+
+#![no_std]
+
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, symbol_short, Address, BytesN, Env};
+
+/// Storage keys
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// Metadata hash of a listed committer (persistent)
+    Listing(Address),
+}
+
+/// Error codes for the CommitterDirectory contract
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum DirectoryError {
+    /// Metadata hash cannot be all zeros
+    EmptyMetadataHash = 1,
+    /// Committer is not listed
+    NotListed = 2,
+}
+
+/// # CommitterDirectory Contract
+///
+/// Self-service committer listing. There is no admin: each committer can
+/// only list, update or remove itself.
+#[contract]
+pub struct CommitterDirectory;
+
+#[contractimpl]
+impl CommitterDirectory {
+    /// List `committer`, or update its metadata hash if already listed.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Soroban environment
+    /// * `committer` - Committer listing itself (must authorize)
+    /// * `metadata_hash` - Hash of the committer's off-chain metadata document
+    ///
+    /// # Events
+    ///
+    /// Emits `("listed", committer)` with `metadata_hash`
+    ///
+    /// # Errors
+    ///
+    /// * `EmptyMetadataHash` - metadata_hash is all zeros
+    pub fn register(env: Env, committer: Address, metadata_hash: BytesN<32>) -> Result<(), DirectoryError> {
+        committer.require_auth();
+
+        if metadata_hash.to_array().iter().all(|&b| b == 0) {
+            return Err(DirectoryError::EmptyMetadataHash);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Listing(committer.clone()), &metadata_hash);
+
+        env.events().publish((symbol_short!("listed"), committer), metadata_hash);
+
+        Ok(())
+    }
+
+    /// Remove `committer`'s own listing.
+    ///
+    /// # Events
+    ///
+    /// Emits `("delisted", committer)` with `()`
+    ///
+    /// # Errors
+    ///
+    /// * `NotListed` - committer has no listing
+    pub fn unregister(env: Env, committer: Address) -> Result<(), DirectoryError> {
+        committer.require_auth();
+
+        let key = DataKey::Listing(committer.clone());
+        if !env.storage().persistent().has(&key) {
+            return Err(DirectoryError::NotListed);
+        }
+        env.storage().persistent().remove(&key);
+
+        env.events().publish((symbol_short!("delisted"), committer), ());
+
+        Ok(())
+    }
+
+    /// Check whether `committer` is currently listed
+    pub fn is_listed(env: Env, committer: Address) -> bool {
+        env.storage().persistent().has(&DataKey::Listing(committer))
+    }
+
+    /// Get the metadata hash `committer` listed with, if any
+    pub fn get_metadata(env: Env, committer: Address) -> Option<BytesN<32>> {
+        env.storage().persistent().get(&DataKey::Listing(committer))
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// UNIT TESTS
+// ═══════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::testutils::Address as _;
+    use soroban_sdk::Env;
+
+    fn setup() -> (Env, CommitterDirectoryClient<'static>) {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register_contract(None, CommitterDirectory);
+        let client = CommitterDirectoryClient::new(&env, &contract_id);
+        (env, client)
+    }
+
+    #[test]
+    fn test_register_update_and_unregister() {
+        let (env, client) = setup();
+        let committer = Address::generate(&env);
+        assert!(!client.is_listed(&committer));
+
+        client.register(&committer, &BytesN::from_array(&env, &[1u8; 32]));
+        assert!(client.is_listed(&committer));
+
+        client.register(&committer, &BytesN::from_array(&env, &[2u8; 32]));
+        assert_eq!(client.get_metadata(&committer), Some(BytesN::from_array(&env, &[2u8; 32])));
+
+        client.unregister(&committer);
+        assert!(!client.is_listed(&committer));
+        assert_eq!(client.get_metadata(&committer), None);
+        assert_eq!(client.try_unregister(&committer), Err(Ok(DirectoryError::NotListed)));
+    }
+
+    #[test]
+    fn test_register_rejects_empty_metadata() {
+        let (env, client) = setup();
+        let committer = Address::generate(&env);
+
+        let result = client.try_register(&committer, &BytesN::from_array(&env, &[0u8; 32]));
+        assert_eq!(result, Err(Ok(DirectoryError::EmptyMetadataHash)));
+        assert!(!client.is_listed(&committer));
+    }
+
+    #[test]
+    fn test_register_requires_committer_auth() {
+        let env = Env::default();
+        let contract_id = env.register_contract(None, CommitterDirectory);
+        let client = CommitterDirectoryClient::new(&env, &contract_id);
+
+        let committer = Address::generate(&env);
+        assert!(client.try_register(&committer, &BytesN::from_array(&env, &[1u8; 32])).is_err());
+        assert!(!client.is_listed(&committer));
+    }
+}
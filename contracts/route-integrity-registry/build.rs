@@ -0,0 +1,58 @@
+//! Captures build provenance for auditors comparing deployed wasm to source.
+//!
+//! Emits `STELLA_BUILD_*` env vars (read by `get_build_info`) and writes
+//! `build_meta.rs` into OUT_DIR with the matching `contractmeta!` entries, so
+//! the on-chain metadata and the runtime answer come from the same values.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8(output.stdout).ok()?;
+    Some(text.trim().to_string())
+}
+
+fn main() {
+    let git = command_output("git", &["describe", "--always", "--dirty"])
+        .unwrap_or_else(|| "unknown".to_string());
+    let profile = env::var("PROFILE").unwrap_or_else(|_| "unknown".to_string());
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let rustc = command_output(&rustc, &["--version"]).unwrap_or_else(|| "unknown".to_string());
+
+    let mut features: Vec<String> = env::vars()
+        .filter_map(|(key, _)| key.strip_prefix("CARGO_FEATURE_").map(|f| f.to_lowercase()))
+        .collect();
+    features.sort();
+    let features = features.join(",");
+
+    let entries = [
+        ("STELLA_BUILD_GIT", "stella_git", &git),
+        ("STELLA_BUILD_PROFILE", "stella_profile", &profile),
+        ("STELLA_BUILD_FEATURES", "stella_features", &features),
+        ("STELLA_BUILD_RUSTC", "stella_rustc", &rustc),
+    ];
+
+    let mut meta = String::new();
+    for (var, key, value) in entries.iter() {
+        println!("cargo:rustc-env={}={}", var, value);
+        meta.push_str(&format!(
+            "soroban_sdk::contractmeta!(key = {:?}, val = {:?});\n",
+            key, value
+        ));
+    }
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+    fs::write(Path::new(&out_dir).join("build_meta.rs"), meta).expect("write build_meta.rs");
+
+    println!("cargo:rerun-if-changed=build.rs");
+    if let Some(git_dir) = command_output("git", &["rev-parse", "--git-dir"]) {
+        println!("cargo:rerun-if-changed={}/HEAD", git_dir);
+        println!("cargo:rerun-if-changed={}/refs/heads", git_dir);
+    }
+}
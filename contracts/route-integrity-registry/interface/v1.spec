@@ -0,0 +1,290 @@
+error RegistryError::ActivationTooFar = 13
+error RegistryError::AlreadyCancelled = 47
+error RegistryError::AlreadyConsumed = 26
+error RegistryError::AlreadyInactive = 46
+error RegistryError::AlreadyRevoked = 34
+error RegistryError::AlreadyRotated = 36
+error RegistryError::AlreadySuperseded = 35
+error RegistryError::AnomalyAlreadyReported = 6
+error RegistryError::CommitCountOverflow = 32
+error RegistryError::CommitmentNotActive = 25
+error RegistryError::ConflictingOptions = 23
+error RegistryError::CorridorAlreadyRegistered = 20
+error RegistryError::CorridorPairAlreadyRegistered = 21
+error RegistryError::DualHashesEqual = 15
+error RegistryError::DuplicateCommitment = 2
+error RegistryError::EmptyAssetHash = 18
+error RegistryError::EmptyBatch = 40
+error RegistryError::EmptyConsumptionRef = 24
+error RegistryError::EmptyEvidenceHash = 10
+error RegistryError::EmptyExpectation = 22
+error RegistryError::EmptyNameHash = 30
+error RegistryError::EmptyReasonHash = 33
+error RegistryError::EmptyRouteHash = 1
+error RegistryError::EmptyRulesHash = 8
+error RegistryError::EmptySecondaryHash = 14
+error RegistryError::EmptySolverHash = 9
+error RegistryError::ExpiredTimestamp = 3
+error RegistryError::ExpiryTooFar = 4
+error RegistryError::FamilyLimitExceeded = 51
+error RegistryError::IdenticalCorridorAssets = 19
+error RegistryError::InvalidActivationWindow = 12
+error RegistryError::InvalidWindow = 44
+error RegistryError::NotFound = 5
+error RegistryError::ProfileUriTooLong = 31
+error RegistryError::QuoteExpired = 38
+error RegistryError::RecentCapacityExceeded = 43
+error RegistryError::RevealMismatch = 41
+error RegistryError::SelfReferentialAddress = 11
+error RegistryError::SelfRelay = 17
+error RegistryError::SignerAlreadyRegistered = 48
+error RegistryError::SignerDeactivated = 49
+error RegistryError::TooManyAnomalies = 7
+error RegistryError::TooManyConsumptions = 27
+error RegistryError::TooManyEndorsements = 29
+error RegistryError::TooManyEntries = 39
+error RegistryError::TooManyRotations = 37
+error RegistryError::TooManySigners = 50
+error RegistryError::TtlTooLong = 42
+error RegistryError::Unauthorized = 28
+error RegistryError::WindowTooLong = 45
+error VerifyFailure::Cancelled = 7
+error VerifyFailure::Expired = 5
+error VerifyFailure::NotFound = 1
+error VerifyFailure::Revoked = 4
+error VerifyFailure::RulesMismatch = 2
+error VerifyFailure::Scheduled = 6
+error VerifyFailure::SolverMismatch = 3
+fn ack_route(route_hash: BytesN<32>, user: Address) -> Result<(), RegistryError>
+fn approve_delegate(owner: Address, delegate: Address) -> Result<(), RegistryError>
+fn attest(route_hash: BytesN<32>, attester: Address) -> Result<(), RegistryError>
+fn attest_batch(attester: Address, route_hashes: Vec<BytesN<32>>) -> Result<Vec<bool>, RegistryError>
+fn attestation_payload(route_hash: BytesN<32>) -> Result<Bytes, RegistryError>
+fn attestation_payload_hash(route_hash: BytesN<32>) -> Result<BytesN<32>, RegistryError>
+fn cancel_quote(committer: Address, route_hash: BytesN<32>) -> Result<(), RegistryError>
+fn commit(committer: Address, route_hash: BytesN<32>, core: CoreHashes, options: CommitOptions) -> Result<u64, RegistryError>
+fn commit_as(owner: Address, delegate: Address, route_hash: BytesN<32>, rules_hash: BytesN<32>, solver_version_hash: BytesN<32>, expiry: u64) -> Result<u64, RegistryError>
+fn commit_as_scoped(owner: Address, delegate: Address, scope: CommitScope, entry: CommitEntry) -> Result<u64, RegistryError>
+fn commit_batch_root(committer: Address, batch_root: BytesN<32>, rules_hash: BytesN<32>, solver_version_hash: BytesN<32>, expiry: u64, leaf_count: u32) -> Result<(), RegistryError>
+fn commit_blinded(committer: Address, blinded_hash: BytesN<32>, expiry: u64) -> Result<(), RegistryError>
+fn commit_child_routes(committer: Address, parent_route_hash: BytesN<32>, children: Vec<CommitEntry>) -> Result<(), RegistryError>
+fn commit_existence(committer: Address, route_hash: BytesN<32>) -> Result<(), RegistryError>
+fn commit_route(committer: Address, route_hash: BytesN<32>, rules_hash: BytesN<32>, solver_version_hash: BytesN<32>, expiry: u64) -> Result<u64, RegistryError>
+fn commit_route_bound(committer: Address, route_hash: BytesN<32>, rules_hash: BytesN<32>, solver_version_hash: BytesN<32>, expiry: u64, user_binding: BytesN<32>) -> Result<u64, RegistryError>
+fn commit_route_dual(committer: Address, route_hash_sha256: BytesN<32>, route_hash_keccak: BytesN<32>, rules_hash: BytesN<32>, solver_version_hash: BytesN<32>, expiry: u64) -> Result<u64, RegistryError>
+fn commit_route_expiring(committer: Address, route_hash: BytesN<32>, rules_hash: BytesN<32>, solver_version_hash: BytesN<32>, expiry: Expiry) -> Result<u64, RegistryError>
+fn commit_route_for(committer: Address, route_hash: BytesN<32>, rules_hash: BytesN<32>, solver_version_hash: BytesN<32>, expiry: u64) -> Result<u64, RegistryError>
+fn commit_route_relayed(relayer: Address, committer: Address, route_hash: BytesN<32>, rules_hash: BytesN<32>, solver_version_hash: BytesN<32>, expiry: u64) -> Result<u64, RegistryError>
+fn commit_route_sandbox(committer: Address, route_hash: BytesN<32>, rules_hash: BytesN<32>, solver_version_hash: BytesN<32>, expiry: u64) -> Result<(), RegistryError>
+fn commit_route_scheduled(committer: Address, route_hash: BytesN<32>, rules_hash: BytesN<32>, solver_version_hash: BytesN<32>, active_from: u64, expiry: u64) -> Result<u64, RegistryError>
+fn commit_route_signed(pubkey: BytesN<32>, signature: BytesN<64>, route_hash: BytesN<32>, rules_hash: BytesN<32>, solver_version_hash: BytesN<32>, expiry: u64) -> Result<u64, RegistryError>
+fn commit_routes(committer: Address, entries: Vec<CommitEntry>) -> Result<(), RegistryError>
+fn commit_routes_partial(committer: Address, entries: Vec<CommitEntry>) -> Result<Vec<CommitResult>, RegistryError>
+fn commits_last_n_days(n: u32) -> Vec<u64>
+fn commits_on_day(day_index: u64) -> u64
+fn committer_count() -> u64
+fn count_by_rules(rules_hash: BytesN<32>) -> u64
+fn count_by_solver(solver_hash: BytesN<32>) -> u64
+fn deactivate_signer(committer: Address, signer_pubkey: BytesN<32>) -> Result<(), RegistryError>
+fn emit_commit_only(committer: Address, route_hash: BytesN<32>, rules_hash: BytesN<32>, solver_version_hash: BytesN<32>) -> Result<(), RegistryError>
+fn endorse_commit(route_hash: BytesN<32>, endorser: Address) -> Result<(), RegistryError>
+fn exists_any(route_hashes: Vec<BytesN<32>>) -> Result<bool, RegistryError>
+fn extend_ttls(route_hashes: Vec<BytesN<32>>, extend_to: u32) -> Result<u32, RegistryError>
+fn find_corridor_by_pair(source_asset_hash: BytesN<32>, dest_asset_hash: BytesN<32>) -> Option<Symbol>
+fn first_existing(route_hashes: Vec<BytesN<32>>) -> Result<Option<BytesN<32>>, RegistryError>
+fn get_ack(route_hash: BytesN<32>, user: Address) -> Option<u64>
+fn get_anomalies(subject: AnomalySubject) -> Vec<AnomalyReport>
+fn get_attestation(route_hash: BytesN<32>, attester: Address) -> Option<u64>
+fn get_batch(batch_root: BytesN<32>) -> Result<BatchCommitment, RegistryError>
+fn get_blinded(blinded_hash: BytesN<32>) -> Option<BlindedRecord>
+fn get_build_info() -> BuildInfo
+fn get_cancellation(route_hash: BytesN<32>) -> Option<u64>
+fn get_canonical_commit_count(committer: Address) -> u64
+fn get_canonical_commits(committer: Address) -> Vec<BytesN<32>>
+fn get_children(route_hash: BytesN<32>) -> Vec<BytesN<32>>
+fn get_commit(route_hash: BytesN<32>) -> Result<RouteCommitment, RegistryError>
+fn get_commit_at(route_hash: BytesN<32>) -> Result<CommitAt, RegistryError>
+fn get_commit_by_committer_seq(committer: Address, seq: u64) -> Result<BytesN<32>, RegistryError>
+fn get_commit_by_id(id: u64) -> Result<RouteCommitment, RegistryError>
+fn get_commit_count_by_committer(committer: Address) -> u64
+fn get_commit_hashes_range(start_id: u64, limit: u32) -> Vec<BytesN<32>>
+fn get_commit_summary(route_hash: BytesN<32>) -> CommitSummary
+fn get_commits(route_hashes: Vec<BytesN<32>>) -> Result<Vec<Option<RouteCommitment>>, RegistryError>
+fn get_commits_by_committer(committer: Address, offset: u32, limit: u32) -> Vec<BytesN<32>>
+fn get_commits_by_rules(rules_hash: BytesN<32>, offset: u32, limit: u32) -> Vec<BytesN<32>>
+fn get_commits_by_solver(solver_hash: BytesN<32>, offset: u32, limit: u32) -> Vec<BytesN<32>>
+fn get_commits_in_window(start_ts: u64, end_ts: u64, offset: u32, limit: u32) -> Result<Vec<BytesN<32>>, RegistryError>
+fn get_committer(route_hash: BytesN<32>) -> Result<Address, RegistryError>
+fn get_committer_day_digest(committer: Address, day: u64) -> Option<BytesN<32>>
+fn get_committer_stats(committer: Address) -> Option<CommitterStats>
+fn get_committers(offset: u32, limit: u32) -> Vec<Address>
+fn get_consumptions(route_hash: BytesN<32>) -> Vec<ConsumptionRecord>
+fn get_endorsements(route_hash: BytesN<32>) -> Vec<Address>
+fn get_event_only_count() -> u64
+fn get_existence(route_hash: BytesN<32>) -> Option<(Address, u64)>
+fn get_expiry(route_hash: BytesN<32>) -> Result<u64, RegistryError>
+fn get_grant(owner: Address, delegate: Address) -> Option<DelegationGrant>
+fn get_ledger_commits(sequence: u32) -> Vec<BytesN<32>>
+fn get_limits() -> Map<Symbol, u64>
+fn get_parent(route_hash: BytesN<32>) -> Option<BytesN<32>>
+fn get_predecessor(route_hash: BytesN<32>) -> Option<BytesN<32>>
+fn get_profile(committer: Address) -> Option<CommitterProfile>
+fn get_recent(n: u32) -> Result<Vec<BytesN<32>>, RegistryError>
+fn get_relayer(route_hash: BytesN<32>) -> Option<Address>
+fn get_revocation(route_hash: BytesN<32>) -> Option<RevocationRecord>
+fn get_sandbox_commit(route_hash: BytesN<32>) -> Result<RouteCommitment, RegistryError>
+fn get_scope(route_hash: BytesN<32>) -> Option<CommitScope>
+fn get_secondary_hash(route_hash: BytesN<32>) -> Option<BytesN<32>>
+fn get_signer(route_hash: BytesN<32>) -> Option<BytesN<32>>
+fn get_signer_deactivation(signer_pubkey: BytesN<32>) -> Option<u64>
+fn get_signer_registration(signer_pubkey: BytesN<32>) -> Option<SignerRegistration>
+fn get_signers(committer: Address) -> Vec<BytesN<32>>
+fn get_status(route_hash: BytesN<32>) -> Result<CommitmentStatus, RegistryError>
+fn get_successor(route_hash: BytesN<32>) -> Option<BytesN<32>>
+fn get_user_binding(route_hash: BytesN<32>) -> Option<BytesN<32>>
+fn get_validity_window(route_hash: BytesN<32>) -> Result<ValidityWindow, RegistryError>
+fn grant_delegate(owner: Address, delegate: Address, expires_at: u64, allowed_namespace: Option<Symbol>, allowed_corridor: Option<Symbol>, max_commits: u32) -> Result<(), RegistryError>
+fn has_commit(route_hash: BytesN<32>) -> bool
+fn has_commits(route_hashes: Vec<BytesN<32>>) -> Result<Vec<bool>, RegistryError>
+fn has_sandbox_commit(route_hash: BytesN<32>) -> bool
+fn is_active(route_hash: BytesN<32>) -> bool
+fn is_cancelled(route_hash: BytesN<32>) -> bool
+fn is_delegate(owner: Address, delegate: Address) -> bool
+fn is_endorsed_by(route_hash: BytesN<32>, endorser: Address) -> bool
+fn is_expired(route_hash: BytesN<32>) -> Result<bool, RegistryError>
+fn is_relayed(route_hash: BytesN<32>) -> bool
+fn is_revoked(route_hash: BytesN<32>) -> bool
+fn latest_commit_by_committer(committer: Address) -> Option<(BytesN<32>, u64)>
+fn latest_commit_id() -> u64
+fn mark_consumed(consumer: Address, route_hash: BytesN<32>, consumption_ref: BytesN<32>) -> Result<(), RegistryError>
+fn max_batch_size() -> u32
+fn max_read_batch_size() -> u32
+fn register_corridor(registrar: Address, corridor: Symbol, source_asset_hash: BytesN<32>, dest_asset_hash: BytesN<32>) -> Result<(), RegistryError>
+fn register_profile(committer: Address, name_hash: BytesN<32>, metadata_uri: Bytes) -> Result<(), RegistryError>
+fn register_signer(committer: Address, signer_pubkey: BytesN<32>, signature: BytesN<64>) -> Result<(), RegistryError>
+fn register_successor(old: Address, new: Address) -> Result<(), RegistryError>
+fn report_anomaly(reporter: Address, subject: AnomalySubject, evidence_hash: BytesN<32>) -> Result<(), RegistryError>
+fn resolve_committer(addr: Address) -> Address
+fn resolve_corridor(corridor: Symbol) -> Option<CorridorEntry>
+fn reveal(blinded_hash: BytesN<32>, route_hash: BytesN<32>, rules_hash: BytesN<32>, solver_version_hash: BytesN<32>, salt: BytesN<32>) -> Result<u64, RegistryError>
+fn revoke_commit(route_hash: BytesN<32>, committer: Address, reason_hash: BytesN<32>) -> Result<(), RegistryError>
+fn revoke_commits(committer: Address, route_hashes: Vec<BytesN<32>>, reason_hash: BytesN<32>) -> Result<Vec<bool>, RegistryError>
+fn revoke_delegate(owner: Address, delegate: Address) -> Result<(), RegistryError>
+fn sample_day(day: u64, seed: BytesN<32>, count: u32) -> Vec<BytesN<32>>
+fn supersede_commit(old_route_hash: BytesN<32>, new_route_hash: BytesN<32>, rules_hash: BytesN<32>, solver_version_hash: BytesN<32>, expiry: u64, committer: Address) -> Result<u64, RegistryError>
+fn time_remaining(route_hash: BytesN<32>) -> Result<u64, RegistryError>
+fn total_commits() -> u64
+fn upgrade_existence(committer: Address, route_hash: BytesN<32>, rules_hash: BytesN<32>, solver_version_hash: BytesN<32>, expiry: u64) -> Result<u64, RegistryError>
+fn verify_commit(route_hash: BytesN<32>, expected_rules_hash: BytesN<32>, expected_solver_hash: BytesN<32>) -> bool
+fn verify_commit_active(route_hash: BytesN<32>, expected_rules_hash: BytesN<32>, expected_solver_hash: BytesN<32>) -> bool
+fn verify_commit_canonical(route_hash: BytesN<32>, expected_rules_hash: BytesN<32>, expected_solver_hash: BytesN<32>, committer: Address) -> bool
+fn verify_commit_detailed(route_hash: BytesN<32>, expected_rules_hash: BytesN<32>, expected_solver_hash: BytesN<32>) -> Result<(), VerifyFailure>
+fn verify_commit_from_listed(directory: Address, route_hash: BytesN<32>, expected_rules_hash: BytesN<32>, expected_solver_hash: BytesN<32>) -> bool
+fn verify_commits(items: Vec<VerifyItem>) -> Result<Vec<bool>, RegistryError>
+fn verify_dual_manifest(route_hash: BytesN<32>, manifest: Bytes) -> bool
+fn verify_for_user(route_hash: BytesN<32>, expected_rules_hash: BytesN<32>, expected_solver_hash: BytesN<32>, user_binding: BytesN<32>) -> bool
+fn verify_inclusion(batch_root: BytesN<32>, route_hash: BytesN<32>, proof: Vec<BytesN<32>>, index: u32) -> bool
+fn verify_partial(route_hash: BytesN<32>, expected: PartialExpectation) -> Result<VerifyResult, RegistryError>
+fn was_consumed_by(route_hash: BytesN<32>, consumer: Address) -> bool
+struct AnomalyReport { evidence_hash: BytesN<32>, reporter: Address, timestamp: u64 }
+struct BatchCommitment { committer: Address, expiry: u64, leaf_count: u32, rules_hash: BytesN<32>, solver_version_hash: BytesN<32>, timestamp: u64 }
+struct BlindedRecord { committer: Address, expiry: u64, timestamp: u64 }
+struct BuildInfo { features: String, git: String, profile: String, rustc: String }
+struct CommitAt { at_ledger: u32, at_timestamp: u64, value: RouteCommitment }
+struct CommitEntry { expiry: u64, route_hash: BytesN<32>, rules_hash: BytesN<32>, solver_version_hash: BytesN<32> }
+struct CommitOptions { expiry: Expiry, schedule: ScheduleOption, secondary_hash: SecondaryHashOption, ttl: TtlOption }
+struct CommitScope { corridor: Symbol, namespace: Symbol }
+struct CommitSummary { exists: bool, expired: bool, revoked: bool, timestamp: u64 }
+struct CommitterProfile { metadata_uri: Bytes, name_hash: BytesN<32>, updated_at: u64 }
+struct CommitterStats { count: u64, first_ts: u64, last_ts: u64 }
+struct ConsumptionRecord { at: u64, consumer: Address, consumption_ref: BytesN<32> }
+struct CoreHashes { rules_hash: BytesN<32>, solver_version_hash: BytesN<32> }
+struct CorridorEntry { dest_asset_hash: BytesN<32>, registered_at: u64, registrar: Address, source_asset_hash: BytesN<32> }
+struct DelegationGrant { allowed_corridor: Option<Symbol>, allowed_namespace: Option<Symbol>, approved_at: u64, delegate: Address, expires_at: u64, remaining_commits: u32 }
+struct ExistenceRecord { committer: Address, timestamp: u64 }
+struct IndexSlots { day: u64, day_seq: u64, rules_seq: u64, solver_seq: u64 }
+struct LegacyCommitKey { route_hash: BytesN<32> }
+struct LegacyRouteCommitment { committer: Address, expiry: u64, rules_hash: BytesN<32>, solver_version_hash: BytesN<32>, timestamp: u64 }
+struct PartialExpectation { committer: AddressExpectation, rules_hash: HashExpectation, solver_version_hash: HashExpectation }
+struct RevocationRecord { reason_hash: BytesN<32>, timestamp: u64 }
+struct RouteCommitment { active_from: u64, commit_id: u64, committer: Address, committer_seq: u64, expiry: u64, intra_ledger_index: u32, rules_hash: BytesN<32>, solver_version_hash: BytesN<32>, timestamp: u64 }
+struct SignerRegistration { committer: Address, registered_at: u64 }
+struct ValidityWindow { active_from: u64, expiry: Expiry }
+struct VerifyItem { expected_rules_hash: BytesN<32>, expected_solver_hash: BytesN<32>, route_hash: BytesN<32> }
+union AddressExpectation::Equals(Address)
+union AddressExpectation::Ignore
+union AnomalySubject::Committer(Address)
+union AnomalySubject::Route(BytesN<32>)
+union CommitResult::Committed(u64)
+union CommitResult::Failed(u32)
+union CommitmentStatus::Active
+union CommitmentStatus::Cancelled
+union CommitmentStatus::Expired
+union CommitmentStatus::Revoked
+union CommitmentStatus::Scheduled
+union DataKey::Ack(BytesN<32>, Address)
+union DataKey::Anomalies(AnomalySubject)
+union DataKey::Attestation(BytesN<32>, Address)
+union DataKey::Batch(BytesN<32>)
+union DataKey::Blinded(BytesN<32>)
+union DataKey::Cancellation(BytesN<32>)
+union DataKey::Children(BytesN<32>)
+union DataKey::Commit(BytesN<32>)
+union DataKey::CommitIdPage(u64)
+union DataKey::CommitterListCount
+union DataKey::CommitterListPage(u64)
+union DataKey::CommitterPage(Address, u64)
+union DataKey::CommitterPredecessor(Address)
+union DataKey::CommitterStats(Address)
+union DataKey::CommitterSuccessor(Address)
+union DataKey::Consumptions(BytesN<32>)
+union DataKey::Corridor(Symbol)
+union DataKey::CorridorPair(BytesN<32>, BytesN<32>)
+union DataKey::DayCount(u64)
+union DataKey::DayDigest(Address, u64)
+union DataKey::DayPage(u64, u64)
+union DataKey::Delegation(Address, Address)
+union DataKey::Endorsement(BytesN<32>, Address)
+union DataKey::Endorsers(BytesN<32>)
+union DataKey::EventOnlyCount
+union DataKey::Existence(BytesN<32>)
+union DataKey::LedgerCount(u32)
+union DataKey::LedgerPage(u32, u32)
+union DataKey::Parent(BytesN<32>)
+union DataKey::Predecessor(BytesN<32>)
+union DataKey::Profile(Address)
+union DataKey::Relayer(BytesN<32>)
+union DataKey::Revocation(BytesN<32>)
+union DataKey::RulesCount(BytesN<32>)
+union DataKey::RulesPage(BytesN<32>, u64)
+union DataKey::Sandbox(BytesN<32>)
+union DataKey::Scope(BytesN<32>)
+union DataKey::SecondaryHash(BytesN<32>)
+union DataKey::Signer(BytesN<32>)
+union DataKey::SignerDeactivation(BytesN<32>)
+union DataKey::SignerKey(BytesN<32>)
+union DataKey::SignerKeys(Address)
+union DataKey::SolverCount(BytesN<32>)
+union DataKey::SolverPage(BytesN<32>, u64)
+union DataKey::Successor(BytesN<32>)
+union DataKey::TotalCommits
+union DataKey::UserBinding(BytesN<32>)
+union Expiry::At(u64)
+union Expiry::Never
+union HashExpectation::Equals(BytesN<32>)
+union HashExpectation::Ignore
+union ScheduleOption::From(u64)
+union ScheduleOption::Immediate
+union SecondaryHashOption::Keccak(BytesN<32>)
+union SecondaryHashOption::Unset
+union StoredCommitment::V1(LegacyRouteCommitment)
+union StoredCommitment::V2(RouteCommitment)
+union StoredCommitment::V3(RouteCommitment, IndexSlots)
+union TtlOption::Seconds(u64)
+union TtlOption::Unset
+union VerifyResult::CommitterMismatch
+union VerifyResult::ExistenceOnly
+union VerifyResult::Match
+union VerifyResult::NotFound
+union VerifyResult::RulesMismatch
+union VerifyResult::SolverMismatch
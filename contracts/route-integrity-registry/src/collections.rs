@@ -0,0 +1,39 @@
+//! Bounded collection helpers for list-building code paths.
+//!
+//! Every listing the registry returns has a documented maximum size. These
+//! helpers are the only place lists are grown, so the bound is enforced by
+//! construction rather than by each caller remembering to check it.
+
+use soroban_sdk::{Env, IntoVal, TryFromVal, Val, Vec};
+
+use crate::RegistryError;
+
+/// Append `item` unless `vec` already holds `max` entries, in which case return `err`
+pub(crate) fn bounded_push<T>(
+    vec: &mut Vec<T>,
+    item: T,
+    max: u32,
+    err: RegistryError,
+) -> Result<(), RegistryError>
+where
+    T: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    if vec.len() >= max {
+        return Err(err);
+    }
+    vec.push_back(item);
+    Ok(())
+}
+
+/// Collect at most `max` items, in iteration order; the rest are never pulled
+pub(crate) fn collect_bounded<T, I>(env: &Env, items: I, max: u32) -> Vec<T>
+where
+    T: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+    I: IntoIterator<Item = T>,
+{
+    let mut vec = Vec::new(env);
+    for item in items.into_iter().take(max as usize) {
+        vec.push_back(item);
+    }
+    vec
+}
@@ -0,0 +1,14 @@
+//! Client for the opt-in `CommitterDirectory` companion contract.
+//!
+//! Only the call the registry makes is declared, so the registry does not
+//! depend on the directory crate (or its wasm) to build.
+
+use soroban_sdk::{contractclient, Address, Env};
+
+// Only the generated client is used; the trait itself is just its declaration
+#[allow(dead_code)]
+#[contractclient(name = "DirectoryClient")]
+pub trait CommitterDirectory {
+    /// Whether `committer` is currently listed in the directory
+    fn is_listed(env: Env, committer: Address) -> bool;
+}
@@ -0,0 +1,63 @@
+//! Parameter guards shared by every entry point.
+//!
+//! Each guard takes the specific `RegistryError` to return, so callers keep
+//! precise error codes while the checks themselves live in one place.
+
+use soroban_sdk::{Address, BytesN, Env, Vec};
+
+use crate::limits::{MAX_BATCH_SIZE, MAX_READ_BATCH_SIZE};
+use crate::RegistryError;
+
+/// Check if a 32-byte hash is all zeros
+pub(crate) fn is_zero_hash(hash: &BytesN<32>) -> bool {
+    let bytes = hash.to_array();
+    bytes.iter().all(|&b| b == 0)
+}
+
+/// Reject an all-zero hash with `err`
+pub(crate) fn require_nonzero_hash(
+    hash: &BytesN<32>,
+    err: RegistryError,
+) -> Result<(), RegistryError> {
+    if is_zero_hash(hash) {
+        return Err(err);
+    }
+    Ok(())
+}
+
+/// Reject two values that must refer to different things with `err`
+pub(crate) fn require_distinct<T: PartialEq>(
+    a: &T,
+    b: &T,
+    err: RegistryError,
+) -> Result<(), RegistryError> {
+    if a == b {
+        return Err(err);
+    }
+    Ok(())
+}
+
+/// Reject the registry's own address where an external party is expected
+pub(crate) fn require_external(
+    env: &Env,
+    addr: &Address,
+    err: RegistryError,
+) -> Result<(), RegistryError> {
+    require_distinct(addr, &env.current_contract_address(), err)
+}
+
+/// Reject batch write input longer than `max_batch_size` with `TooManyEntries`
+pub(crate) fn require_batch_size<T>(items: &Vec<T>) -> Result<(), RegistryError> {
+    if items.len() > MAX_BATCH_SIZE {
+        return Err(RegistryError::TooManyEntries);
+    }
+    Ok(())
+}
+
+/// Reject batch read input longer than `max_read_batch_size` with `TooManyEntries`
+pub(crate) fn require_read_batch_size<T>(items: &Vec<T>) -> Result<(), RegistryError> {
+    if items.len() > MAX_READ_BATCH_SIZE {
+        return Err(RegistryError::TooManyEntries);
+    }
+    Ok(())
+}
@@ -0,0 +1,180 @@
+//! Interface stability check against committed spec snapshots.
+//!
+//! Reads the `contractspecv0` section of the compiled contract wasm (what
+//! `stellar contract info interface` shows integrators), strips doc
+//! comments, and renders one line per entry:
+//!
+//! * `fn name(arg: Type, ..) -> Type` per function
+//! * `struct Name { field: Type, .. }` per struct (fields are part of the encoding)
+//! * `union Name::Case(..)`, `enum Name::Case = n`, `error Name::Case = n` per case
+//!
+//! The wasm is the artifact named by `STELLA_CONTRACT_WASM` (e.g. the release
+//! build in CI). Without it the check has nothing to read, so its test is
+//! ignored by default and CI runs it after building the wasm.
+//!
+//! Lines in `interface/v{INTERFACE_VERSION}.spec` must still be produced
+//! verbatim. New lines are additions and are allowed, but are only pinned
+//! by the next version's snapshot. Changing or removing a line requires
+//! bumping [`crate::INTERFACE_VERSION`]; `STELLA_BLESS_INTERFACE=1` then
+//! writes the new version's snapshot, leaving the old one as an archive.
+//! Blessing never rewrites an existing snapshot.
+//!
+//! `interface/v1.spec` is the baseline shipped with the first deployed
+//! release; nothing before it was ever deployed.
+
+extern crate std;
+
+use std::format;
+use std::string::String;
+use std::vec::Vec;
+
+use soroban_sdk::xdr::{ScSpecEntry, ScSpecTypeDef, ScSpecUdtUnionCaseV0};
+
+/// Every spec entry embedded in the compiled contract named by `STELLA_CONTRACT_WASM`
+fn contract_spec() -> Vec<ScSpecEntry> {
+    let path = std::env::var("STELLA_CONTRACT_WASM")
+        .expect("STELLA_CONTRACT_WASM must name the compiled contract wasm");
+    let wasm = std::fs::read(&path).unwrap_or_else(|e| panic!("read {}: {}", path, e));
+    soroban_spec::read::from_wasm(&wasm).expect("contractspecv0 section")
+}
+
+fn render_type(ty: &ScSpecTypeDef) -> String {
+    match ty {
+        ScSpecTypeDef::Option(t) => format!("Option<{}>", render_type(&t.value_type)),
+        ScSpecTypeDef::Result(t) => {
+            format!("Result<{}, {}>", render_type(&t.ok_type), render_type(&t.error_type))
+        }
+        ScSpecTypeDef::Vec(t) => format!("Vec<{}>", render_type(&t.element_type)),
+        ScSpecTypeDef::Map(t) => {
+            format!("Map<{}, {}>", render_type(&t.key_type), render_type(&t.value_type))
+        }
+        ScSpecTypeDef::Tuple(t) => {
+            let items: Vec<String> = t.value_types.iter().map(render_type).collect();
+            format!("({})", items.join(", "))
+        }
+        ScSpecTypeDef::BytesN(t) => format!("BytesN<{}>", t.n),
+        ScSpecTypeDef::Udt(t) => t.name.to_utf8_string_lossy(),
+        ScSpecTypeDef::Bool => String::from("bool"),
+        ScSpecTypeDef::Void => String::from("()"),
+        ScSpecTypeDef::U32 => String::from("u32"),
+        ScSpecTypeDef::I32 => String::from("i32"),
+        ScSpecTypeDef::U64 => String::from("u64"),
+        ScSpecTypeDef::I64 => String::from("i64"),
+        ScSpecTypeDef::U128 => String::from("u128"),
+        ScSpecTypeDef::I128 => String::from("i128"),
+        other => format!("{:?}", other),
+    }
+}
+
+/// Normalized `(key, line)` pairs for one spec entry; docs are dropped
+fn render_entry(entry: &ScSpecEntry) -> Vec<(String, String)> {
+    match entry {
+        ScSpecEntry::FunctionV0(f) => {
+            let name = f.name.0.to_utf8_string_lossy();
+            let inputs: Vec<String> = f
+                .inputs
+                .iter()
+                .map(|i| format!("{}: {}", i.name.to_utf8_string_lossy(), render_type(&i.type_)))
+                .collect();
+            let output = f.outputs.iter().map(render_type).next().unwrap_or_else(|| String::from("()"));
+            let key = format!("fn {}", name);
+            std::vec![(key.clone(), format!("{}({}) -> {}", key, inputs.join(", "), output))]
+        }
+        ScSpecEntry::UdtStructV0(s) => {
+            let fields: Vec<String> = s
+                .fields
+                .iter()
+                .map(|f| format!("{}: {}", f.name.to_utf8_string_lossy(), render_type(&f.type_)))
+                .collect();
+            let key = format!("struct {}", s.name.to_utf8_string_lossy());
+            std::vec![(key.clone(), format!("{} {{ {} }}", key, fields.join(", ")))]
+        }
+        ScSpecEntry::UdtUnionV0(u) => {
+            let name = u.name.to_utf8_string_lossy();
+            u.cases
+                .iter()
+                .map(|case| match case {
+                    ScSpecUdtUnionCaseV0::VoidV0(c) => {
+                        let key = format!("union {}::{}", name, c.name.to_utf8_string_lossy());
+                        (key.clone(), key)
+                    }
+                    ScSpecUdtUnionCaseV0::TupleV0(c) => {
+                        let key = format!("union {}::{}", name, c.name.to_utf8_string_lossy());
+                        let items: Vec<String> = c.type_.iter().map(render_type).collect();
+                        (key.clone(), format!("{}({})", key, items.join(", ")))
+                    }
+                })
+                .collect()
+        }
+        ScSpecEntry::UdtEnumV0(e) => {
+            let name = e.name.to_utf8_string_lossy();
+            e.cases
+                .iter()
+                .map(|c| {
+                    let key = format!("enum {}::{}", name, c.name.to_utf8_string_lossy());
+                    (key.clone(), format!("{} = {}", key, c.value))
+                })
+                .collect()
+        }
+        ScSpecEntry::UdtErrorEnumV0(e) => {
+            let name = e.name.to_utf8_string_lossy();
+            e.cases
+                .iter()
+                .map(|c| {
+                    let key = format!("error {}::{}", name, c.name.to_utf8_string_lossy());
+                    (key.clone(), format!("{} = {}", key, c.value))
+                })
+                .collect()
+        }
+    }
+}
+
+/// Normalized lines for the current interface, sorted
+pub(crate) fn current_interface() -> Vec<(String, String)> {
+    let mut lines: Vec<(String, String)> = contract_spec().iter().flat_map(render_entry).collect();
+    lines.sort();
+    lines
+}
+
+/// Compare the current interface against the snapshot for `INTERFACE_VERSION`
+pub(crate) fn check_against_snapshot() {
+    let path = format!(
+        "{}/interface/v{}.spec",
+        env!("CARGO_MANIFEST_DIR"),
+        crate::INTERFACE_VERSION
+    );
+    let bless = std::env::var("STELLA_BLESS_INTERFACE").is_ok();
+    let current = current_interface();
+
+    let snapshot = match std::fs::read_to_string(&path) {
+        Ok(_) if bless => panic!(
+            "interface snapshot {} already exists; blessing only starts the snapshot of a new INTERFACE_VERSION",
+            path
+        ),
+        Ok(text) => text,
+        Err(_) if bless => String::new(),
+        Err(_) => panic!("missing interface snapshot {}; rerun with STELLA_BLESS_INTERFACE=1", path),
+    };
+
+    let mut broken = Vec::new();
+    for line in snapshot.lines().filter(|l| !l.is_empty()) {
+        if !current.iter().any(|(_, current_line)| current_line == line) {
+            broken.push(line);
+        }
+    }
+    assert!(
+        broken.is_empty(),
+        "interface v{} changed or removed entries (bump INTERFACE_VERSION and start a new snapshot):\n{}",
+        crate::INTERFACE_VERSION,
+        broken.join("\n")
+    );
+
+    if bless {
+        let mut text = String::new();
+        for (_, line) in &current {
+            text.push_str(line);
+            text.push('\n');
+        }
+        std::fs::write(&path, text).expect("write interface snapshot");
+    }
+}
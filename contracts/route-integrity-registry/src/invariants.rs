@@ -0,0 +1,200 @@
+//! Cross-checks the registry's counters and indexes against each other.
+//!
+//! Test support for keeping every derived structure in step with the
+//! commitments it describes. [`assert_registry_invariants`] walks the commit
+//! id index, the one structure every stored commitment is appended to, and
+//! rebuilds from it what the committer, rules, solver and day indexes, the
+//! counters, the recent listing and the day digests must hold. A write path
+//! that forgets to update one of them fails the check in every test that
+//! exercises it.
+//!
+//! Unlike [`crate::replay`], nothing here reads events: a structure that
+//! disagrees with the records fails even when its event is consistent.
+
+extern crate std;
+
+use std::vec::Vec as StdVec;
+
+use sha2::{Digest as _, Sha256};
+use soroban_sdk::{Address, BytesN, Env, IntoVal, TryFromVal, Val, Vec};
+
+use crate::{
+    DataKey, IndexSlots, RouteCommitment, RouteIntegrityRegistryClient, StoredCommitment, INDEX_PAGE_SIZE,
+    MAX_RANGE_COMMITS_LISTED, RECENT_CAPACITY, SECONDS_PER_DAY,
+};
+
+/// Groups keyed by `K`, in order of first appearance
+struct Grouped<K> {
+    groups: StdVec<(K, StdVec<BytesN<32>>)>,
+}
+
+impl<K: PartialEq> Grouped<K> {
+    fn new() -> Self {
+        Self { groups: StdVec::new() }
+    }
+
+    fn push(&mut self, key: K, route_hash: &BytesN<32>) {
+        match self.groups.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, hashes)) => hashes.push(route_hash.clone()),
+            None => self.groups.push((key, std::vec![route_hash.clone()])),
+        }
+    }
+}
+
+/// Every entry of a paged listing, fetched `max_range_commits_listed` at a time
+fn list_all<T: Clone + IntoVal<Env, Val> + TryFromVal<Env, Val>>(mut fetch: impl FnMut(u32) -> Vec<T>) -> StdVec<T> {
+    let mut all = StdVec::new();
+    loop {
+        let page = fetch(all.len() as u32);
+        if page.is_empty() {
+            return all;
+        }
+        all.extend(page.iter());
+    }
+}
+
+/// One page of an index, read straight from storage
+fn stored_page(env: &Env, client: &RouteIntegrityRegistryClient, page_key: DataKey) -> Option<Vec<BytesN<32>>> {
+    env.as_contract(&client.address, || env.storage().persistent().get(&page_key))
+}
+
+/// Entry `seq` (1-based) of a paged index, read straight from storage
+fn index_entry(env: &Env, client: &RouteIntegrityRegistryClient, seq: u64, page_key: DataKey) -> Option<BytesN<32>> {
+    stored_page(env, client, page_key)?.get(((seq - 1) % INDEX_PAGE_SIZE as u64) as u32)
+}
+
+/// Page holding entry `seq` (1-based) of a paged index
+fn page(seq: u64) -> u64 {
+    (seq - 1) / INDEX_PAGE_SIZE as u64
+}
+
+/// Assert that every counter and index agrees with the commitments stored
+pub(crate) fn assert_registry_invariants(env: &Env, client: &RouteIntegrityRegistryClient) {
+    let total = client.total_commits();
+    assert_eq!(client.latest_commit_id(), total, "latest id differs from the commit count");
+
+    // The id index lists each commitment once, ids 1..=total with no gaps
+    let by_id = list_all(|offset| client.get_commit_hashes_range(&(offset as u64 + 1), &MAX_RANGE_COMMITS_LISTED));
+    assert_eq!(by_id.len() as u64, total, "id index length differs from the commit count");
+    let past_end = env.as_contract(&client.address, || {
+        env.storage().persistent().has(&DataKey::CommitIdPage(total.div_ceil(INDEX_PAGE_SIZE as u64)))
+    });
+    assert!(!past_end, "id index has a page past the commit count");
+
+    let mut committers: Grouped<Address> = Grouped::new();
+    let mut rules: Grouped<BytesN<32>> = Grouped::new();
+    let mut solvers: Grouped<BytesN<32>> = Grouped::new();
+    let mut days: Grouped<u64> = Grouped::new();
+    let mut digests: StdVec<((Address, u64), [u8; 32])> = StdVec::new();
+    let mut committer_days: StdVec<(Address, (u64, u64))> = StdVec::new();
+
+    for (position, route_hash) in by_id.iter().enumerate() {
+        let id = position as u64 + 1;
+        assert!(!by_id[..position].contains(route_hash), "route hash listed under two ids");
+        let stored: StoredCommitment = env
+            .as_contract(&client.address, || env.storage().persistent().get(&DataKey::Commit(route_hash.clone())))
+            .expect("id index names a missing commitment");
+        let StoredCommitment::V3(commitment, slots) = stored else {
+            panic!("indexed commitment stored without its index slots");
+        };
+        assert_eq!(commitment.commit_id, id, "commitment disagrees with its id");
+        assert_eq!(client.get_commit_by_id(&id), commitment, "id lookup disagrees with the record");
+        check_slots(env, client, route_hash, &commitment, &slots);
+
+        let committer = commitment.committer.clone();
+        committers.push(committer.clone(), route_hash);
+        rules.push(commitment.rules_hash.clone(), route_hash);
+        solvers.push(commitment.solver_version_hash.clone(), route_hash);
+        days.push(slots.day, route_hash);
+
+        match committer_days.iter_mut().find(|(c, _)| *c == committer) {
+            Some((_, (first, last))) => {
+                *first = (*first).min(slots.day);
+                *last = (*last).max(slots.day);
+            }
+            None => committer_days.push((committer.clone(), (slots.day, slots.day))),
+        }
+        let key = (committer, slots.day);
+        let prev = digests.iter().find(|(k, _)| *k == key).map_or([0u8; 32], |(_, d)| *d);
+        let mut hasher = Sha256::new();
+        hasher.update(prev);
+        hasher.update(route_hash.to_array());
+        let next: [u8; 32] = hasher.finalize().into();
+        match digests.iter_mut().find(|(k, _)| *k == key) {
+            Some(entry) => entry.1 = next,
+            None => digests.push((key, next)),
+        }
+    }
+
+    // Committers: counts, stats and the first-commit listing
+    assert_eq!(client.committer_count(), committers.groups.len() as u64, "committer count differs from the records");
+    let listed = list_all(|offset| client.get_committers(&offset, &MAX_RANGE_COMMITS_LISTED));
+    let expected: StdVec<Address> = committers.groups.iter().map(|(c, _)| c.clone()).collect();
+    assert_eq!(listed, expected, "committer list differs from first-commit order");
+    let mut summed = 0;
+    for (committer, hashes) in &committers.groups {
+        let stats = client.get_committer_stats(committer).expect("committer without stats");
+        assert_eq!(stats.count, hashes.len() as u64, "committer count differs from its records");
+        assert_eq!(client.get_commit_count_by_committer(committer), stats.count);
+        let (_, (first_day, last_day)) = committer_days.iter().find(|(c, _)| c == committer).unwrap();
+        assert_eq!(stats.first_ts / SECONDS_PER_DAY, *first_day, "first commit time outside the committer's first day");
+        assert_eq!(stats.last_ts / SECONDS_PER_DAY, *last_day, "last commit time outside the committer's last day");
+        let listed = list_all(|offset| client.get_commits_by_committer(committer, &offset, &MAX_RANGE_COMMITS_LISTED));
+        assert_eq!(&listed, hashes, "committer index differs from its records");
+        let latest = client.latest_commit_by_committer(committer).map(|(route_hash, _)| route_hash);
+        assert_eq!(latest.as_ref(), hashes.last(), "latest commit differs from the committer index");
+        summed += stats.count;
+    }
+    assert_eq!(summed, total, "per-committer counts do not sum to the commit count");
+
+    // Rules, solver and day indexes: each count matches its listing
+    for (rules_hash, hashes) in &rules.groups {
+        assert_eq!(client.count_by_rules(rules_hash), hashes.len() as u64, "rules count differs from the records");
+        let listed = list_all(|offset| client.get_commits_by_rules(rules_hash, &offset, &MAX_RANGE_COMMITS_LISTED));
+        assert_eq!(&listed, hashes, "rules index differs from the records");
+    }
+    for (solver_hash, hashes) in &solvers.groups {
+        assert_eq!(client.count_by_solver(solver_hash), hashes.len() as u64, "solver count differs from the records");
+        let listed = list_all(|offset| client.get_commits_by_solver(solver_hash, &offset, &MAX_RANGE_COMMITS_LISTED));
+        assert_eq!(&listed, hashes, "solver index differs from the records");
+    }
+    for (day, hashes) in &days.groups {
+        assert_eq!(client.commits_on_day(day), hashes.len() as u64, "day count differs from the records");
+        // Day pages have no paged getter; windows and samples read them capped
+        let pages = (hashes.len() as u64).div_ceil(INDEX_PAGE_SIZE as u64);
+        let listed: StdVec<BytesN<32>> = (0..pages)
+            .flat_map(|page| stored_page(env, client, DataKey::DayPage(*day, page)).expect("day page missing"))
+            .collect();
+        assert_eq!(&listed, hashes, "day index differs from the records");
+    }
+
+    // The recent listing is the tail of the id index, newest first
+    let recent: StdVec<BytesN<32>> = client.get_recent(&RECENT_CAPACITY).iter().collect();
+    let tail: StdVec<BytesN<32>> = by_id.iter().rev().take(RECENT_CAPACITY as usize).cloned().collect();
+    assert_eq!(recent, tail, "recent listing differs from the id index");
+
+    // Day digests fold each committer's commitments in id order
+    for ((committer, day), digest) in &digests {
+        let stored = client.get_committer_day_digest(committer, day).map(|d| d.to_array());
+        assert_eq!(stored, Some(*digest), "day digest differs from the records");
+    }
+}
+
+/// The index positions a record claims must hold its route hash
+fn check_slots(
+    env: &Env,
+    client: &RouteIntegrityRegistryClient,
+    route_hash: &BytesN<32>,
+    commitment: &RouteCommitment,
+    slots: &IndexSlots,
+) {
+    let seq = commitment.committer_seq;
+    let by_seq = client.get_commit_by_committer_seq(&commitment.committer, &seq);
+    assert_eq!(&by_seq, route_hash, "committer index slot holds another commitment");
+    let rules_key = DataKey::RulesPage(commitment.rules_hash.clone(), page(slots.rules_seq));
+    assert_eq!(index_entry(env, client, slots.rules_seq, rules_key).as_ref(), Some(route_hash), "rules slot holds another commitment");
+    let solver_key = DataKey::SolverPage(commitment.solver_version_hash.clone(), page(slots.solver_seq));
+    assert_eq!(index_entry(env, client, slots.solver_seq, solver_key).as_ref(), Some(route_hash), "solver slot holds another commitment");
+    let day_key = DataKey::DayPage(slots.day, page(slots.day_seq));
+    assert_eq!(index_entry(env, client, slots.day_seq, day_key).as_ref(), Some(route_hash), "day slot holds another commitment");
+}
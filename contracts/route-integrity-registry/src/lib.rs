@@ -40,16 +40,64 @@
 
 #![no_std]
 
+mod collections;
+mod directory;
+mod guards;
+#[cfg(test)]
+mod interface;
+#[cfg(test)]
+mod invariants;
+mod limits;
+#[cfg(test)]
+mod replay;
+
 use soroban_sdk::{
-    contract, contractimpl, contracttype, contracterror, symbol_short,
-    Address, BytesN, Env, log,
+    contract, contractimpl, contracttype, contracterror, symbol_short, unwrap::UnwrapOptimized, xdr::{FromXdr, ToXdr},
+    Address, Bytes, BytesN, Env, IntoVal, Map, String, Symbol, TryFromVal, Val, Vec, log,
 };
 
-/// Maximum age for a commitment (10 years in seconds) - sanity check
-const MAX_EXPIRY_DURATION: u64 = 315_360_000;
+use limits::{
+    DAY_DIGEST_TTL_LEDGERS, INDEX_PAGE_SIZE, LEDGER_ORDER_TTL_LEDGERS, MAX_ANOMALIES_PER_SUBJECT, MAX_BATCH_SIZE,
+    MAX_CANONICAL_COMMITS_LISTED, MAX_CHILDREN_PER_ROUTE, MAX_CONSUMPTIONS_PER_ROUTE, MAX_ENDORSEMENTS_PER_ROUTE,
+    MAX_EXPIRY_DURATION, MAX_LEDGER_COMMITS_LISTED, MAX_PROFILE_URI_BYTES, MAX_RANGE_COMMITS_LISTED,
+    MAX_READ_BATCH_SIZE, MAX_ROTATION_HOPS, MAX_ROUTE_DEPTH, MAX_SAMPLE_SIZE, MAX_SIGNERS_PER_COMMITTER, MAX_STATS_DAYS, MAX_WINDOW_DAYS, RECENT_CAPACITY, SANDBOX_TTL_LEDGERS,
+};
+
+/// Version of the public contract interface (function signatures and types)
+///
+/// Bump when an existing function or contract type changes incompatibly;
+/// `interface/v{N}.spec` pins the interface of each version.
+pub const INTERFACE_VERSION: u32 = 1;
+
+/// Length of a digest day in seconds (days are `timestamp / SECONDS_PER_DAY`)
+const SECONDS_PER_DAY: u64 = 86_400;
+
+// Build provenance, embedded as contract metadata (see build.rs)
+include!(concat!(env!("OUT_DIR"), "/build_meta.rs"));
+
+/// `git describe --always --dirty` of the source tree this wasm was built from
+const BUILD_GIT: &str = env!("STELLA_BUILD_GIT");
+
+/// Cargo profile the contract was built with
+const BUILD_PROFILE: &str = env!("STELLA_BUILD_PROFILE");
+
+/// Enabled cargo features, comma-separated (empty if none)
+const BUILD_FEATURES: &str = env!("STELLA_BUILD_FEATURES");
+
+/// `rustc --version` of the compiler used
+const BUILD_RUSTC: &str = env!("STELLA_BUILD_RUSTC");
+
+/// Domain tag prefixed to attestation payloads; the suffix versions the byte layout
+const ATTESTATION_DOMAIN: &[u8; 16] = b"stella.attest.v1";
+
+/// Domain tag prefixed to `commit_route_signed` messages; the suffix versions the byte layout
+const SIGNED_COMMIT_DOMAIN: &[u8; 16] = b"stella.signed.v1";
+
+/// Domain tag prefixed to `register_signer` messages; the suffix versions the byte layout
+const SIGNER_REGISTRATION_DOMAIN: &[u8; 16] = b"stella.signer.v1";
 
-/// Storage key prefix for route commitments
-const COMMIT_PREFIX: &str = "commit";
+/// `remaining_commits` of a grant that is never counted down
+pub const UNLIMITED_COMMITS: u32 = u32::MAX;
 
 /// Commitment metadata stored for each route
 ///
@@ -73,425 +121,10050 @@ pub struct RouteCommitment {
     /// Optional expiry timestamp (0 = no expiry)
     /// Indicates how long the quoted route remains valid
     pub expiry: u64,
+    
+    /// Ledger timestamp from which the commitment counts as active
+    /// Equals `timestamp` unless committed via `commit_route_scheduled`
+    pub active_from: u64,
+    
+    /// Position among the commitments recorded in the same ledger (0, 1, 2, ...)
+    /// Orders same-timestamp commits; always 0 for sandbox commitments
+    pub intra_ledger_index: u32,
+    
+    /// Position among this committer's commitments (1, 2, 3, ...)
+    /// Contiguous per committer, so gaps in an off-chain log are detectable; 0 for sandbox commitments
+    pub committer_seq: u64,
+    
+    /// Registry-wide id (1, 2, 3, ...), assigned in storage order and never reused
+    /// Contiguous across all committers; 0 for sandbox commitments
+    pub commit_id: u64,
+}
+
+/// A commitment read stamped with the ledger it was read at
+///
+/// Lets wallets caching read results judge staleness against chain head.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CommitAt {
+    /// The commitment as stored
+    pub value: RouteCommitment,
+    
+    /// Ledger sequence the read executed in
+    pub at_ledger: u32,
+    
+    /// Ledger timestamp the read executed at
+    pub at_timestamp: u64,
+}
+
+/// One-call snapshot of a commitment's state
+///
+/// Returned by `get_commit_summary`; a missing route is `exists: false`
+/// with every other field zeroed.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CommitSummary {
+    /// A full commitment exists for the route hash
+    pub exists: bool,
+    
+    /// The quote's expiry has passed at the current ledger time
+    pub expired: bool,
+    
+    /// The committer revoked the commitment
+    pub revoked: bool,
+    
+    /// Ledger timestamp when the commitment was created
+    pub timestamp: u64,
+}
+
+/// Build provenance of the deployed wasm
+///
+/// Mirrors the `stella_*` contract metadata entries embedded at compile time.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BuildInfo {
+    /// `git describe --always --dirty` of the source tree
+    pub git: String,
+    
+    /// Cargo profile (e.g. "release")
+    pub profile: String,
+    
+    /// Enabled cargo features, comma-separated
+    pub features: String,
+    
+    /// Compiler version
+    pub rustc: String,
+}
+
+/// Every storage key the registry writes
+///
+/// One enum for all entries, so the variant discriminant keeps keys with
+/// identical payloads (e.g. a route hash) from ever colliding.
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// Route commitment (persistent)
+    Commit(BytesN<32>),
+    /// Sandbox route commitment (temporary)
+    Sandbox(BytesN<32>),
+    /// Secondary (keccak-256) digest of a dual-hashed route (persistent)
+    SecondaryHash(BytesN<32>),
+    /// Relayer that submitted a commitment on a committer's behalf (persistent)
+    Relayer(BytesN<32>),
+    /// Existence-only record (persistent)
+    Existence(BytesN<32>),
+    /// Number of commitments recorded in a ledger sequence (temporary)
+    LedgerCount(u32),
+    /// Page of route hashes committed in one ledger, by (sequence, page) (temporary)
+    LedgerPage(u32, u32),
+    /// Committer's rolling digest for a day (temporary)
+    DayDigest(Address, u64),
+    /// Anomaly reports filed against a subject (persistent)
+    Anomalies(AnomalySubject),
+    /// Corridor dictionary entry by symbol (persistent)
+    Corridor(Symbol),
+    /// Corridor symbol registered for a (source, dest) asset pair (persistent)
+    CorridorPair(BytesN<32>, BytesN<32>),
+    /// Number of event-only commitments emitted so far (instance)
+    EventOnlyCount,
+    /// Number of full commitments stored so far (instance)
+    TotalCommits,
+    /// Consumption markers left on a commitment (persistent)
+    Consumptions(BytesN<32>),
+    /// Grant held by an (owner, delegate) delegation (persistent)
+    Delegation(Address, Address),
+    /// Ed25519 key that signed a commitment's payload (persistent)
+    Signer(BytesN<32>),
+    /// Endorsers of a commitment, in endorsement order (persistent)
+    Endorsers(BytesN<32>),
+    /// Timestamp of one endorser's endorsement of a commitment (persistent)
+    Endorsement(BytesN<32>, Address),
+    /// Committer's self-published display profile (persistent)
+    Profile(Address),
+    /// Page of a committer's route hashes in sequence order (persistent)
+    CommitterPage(Address, u64),
+    /// Count and first/last commit times of a committer (persistent)
+    CommitterStats(Address),
+    /// Number of distinct committers (persistent)
+    CommitterListCount,
+    /// Page of distinct committers, in first-commit order (persistent)
+    CommitterListPage(u64),
+    /// Page of route hashes by registry-wide id (persistent)
+    CommitIdPage(u64),
+    /// Number of commitments made under a rules hash (persistent)
+    RulesCount(BytesN<32>),
+    /// Page of route hashes committed under a rules hash (persistent)
+    RulesPage(BytesN<32>, u64),
+    /// Number of commitments made under a solver version hash (persistent)
+    SolverCount(BytesN<32>),
+    /// Page of route hashes committed under a solver version hash (persistent)
+    SolverPage(BytesN<32>, u64),
+    /// Number of commitments stored on a day (persistent)
+    DayCount(u64),
+    /// Page of route hashes stored on a day (persistent)
+    DayPage(u64, u64),
+    /// Committer's revocation marker for a commitment (persistent)
+    Revocation(BytesN<32>),
+    /// Route hash of the commitment that superseded this one (persistent)
+    Successor(BytesN<32>),
+    /// Route hash of the commitment this one superseded (persistent)
+    Predecessor(BytesN<32>),
+    /// Address a committer key was rotated to (persistent)
+    CommitterSuccessor(Address),
+    /// Address a committer key was rotated from (persistent)
+    CommitterPredecessor(Address),
+    /// Timestamp of a user's acknowledgment of a quoted route (persistent)
+    Ack(BytesN<32>, Address),
+    /// Hash binding a commitment to its intended recipient (persistent)
+    UserBinding(BytesN<32>),
+    /// Timestamp of an attester's latest sign-off on a commitment (persistent)
+    Attestation(BytesN<32>, Address),
+    /// Batch commitment by Merkle root (persistent)
+    Batch(BytesN<32>),
+    /// Blinded commitment awaiting its reveal (persistent)
+    Blinded(BytesN<32>),
+    /// Timestamp of the committer's cancellation of a quote (persistent)
+    Cancellation(BytesN<32>),
+    /// Committer an offline signing key was registered to (persistent)
+    SignerKey(BytesN<32>),
+    /// Timestamp an offline signing key was deactivated (persistent)
+    SignerDeactivation(BytesN<32>),
+    /// Offline signing keys a committer registered, in order (persistent)
+    SignerKeys(Address),
+    /// Child routes a parent route was split into, in order (persistent)
+    Children(BytesN<32>),
+    /// Parent route a child route was split from (persistent)
+    Parent(BytesN<32>),
+    /// Scope a delegate declared for a commitment (persistent)
+    Scope(BytesN<32>),
 }
 
-/// Storage key for a route commitment
+/// Pre-`DataKey` storage key for a route commitment
+///
+/// Read-only: commitments written before the `DataKey` scheme remain
+/// readable through it, but nothing is written under this shape anymore.
 #[contracttype]
 #[derive(Clone)]
-pub struct CommitKey {
+pub struct LegacyCommitKey {
     pub route_hash: BytesN<32>,
 }
 
-/// Contract error codes
-#[contracterror]
+/// The original five-field commitment record
+///
+/// Frozen: host structs only decode into a type with exactly the same
+/// fields, so this keeps records written under `LegacyCommitKey`, and as
+/// `StoredCommitment::V1`, readable.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyRouteCommitment {
+    pub rules_hash: BytesN<32>,
+    pub solver_version_hash: BytesN<32>,
+    pub committer: Address,
+    pub timestamp: u64,
+    pub expiry: u64,
+}
+
+impl From<LegacyRouteCommitment> for RouteCommitment {
+    /// Fill the fields a legacy record predates: active from its timestamp,
+    /// with no ledger position, committer sequence or commit id
+    fn from(legacy: LegacyRouteCommitment) -> Self {
+        RouteCommitment {
+            rules_hash: legacy.rules_hash,
+            solver_version_hash: legacy.solver_version_hash,
+            committer: legacy.committer,
+            timestamp: legacy.timestamp,
+            expiry: legacy.expiry,
+            active_from: legacy.timestamp,
+            intra_ledger_index: 0,
+            committer_seq: 0,
+            commit_id: 0,
+        }
+    }
+}
+
+/// Commitment record as persisted under `DataKey::Commit`
+///
+/// Versioned so `RouteCommitment` can gain fields without stranding the
+/// records already stored: a new layout gets a new variant, and reads
+/// upgrade older variants to the current struct.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StoredCommitment {
+    /// The original five-field layout
+    V1(LegacyRouteCommitment),
+    /// The layout with activation, ordering and id fields
+    V2(RouteCommitment),
+    /// The current layout: `V2` plus the commitment's index positions
+    V3(RouteCommitment, IndexSlots),
+}
+
+impl From<StoredCommitment> for RouteCommitment {
+    fn from(stored: StoredCommitment) -> Self {
+        match stored {
+            StoredCommitment::V1(legacy) => legacy.into(),
+            StoredCommitment::V2(commitment) | StoredCommitment::V3(commitment, _) => commitment,
+        }
+    }
+}
+
+/// Where a commitment sits in the rules, solver and day indexes
+///
+/// Kept beside the record, at no extra write, so `extend_ttls` can find
+/// the index pages that list the commitment.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IndexSlots {
+    /// Sequence number in its rules hash's index
+    pub rules_seq: u64,
+    
+    /// Sequence number in its solver version hash's index
+    pub solver_seq: u64,
+    
+    /// Day the commitment was stored on
+    pub day: u64,
+    
+    /// Sequence number in that day's index
+    pub day_seq: u64,
+}
+
+/// Minimal existence-only record: who claimed a route hash, and when
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExistenceRecord {
+    /// Address that claimed the route hash
+    pub committer: Address,
+    
+    /// Ledger timestamp when the claim was recorded
+    pub timestamp: u64,
+}
+
+/// A blinded commitment awaiting its reveal
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BlindedRecord {
+    /// Address that committed the blinded digest
+    pub committer: Address,
+    
+    /// Ledger timestamp when the blinded digest was committed
+    pub timestamp: u64,
+    
+    /// Unix timestamp after which the digest can no longer be revealed (0 = no expiry)
+    pub expiry: u64,
+}
+
+/// A committer's public withdrawal of one of its commitments
+///
+/// Stored beside the commitment, which itself is never modified.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RevocationRecord {
+    /// Hash of the off-chain explanation (e.g. stale liquidity report)
+    pub reason_hash: BytesN<32>,
+    
+    /// Ledger timestamp when the revocation was recorded
+    pub timestamp: u64,
+}
+
+/// A delegate's permission to commit on an owner's behalf
+///
+/// `approve_delegate` grants it without restrictions; `grant_delegate` can
+/// bound it in time, in scope and in number of commits.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DelegationGrant {
+    /// Address allowed to commit for the owner
+    pub delegate: Address,
+    
+    /// Ledger timestamp when the grant was made
+    pub approved_at: u64,
+    
+    /// Unix timestamp the grant stops working at (0 = never)
+    pub expires_at: u64,
+    
+    /// Only commitments declared in this namespace, when set
+    pub allowed_namespace: Option<Symbol>,
+    
+    /// Only commitments declared for this corridor, when set
+    pub allowed_corridor: Option<Symbol>,
+    
+    /// Commits left; `UNLIMITED_COMMITS` is never counted down
+    pub remaining_commits: u32,
+}
+
+/// Scope a delegate declares for a commitment, checked against its grant
+///
+/// Stored beside the commitment so auditors can hold the declaration
+/// against the route manifest.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CommitScope {
+    /// Owner-defined namespace, e.g. a product line
+    pub namespace: Symbol,
+    
+    /// Registered corridor symbol the route serves
+    pub corridor: Symbol,
+}
+
+/// An offline signing key registered to a committer
+///
+/// Written once; deactivation is a separate marker beside it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignerRegistration {
+    /// Committer credited with the key's signed commitments
+    pub committer: Address,
+    
+    /// Ledger timestamp when the key was registered
+    pub registered_at: u64,
+}
+
+/// Aggregate activity of one committer
+///
+/// Timestamps are the ledger times the commitments were stored, so a
+/// backdated upgrade or reveal counts from when it happened.
+#[contracttype]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
-#[repr(u32)]
-pub enum RegistryError {
-    /// Route hash cannot be all zeros
-    EmptyRouteHash = 1,
-    /// A commitment for this route_hash already exists
-    DuplicateCommitment = 2,
-    /// Expiry timestamp must be in the future
-    ExpiredTimestamp = 3,
-    /// Expiry too far in the future (sanity check)
-    ExpiryTooFar = 4,
-    /// Commitment not found
-    NotFound = 5,
+pub struct CommitterStats {
+    /// Number of full commitments recorded
+    pub count: u64,
+    
+    /// Ledger timestamp of the first commitment
+    pub first_ts: u64,
+    
+    /// Ledger timestamp of the latest commitment
+    pub last_ts: u64,
 }
 
-/// # RouteIntegrityRegistry Contract
+/// Display profile a committer publishes about itself
 ///
-/// Immutable, non-custodial registry for routing transparency.
+/// Purely informational for wallets; commitments never depend on it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CommitterProfile {
+    /// Hash of the committer's display name
+    pub name_hash: BytesN<32>,
+    
+    /// URI of the committer's off-chain metadata document
+    pub metadata_uri: Bytes,
+    
+    /// Ledger timestamp of the last registration or update
+    pub updated_at: u64,
+}
+
+/// A registered corridor in the canonical corridor dictionary
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CorridorEntry {
+    /// Address that registered the corridor
+    pub registrar: Address,
+    
+    /// Hash identifying the source asset
+    pub source_asset_hash: BytesN<32>,
+    
+    /// Hash identifying the destination asset
+    pub dest_asset_hash: BytesN<32>,
+    
+    /// Ledger timestamp when the corridor was registered
+    pub registered_at: u64,
+}
+
+/// Subject of a watchtower anomaly report
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AnomalySubject {
+    /// A specific committed (or claimed) route hash
+    Route(BytesN<32>),
+    /// A committer address
+    Committer(Address),
+}
+
+/// A single anomaly report from a third-party watchtower
 ///
-/// ## Security Properties
+/// The registry stores reports verbatim and never adjudicates them.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AnomalyReport {
+    /// Watchtower that filed the report
+    pub reporter: Address,
+    
+    /// Hash of the off-chain evidence backing the report
+    pub evidence_hash: BytesN<32>,
+    
+    /// Ledger timestamp when the report was recorded
+    pub timestamp: u64,
+}
+
+/// One consumer's once-only use of a commitment
 ///
-/// - **No admin**: Zero privileged functions
-/// - **No upgrades**: Contract is final once deployed
-/// - **No custody**: Cannot hold or transfer any assets
-/// - **Append-only**: Commitments cannot be modified or deleted
-/// - **Open access**: Anyone can commit routes
-#[contract]
-pub struct RouteIntegrityRegistry;
+/// The registry only records the marker; what "consumed" means (an escrow
+/// release, a payout) is up to the consumer.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConsumptionRecord {
+    /// Address or contract that consumed the commitment
+    pub consumer: Address,
+    
+    /// Consumer-side reference for the use (e.g. an escrow id hash)
+    pub consumption_ref: BytesN<32>,
+    
+    /// Ledger timestamp when the consumption was recorded
+    pub at: u64,
+}
 
-#[contractimpl]
-impl RouteIntegrityRegistry {
-    /// Commit routing metadata to the public registry.
-    ///
-    /// # Arguments
-    ///
-    /// * `env` - Soroban environment
-    /// * `route_hash` - SHA-256 hash of the complete route manifest
-    /// * `rules_hash` - SHA-256 hash of the routing rules configuration
-    /// * `solver_version_hash` - SHA-256 hash of the solver version/commit
-    /// * `expiry` - Unix timestamp when quote expires (0 = no expiry)
-    ///
-    /// # Returns
-    ///
-    /// * `Ok(())` on success
-    /// * `Err(RegistryError)` on validation failure
-    ///
-    /// # Events
-    ///
-    /// Emits `RouteCommitted` with all commitment data
+/// Quote expiry of a commitment
+///
+/// Replaces the legacy `expiry: u64` convention where `0` means "never".
+/// Legacy entry points and the stored `RouteCommitment` keep the raw `u64`
+/// and convert through [`Expiry::from_raw`] / [`Expiry::to_raw`] only.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Expiry {
+    /// Quote remains valid indefinitely
+    Never,
+    /// Quote expires at this ledger timestamp
+    At(u64),
+}
+
+impl Expiry {
+    /// Convert a legacy raw expiry (`0` = no expiry)
+    pub fn from_raw(raw: u64) -> Self {
+        match raw {
+            0 => Expiry::Never,
+            ts => Expiry::At(ts),
+        }
+    }
+    
+    /// Whether the expiry has passed at ledger time `now`
     ///
-    /// # Errors
+    /// An expiry equal to `now` counts as expired, matching commit-time
+    /// validation which rejects `expiry <= timestamp`.
+    pub fn is_expired_at(self, now: u64) -> bool {
+        match self {
+            Expiry::Never => false,
+            Expiry::At(ts) => ts <= now,
+        }
+    }
+    
+    /// Convert back to the legacy raw expiry (`0` = no expiry)
     ///
-    /// * `EmptyRouteHash` - route_hash is all zeros
-    /// * `DuplicateCommitment` - route_hash already committed
-    /// * `ExpiredTimestamp` - expiry is in the past
-    /// * `ExpiryTooFar` - expiry exceeds maximum duration
-    pub fn commit_route(
-        env: Env,
-        route_hash: BytesN<32>,
-        rules_hash: BytesN<32>,
-        solver_version_hash: BytesN<32>,
-        expiry: u64,
-    ) -> Result<(), RegistryError> {
-        // Get current ledger timestamp
-        let timestamp = env.ledger().timestamp();
-        
-        // Validate: route_hash must not be empty (all zeros)
-        if Self::is_zero_hash(&route_hash) {
-            log!(&env, "Rejected: empty route_hash");
-            return Err(RegistryError::EmptyRouteHash);
+    /// `At(0)` can never pass validation, so the round trip is lossless
+    /// for every stored value.
+    pub fn to_raw(self) -> u64 {
+        match self {
+            Expiry::Never => 0,
+            Expiry::At(ts) => ts,
+        }
+    }
+}
+
+/// Lifecycle status of a commitment at a given ledger time
+///
+/// When several conditions hold at once the highest-precedence status wins:
+/// `Revoked` > `Cancelled` > `Expired` > `Scheduled` > `Active`. Every status-reporting
+/// entry point derives it through the same internal function.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CommitmentStatus {
+    /// Committed, but `active_from` has not been reached yet
+    Scheduled,
+    /// Inside its validity window
+    Active,
+    /// Expiry has passed
+    Expired,
+    /// Withdrawn by its committer
+    Revoked,
+    /// Abandoned quote, cancelled by its committer before expiry
+    Cancelled,
+}
+
+/// One commitment of a batch: the three hashes and the expiry
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CommitEntry {
+    /// SHA-256 hash of the complete route manifest
+    pub route_hash: BytesN<32>,
+    
+    /// SHA-256 hash of the routing rules configuration
+    pub rules_hash: BytesN<32>,
+    
+    /// SHA-256 hash of the solver version/commit
+    pub solver_version_hash: BytesN<32>,
+    
+    /// Unix timestamp when the quote expires (0 = no expiry)
+    pub expiry: u64,
+}
+
+/// The two hashes every full commitment carries
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CoreHashes {
+    /// SHA-256 hash of the routing rules configuration
+    pub rules_hash: BytesN<32>,
+    
+    /// SHA-256 hash of the solver version/commit
+    pub solver_version_hash: BytesN<32>,
+}
+
+/// Relative expiry option: seconds from the commit's ledger time
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TtlOption {
+    /// No relative expiry
+    Unset,
+    /// Expire this many seconds after the commit
+    Seconds(u64),
+}
+
+/// Activation option of a commitment
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ScheduleOption {
+    /// Active as soon as it is recorded
+    Immediate,
+    /// Active from this ledger timestamp
+    From(u64),
+}
+
+/// Secondary digest option for collision insurance
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SecondaryHashOption {
+    /// Single-hash commitment
+    Unset,
+    /// keccak-256 of the same manifest bytes
+    Keccak(BytesN<32>),
+}
+
+/// Optional settings of the consolidated `commit` entry point
+///
+/// Validated as a whole: `expiry` and `ttl` are mutually exclusive.
+/// `CommitOptions::default()` gives a plain, never-expiring commitment.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CommitOptions {
+    /// Absolute expiry
+    pub expiry: Expiry,
+    
+    /// Relative expiry; conflicts with an `expiry` other than `Never`
+    pub ttl: TtlOption,
+    
+    /// When the commitment becomes active
+    pub schedule: ScheduleOption,
+    
+    /// Second independent route digest
+    pub secondary_hash: SecondaryHashOption,
+}
+
+impl Default for CommitOptions {
+    fn default() -> Self {
+        CommitOptions {
+            expiry: Expiry::Never,
+            ttl: TtlOption::Unset,
+            schedule: ScheduleOption::Immediate,
+            secondary_hash: SecondaryHashOption::Unset,
+        }
+    }
+}
+
+/// Window during which a commitment counts as active
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ValidityWindow {
+    /// Ledger timestamp from which the commitment is active (inclusive)
+    pub active_from: u64,
+    
+    /// When the commitment stops being active
+    pub expiry: Expiry,
+}
+
+/// Expected value of a hash field in a partial verification
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum HashExpectation {
+    /// Caller does not hold this field; skip it
+    Ignore,
+    /// Field must equal this hash
+    Equals(BytesN<32>),
+}
+
+/// Expected value of an address field in a partial verification
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AddressExpectation {
+    /// Caller does not hold this field; skip it
+    Ignore,
+    /// Field must equal this address
+    Equals(Address),
+}
+
+/// One `verify_commit` check of a batch
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VerifyItem {
+    /// Commitment to check
+    pub route_hash: BytesN<32>,
+    
+    /// Rules hash the commitment must carry
+    pub expected_rules_hash: BytesN<32>,
+    
+    /// Solver version hash the commitment must carry
+    pub expected_solver_hash: BytesN<32>,
+}
+
+/// Fields a caller wants checked against a commitment
+///
+/// `Ignore` fields are skipped; at least one must be `Equals`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PartialExpectation {
+    /// Expected rules hash, if the caller holds the rules document
+    pub rules_hash: HashExpectation,
+    
+    /// Expected solver version hash
+    pub solver_version_hash: HashExpectation,
+    
+    /// Expected committer address
+    pub committer: AddressExpectation,
+}
+
+/// A commitment to a Merkle root covering a batch of route hashes
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchCommitment {
+    /// SHA-256 hash of the routing rules configuration
+    pub rules_hash: BytesN<32>,
+    
+    /// SHA-256 hash of the solver version/commit
+    pub solver_version_hash: BytesN<32>,
+    
+    /// Address that committed the batch
+    pub committer: Address,
+    
+    /// Ledger timestamp when committed
+    pub timestamp: u64,
+    
+    /// Unix timestamp when the batch's quotes expire (0 = no expiry)
+    pub expiry: u64,
+    
+    /// Number of route hashes (leaves) under the root
+    pub leaf_count: u32,
+}
+
+/// Outcome of one entry of a best-effort batch commit
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CommitResult {
+    /// Stored, at this ledger timestamp
+    Committed(u64),
+    /// Not stored; carries the `RegistryError` code the entry failed with
+    Failed(u32),
+}
+
+/// Outcome of a field-by-field verification
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VerifyResult {
+    /// Every checked field matches
+    Match,
+    /// No commitment exists for the route hash
+    NotFound,
+    /// Only an existence record exists; there are no fields to compare
+    ExistenceOnly,
+    /// Stored rules hash differs
+    RulesMismatch,
+    /// Stored solver version hash differs
+    SolverMismatch,
+    /// Stored committer differs
+    CommitterMismatch,
+}
+
+/// Contract error codes
+///
+/// Append-only: a code, once assigned, keeps its meaning. Retired codes stay
+/// reserved rather than being handed to another variant. A contract spec
+/// holds at most 50 error cases and this enum uses all of them, so a new
+/// failure has to be reported under the closest existing code.
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum RegistryError {
+    /// Route hash cannot be all zeros
+    EmptyRouteHash = 1,
+    /// A commitment for this route_hash already exists
+    DuplicateCommitment = 2,
+    /// Expiry timestamp must be in the future
+    ExpiredTimestamp = 3,
+    /// Expiry too far in the future (sanity check)
+    ExpiryTooFar = 4,
+    /// Commitment not found
+    NotFound = 5,
+    /// This reporter already filed an anomaly against the subject
+    AnomalyAlreadyReported = 6,
+    /// The subject already holds the maximum number of anomaly reports
+    TooManyAnomalies = 7,
+    /// Rules hash cannot be all zeros
+    EmptyRulesHash = 8,
+    /// Solver version hash cannot be all zeros
+    EmptySolverHash = 9,
+    /// Evidence hash cannot be all zeros
+    EmptyEvidenceHash = 10,
+    /// Address parameters that must differ refer to the same party
+    SelfReferentialAddress = 11,
+    /// Activation must satisfy `timestamp <= active_from < expiry`
+    InvalidActivationWindow = 12,
+    /// Activation too far in the future (sanity check)
+    ActivationTooFar = 13,
+    /// Secondary (keccak-256) route hash cannot be all zeros
+    EmptySecondaryHash = 14,
+    /// The two independent route digests must differ
+    DualHashesEqual = 15,
+    // 16 was `CommitterMismatch`, replaced by `Unauthorized`; reserved, never reuse
+    /// Relayer and committer are the same address
+    SelfRelay = 17,
+    /// Corridor asset hash cannot be all zeros
+    EmptyAssetHash = 18,
+    /// Corridor source and destination assets are the same
+    IdenticalCorridorAssets = 19,
+    /// Corridor symbol is already registered
+    CorridorAlreadyRegistered = 20,
+    /// Asset pair is already registered under another corridor symbol
+    CorridorPairAlreadyRegistered = 21,
+    /// Partial verification requested with no fields to check
+    EmptyExpectation = 22,
+    /// Commit options contradict each other (e.g. both expiry and ttl)
+    ConflictingOptions = 23,
+    /// Consumption reference cannot be all zeros
+    EmptyConsumptionRef = 24,
+    /// Commitment is scheduled, expired or revoked, so it cannot be used
+    CommitmentNotActive = 25,
+    /// This consumer already consumed the commitment
+    AlreadyConsumed = 26,
+    /// The commitment already holds the maximum number of consumption markers
+    TooManyConsumptions = 27,
+    /// Caller is not the party the stored record allows to act: not the
+    /// original committer, or not a delegate of the owner it acts for
+    Unauthorized = 28,
+    /// The commitment already holds the maximum number of endorsements
+    TooManyEndorsements = 29,
+    /// Profile name hash cannot be all zeros
+    EmptyNameHash = 30,
+    /// Profile metadata URI exceeds the maximum length
+    ProfileUriTooLong = 31,
+    /// A commitment counter or index cannot be incremented further
+    CommitCountOverflow = 32,
+    /// Revocation reason hash cannot be all zeros
+    EmptyReasonHash = 33,
+    /// The commitment has already been revoked
+    AlreadyRevoked = 34,
+    /// The commitment has already been superseded
+    AlreadySuperseded = 35,
+    /// A rotating key already has a successor, or the new key is already part of a rotation
+    AlreadyRotated = 36,
+    /// The rotation would make the key chain longer than `max_rotation_hops`
+    TooManyRotations = 37,
+    /// The commitment's quote (or a blinded digest's reveal window) has expired
+    QuoteExpired = 38,
+    /// The batch holds more entries than `max_batch_size` (writes) or
+    /// `max_read_batch_size` (reads)
+    TooManyEntries = 39,
+    /// A batch root must cover at least one leaf, a split at least one
+    /// child, and a delegation grant at least one commit
+    EmptyBatch = 40,
+    /// The revealed preimage does not hash to the blinded digest
+    RevealMismatch = 41,
+    /// Requested TTL exceeds the network's maximum entry TTL
+    TtlTooLong = 42,
+    /// More recent commitments were requested than `recent_capacity`
+    RecentCapacityExceeded = 43,
+    /// Window end is before its start
+    InvalidWindow = 44,
+    /// Window spans more days than `max_window_days`
+    WindowTooLong = 45,
+    /// The commitment has expired or been revoked, so it cannot be cancelled
+    AlreadyInactive = 46,
+    /// The quote has already been cancelled
+    AlreadyCancelled = 47,
+    /// The signing key is already registered to a committer
+    SignerAlreadyRegistered = 48,
+    /// The signing key has been deactivated
+    SignerDeactivated = 49,
+    /// The committer already registered `max_signers_per_committer` keys
+    TooManySigners = 50,
+    /// A split would give the parent more than `max_children_per_route`
+    /// children, or the children more than `max_route_depth` ancestors
+    FamilyLimitExceeded = 51,
+}
+
+/// Why `verify_commit_detailed` rejected a commitment
+///
+/// A contract error rather than a `contracttype` so it can be the error
+/// side of the returned `Result`. Codes are independent of `RegistryError`.
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum VerifyFailure {
+    /// No full commitment exists for the route hash
+    NotFound = 1,
+    /// Stored rules hash differs
+    RulesMismatch = 2,
+    /// Stored solver version hash differs
+    SolverMismatch = 3,
+    /// The committer revoked the commitment
+    Revoked = 4,
+    /// The quote's expiry has passed
+    Expired = 5,
+    /// `active_from` has not been reached yet
+    Scheduled = 6,
+    /// The committer cancelled the quote
+    Cancelled = 7,
+}
+
+/// # RouteIntegrityRegistry Contract
+///
+/// Immutable, non-custodial registry for routing transparency.
+///
+/// ## Security Properties
+///
+/// - **No admin**: Zero privileged functions
+/// - **No upgrades**: Contract is final once deployed
+/// - **No custody**: Cannot hold or transfer any assets
+/// - **Append-only**: Commitments cannot be modified or deleted
+/// - **Open access**: Anyone can commit routes
+#[contract]
+pub struct RouteIntegrityRegistry;
+
+#[contractimpl]
+impl RouteIntegrityRegistry {
+    /// Commit routing metadata to the public registry.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Soroban environment
+    /// * `committer` - Address credited with the commitment (must authorize)
+    /// * `route_hash` - SHA-256 hash of the complete route manifest
+    /// * `rules_hash` - SHA-256 hash of the routing rules configuration
+    /// * `solver_version_hash` - SHA-256 hash of the solver version/commit
+    /// * `expiry` - Unix timestamp when quote expires (0 = no expiry)
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(seq)` - the commitment's per-committer sequence number (1, 2, 3, ...);
+    ///   every full-commit entry point returns it
+    /// * `Err(RegistryError)` on validation failure
+    ///
+    /// # Events
+    ///
+    /// Emits `RouteCommitted` with all commitment data
+    ///
+    /// # Errors
+    ///
+    /// * `EmptyRouteHash` - route_hash is all zeros
+    /// * `EmptyRulesHash` / `EmptySolverHash` - rules or solver hash is all zeros
+    /// * `DuplicateCommitment` - route_hash already committed
+    /// * `ExpiredTimestamp` - expiry is in the past
+    /// * `ExpiryTooFar` - expiry exceeds maximum duration
+    pub fn commit_route(
+        env: Env,
+        committer: Address,
+        route_hash: BytesN<32>,
+        rules_hash: BytesN<32>,
+        solver_version_hash: BytesN<32>,
+        expiry: u64,
+    ) -> Result<u64, RegistryError> {
+        committer.require_auth();
+        
+        let options = CommitOptions { expiry: Expiry::from_raw(expiry), ..Default::default() };
+        Self::commit_with_options(
+            &env,
+            committer,
+            route_hash,
+            CoreHashes { rules_hash, solver_version_hash },
+            options,
+        )
+    }
+    
+    /// Commit on behalf of a named committer, submitted by an operator key.
+    ///
+    /// For infrastructure that signs transactions with a hot operator key
+    /// while commitments are attributed to a well-known protocol address:
+    /// `committer` must have pre-authorized this exact call (e.g. an auth
+    /// entry signed offline) and is stored as the committer of record,
+    /// never the transaction source. Otherwise identical to `commit_route`.
+    ///
+    /// # Errors
+    ///
+    /// Same as `commit_route`
+    pub fn commit_route_for(
+        env: Env,
+        committer: Address,
+        route_hash: BytesN<32>,
+        rules_hash: BytesN<32>,
+        solver_version_hash: BytesN<32>,
+        expiry: u64,
+    ) -> Result<u64, RegistryError> {
+        committer.require_auth();
+        
+        let options = CommitOptions { expiry: Expiry::from_raw(expiry), ..Default::default() };
+        Self::commit_with_options(
+            &env,
+            committer,
+            route_hash,
+            CoreHashes { rules_hash, solver_version_hash },
+            options,
+        )
+    }
+    
+    /// Commit routing metadata with an explicit [`Expiry`].
+    ///
+    /// Same as `commit_route`, but takes `Expiry::Never` / `Expiry::At(ts)`
+    /// instead of the legacy zero-means-forever `u64`.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Soroban environment
+    /// * `committer` - Address credited with the commitment (must authorize)
+    /// * `route_hash` - SHA-256 hash of the complete route manifest
+    /// * `rules_hash` - SHA-256 hash of the routing rules configuration
+    /// * `solver_version_hash` - SHA-256 hash of the solver version/commit
+    /// * `expiry` - When the quote expires
+    ///
+    /// # Errors
+    ///
+    /// Same as `commit_route`
+    pub fn commit_route_expiring(
+        env: Env,
+        committer: Address,
+        route_hash: BytesN<32>,
+        rules_hash: BytesN<32>,
+        solver_version_hash: BytesN<32>,
+        expiry: Expiry,
+    ) -> Result<u64, RegistryError> {
+        committer.require_auth();
+        
+        let options = CommitOptions { expiry, ..Default::default() };
+        Self::commit_with_options(
+            &env,
+            committer,
+            route_hash,
+            CoreHashes { rules_hash, solver_version_hash },
+            options,
+        )
+    }
+    
+    /// Commit routing metadata that only becomes active at a future time.
+    ///
+    /// For scheduled payouts: the commitment is recorded now (timestamp,
+    /// ordering, events) but `get_status` reports `Scheduled` and
+    /// `is_active` is false until `active_from`. `verify_commit` stays a
+    /// time-agnostic hash check.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Soroban environment
+    /// * `committer` - Address credited with the commitment (must authorize)
+    /// * `route_hash` - SHA-256 hash of the complete route manifest
+    /// * `rules_hash` - SHA-256 hash of the routing rules configuration
+    /// * `solver_version_hash` - SHA-256 hash of the solver version/commit
+    /// * `active_from` - Ledger timestamp the commitment activates at
+    /// * `expiry` - Unix timestamp when quote expires (0 = no expiry)
+    ///
+    /// # Errors
+    ///
+    /// Same as `commit_route`, plus:
+    ///
+    /// * `InvalidActivationWindow` - not `timestamp <= active_from < expiry`
+    /// * `ActivationTooFar` - active_from exceeds maximum duration
+    pub fn commit_route_scheduled(
+        env: Env,
+        committer: Address,
+        route_hash: BytesN<32>,
+        rules_hash: BytesN<32>,
+        solver_version_hash: BytesN<32>,
+        active_from: u64,
+        expiry: u64,
+    ) -> Result<u64, RegistryError> {
+        committer.require_auth();
+        
+        let options = CommitOptions {
+            expiry: Expiry::from_raw(expiry),
+            schedule: ScheduleOption::From(active_from),
+            ..Default::default()
+        };
+        Self::commit_with_options(
+            &env,
+            committer,
+            route_hash,
+            CoreHashes { rules_hash, solver_version_hash },
+            options,
+        )
+    }
+    
+    /// Commit a route under two independent digests of the same manifest.
+    ///
+    /// Collision insurance: the commitment is keyed by the SHA-256 digest as
+    /// usual, and the keccak-256 digest of the identical canonical bytes is
+    /// stored alongside it. `verify_dual_manifest` then requires both to match.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Soroban environment
+    /// * `committer` - Address credited with the commitment (must authorize)
+    /// * `route_hash_sha256` - SHA-256 of the canonical route manifest
+    /// * `route_hash_keccak` - keccak-256 of the same manifest bytes
+    /// * `rules_hash` - SHA-256 hash of the routing rules configuration
+    /// * `solver_version_hash` - SHA-256 hash of the solver version/commit
+    /// * `expiry` - Unix timestamp when quote expires (0 = no expiry)
+    ///
+    /// # Events
+    ///
+    /// Emits `RouteCommitted`, then `("dual", route_hash_sha256)` with the keccak digest
+    ///
+    /// # Errors
+    ///
+    /// Same as `commit_route`, plus:
+    ///
+    /// * `EmptySecondaryHash` - keccak digest is all zeros
+    /// * `DualHashesEqual` - both digests are identical
+    pub fn commit_route_dual(
+        env: Env,
+        committer: Address,
+        route_hash_sha256: BytesN<32>,
+        route_hash_keccak: BytesN<32>,
+        rules_hash: BytesN<32>,
+        solver_version_hash: BytesN<32>,
+        expiry: u64,
+    ) -> Result<u64, RegistryError> {
+        committer.require_auth();
+        
+        let options = CommitOptions {
+            expiry: Expiry::from_raw(expiry),
+            secondary_hash: SecondaryHashOption::Keccak(route_hash_keccak),
+            ..Default::default()
+        };
+        Self::commit_with_options(
+            &env,
+            committer,
+            route_hash_sha256,
+            CoreHashes { rules_hash, solver_version_hash },
+            options,
+        )
+    }
+    
+    /// Commit a route submitted by a relayer on behalf of `committer`.
+    ///
+    /// Lets a sponsor (e.g. Stella) pay fees while the partner's own address
+    /// stays the committer of record. Both must authorize; the commitment
+    /// credits `committer`, and the relayer is kept in a side entry.
+    ///
+    /// # Events
+    ///
+    /// Emits `RouteCommitted`, then `("relayed", route_hash)` with `(relayer, committer)`
+    ///
+    /// # Errors
+    ///
+    /// Same as `commit_route`, plus:
+    ///
+    /// * `SelfRelay` - relayer and committer are the same address
+    pub fn commit_route_relayed(
+        env: Env,
+        relayer: Address,
+        committer: Address,
+        route_hash: BytesN<32>,
+        rules_hash: BytesN<32>,
+        solver_version_hash: BytesN<32>,
+        expiry: u64,
+    ) -> Result<u64, RegistryError> {
+        // Checked first: the same address cannot authorize one call twice
+        guards::require_distinct(&relayer, &committer, RegistryError::SelfRelay)?;
+        
+        relayer.require_auth();
+        committer.require_auth();
+        
+        let options = CommitOptions { expiry: Expiry::from_raw(expiry), ..Default::default() };
+        let committer_seq = Self::commit_with_options(
+            &env,
+            committer.clone(),
+            route_hash.clone(),
+            CoreHashes { rules_hash, solver_version_hash },
+            options,
+        )?;
+        
+        let key = DataKey::Relayer(route_hash.clone());
+        env.storage().persistent().set(&key, &relayer);
+        
+        env.events().publish(
+            (symbol_short!("relayed"), route_hash),
+            (relayer, committer),
+        );
+        
+        Ok(committer_seq)
+    }
+    
+    /// Commit a route together with an ed25519 signature over it.
+    ///
+    /// Proves the holder of `pubkey` (e.g. the solver operator's signing
+    /// key) produced the commitment, independently of which account
+    /// submitted or paid for the transaction, so no Soroban auth is needed.
+    /// The commitment is credited to the committer `pubkey` is registered
+    /// to with `register_signer`, or else to the Stellar account whose
+    /// ed25519 key is `pubkey`. The signed message is 184 bytes, integers big-endian,
+    /// and binds the signature to one network and registry so it cannot be
+    /// replayed elsewhere:
+    ///
+    /// ```text
+    /// "stella.signed.v1"   16  domain tag (suffix versions the layout)
+    /// network_id           32  env.ledger().network_id()
+    /// registry contract id 32  raw contract hash of this registry
+    /// route_hash           32
+    /// rules_hash           32
+    /// solver_version_hash  32
+    /// expiry                8  u64 (0 = no expiry)
+    /// ```
+    ///
+    /// The key is kept in a side entry, readable with `get_signer`.
+    ///
+    /// # Events
+    ///
+    /// Emits `RouteCommitted`, then `("signed", route_hash)` with `pubkey`
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidSignature` - the signature does not verify against `pubkey`.
+    ///   Reported as a trap, not a `RegistryError` code: the host's
+    ///   `ed25519_verify` aborts the invocation before anything is written,
+    ///   and the caller sees a host crypto error.
+    /// * `SignerDeactivated` - `pubkey` was registered and has since been
+    ///   deactivated
+    /// * Any `commit_route` validation error
+    pub fn commit_route_signed(
+        env: Env,
+        pubkey: BytesN<32>,
+        signature: BytesN<64>,
+        route_hash: BytesN<32>,
+        rules_hash: BytesN<32>,
+        solver_version_hash: BytesN<32>,
+        expiry: u64,
+    ) -> Result<u64, RegistryError> {
+        let core = CoreHashes { rules_hash, solver_version_hash };
+        let message = Self::signed_commit_message(&env, &route_hash, &core, expiry);
+        env.crypto().ed25519_verify(&pubkey, &message, &signature);
+        
+        let committer = match Self::get_signer_registration(env.clone(), pubkey.clone()) {
+            Some(_) if Self::get_signer_deactivation(env.clone(), pubkey.clone()).is_some() => {
+                return Err(RegistryError::SignerDeactivated);
+            }
+            Some(registration) => registration.committer,
+            None => Self::signer_account(&env, &pubkey),
+        };
+        let options = CommitOptions { expiry: Expiry::from_raw(expiry), ..Default::default() };
+        let committer_seq = Self::commit_with_options(&env, committer, route_hash.clone(), core, options)?;
+        
+        let key = DataKey::Signer(route_hash.clone());
+        env.storage().persistent().set(&key, &pubkey);
+        
+        env.events().publish((symbol_short!("signed"), route_hash), pubkey);
+        
+        Ok(committer_seq)
+    }
+    
+    /// Get the ed25519 key that signed a commitment, if it was signed
+    pub fn get_signer(env: Env, route_hash: BytesN<32>) -> Option<BytesN<32>> {
+        env.storage().persistent().get(&DataKey::Signer(route_hash))
+    }
+    
+    /// Register an offline ed25519 key whose signed commits credit `committer`.
+    ///
+    /// For air-gapped committers: payloads signed offline with the key are
+    /// submitted by a hot relayer through `commit_route_signed` and credited
+    /// to `committer`. The key must consent too, so nobody can claim another
+    /// party's signed commitments: `signature` is the key's signature over
+    /// 80 bytes plus the committer's address XDR:
+    ///
+    /// ```text
+    /// "stella.signer.v1"   16  domain tag (suffix versions the layout)
+    /// network_id           32  env.ledger().network_id()
+    /// registry contract id 32  raw contract hash of this registry
+    /// committer            ..  committer.to_xdr()
+    /// ```
+    ///
+    /// Append-only: a key is registered once, ever, and keeps its slot in
+    /// the committer's list after `deactivate_signer`.
+    ///
+    /// # Events
+    ///
+    /// Emits `("signer", committer)` with `(signer_pubkey, registered_at)`
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidSignature` - trap, as for `commit_route_signed`
+    /// * `SignerAlreadyRegistered` - the key is registered already, to anyone
+    /// * `TooManySigners` - the committer has `max_signers_per_committer` keys
+    pub fn register_signer(
+        env: Env,
+        committer: Address,
+        signer_pubkey: BytesN<32>,
+        signature: BytesN<64>,
+    ) -> Result<(), RegistryError> {
+        committer.require_auth();
+        
+        let message = Self::signer_registration_message(&env, &committer);
+        env.crypto().ed25519_verify(&signer_pubkey, &message, &signature);
+        
+        let key = DataKey::SignerKey(signer_pubkey.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(RegistryError::SignerAlreadyRegistered);
+        }
+        let list_key = DataKey::SignerKeys(committer.clone());
+        let mut signers = Self::get_signers(env.clone(), committer.clone());
+        collections::bounded_push(&mut signers, signer_pubkey.clone(), MAX_SIGNERS_PER_COMMITTER, RegistryError::TooManySigners)?;
+        
+        let registered_at = env.ledger().timestamp();
+        let registration = SignerRegistration { committer: committer.clone(), registered_at };
+        env.storage().persistent().set(&key, &registration);
+        env.storage().persistent().set(&list_key, &signers);
+        
+        env.events().publish(
+            (symbol_short!("signer"), committer),
+            (signer_pubkey, registered_at),
+        );
+        
+        Ok(())
+    }
+    
+    /// Permanently deactivate an offline signing key.
+    ///
+    /// From this call on, `commit_route_signed` rejects every commit signed
+    /// with the key, whatever time the payload claims. Commitments already
+    /// made with it are untouched.
+    ///
+    /// # Events
+    ///
+    /// Emits `("signeroff", committer)` with `(signer_pubkey, deactivated_at)`
+    ///
+    /// # Errors
+    ///
+    /// * `NotFound` - the key was never registered
+    /// * `Unauthorized` - the key is registered to another committer
+    /// * `SignerDeactivated` - the key was already deactivated
+    pub fn deactivate_signer(env: Env, committer: Address, signer_pubkey: BytesN<32>) -> Result<(), RegistryError> {
+        committer.require_auth();
+        
+        let registration = Self::get_signer_registration(env.clone(), signer_pubkey.clone()).ok_or(RegistryError::NotFound)?;
+        if registration.committer != committer {
+            log!(&env, "Rejected: signer deactivation by someone other than its committer");
+            return Err(RegistryError::Unauthorized);
+        }
+        let key = DataKey::SignerDeactivation(signer_pubkey.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(RegistryError::SignerDeactivated);
+        }
+        
+        let deactivated_at = env.ledger().timestamp();
+        env.storage().persistent().set(&key, &deactivated_at);
+        
+        env.events().publish(
+            (symbol_short!("signeroff"), committer),
+            (signer_pubkey, deactivated_at),
+        );
+        
+        Ok(())
+    }
+    
+    /// Get the committer an offline signing key is registered to, if any
+    pub fn get_signer_registration(env: Env, signer_pubkey: BytesN<32>) -> Option<SignerRegistration> {
+        env.storage().persistent().get(&DataKey::SignerKey(signer_pubkey))
+    }
+    
+    /// Get the timestamp an offline signing key was deactivated at, if it was
+    pub fn get_signer_deactivation(env: Env, signer_pubkey: BytesN<32>) -> Option<u64> {
+        env.storage().persistent().get(&DataKey::SignerDeactivation(signer_pubkey))
+    }
+    
+    /// List the offline signing keys a committer registered, oldest first,
+    /// deactivated ones included
+    pub fn get_signers(env: Env, committer: Address) -> Vec<BytesN<32>> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::SignerKeys(committer))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+    
+    /// Commit a route bound to the user it was quoted for.
+    ///
+    /// `user_binding` is a hash of the intended user's address plus a salt,
+    /// so the user stays private while a relayer cannot present the quote
+    /// to someone else as theirs. It is kept in a side entry; an all-zero
+    /// binding means unbound and stores nothing, like `commit_route`.
+    ///
+    /// # Events
+    ///
+    /// Emits `RouteCommitted`, then `("bound", route_hash)` with `user_binding` when bound
+    ///
+    /// # Errors
+    ///
+    /// Same as `commit_route`
+    pub fn commit_route_bound(
+        env: Env,
+        committer: Address,
+        route_hash: BytesN<32>,
+        rules_hash: BytesN<32>,
+        solver_version_hash: BytesN<32>,
+        expiry: u64,
+        user_binding: BytesN<32>,
+    ) -> Result<u64, RegistryError> {
+        committer.require_auth();
+        
+        let options = CommitOptions { expiry: Expiry::from_raw(expiry), ..Default::default() };
+        let committer_seq = Self::commit_with_options(
+            &env,
+            committer,
+            route_hash.clone(),
+            CoreHashes { rules_hash, solver_version_hash },
+            options,
+        )?;
+        
+        if !guards::is_zero_hash(&user_binding) {
+            env.storage()
+                .persistent()
+                .set(&DataKey::UserBinding(route_hash.clone()), &user_binding);
+            env.events().publish((symbol_short!("bound"), route_hash), user_binding);
+        }
+        
+        Ok(committer_seq)
+    }
+    
+    /// Get the user binding of a commitment, if it was bound
+    pub fn get_user_binding(env: Env, route_hash: BytesN<32>) -> Option<BytesN<32>> {
+        env.storage().persistent().get(&DataKey::UserBinding(route_hash))
+    }
+    
+    /// Verify hashes like `verify_commit`, and also require the commitment
+    /// to be bound to `user_binding`.
+    ///
+    /// Unbound commitments verify for any `user_binding`, so commitments
+    /// made before binding existed keep verifying.
+    pub fn verify_for_user(
+        env: Env,
+        route_hash: BytesN<32>,
+        expected_rules_hash: BytesN<32>,
+        expected_solver_hash: BytesN<32>,
+        user_binding: BytesN<32>,
+    ) -> bool {
+        Self::verify_commit(env.clone(), route_hash.clone(), expected_rules_hash, expected_solver_hash)
+            && Self::get_user_binding(env, route_hash).is_none_or(|bound| bound == user_binding)
+    }
+    
+    /// Commit a route with every optional setting in one call.
+    ///
+    /// The consolidated entry point: the `commit_route_*` variants are thin
+    /// wrappers over the same path, so records never drift between them.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Soroban environment
+    /// * `committer` - Address credited with the commitment (must authorize)
+    /// * `route_hash` - SHA-256 hash of the canonical route manifest
+    /// * `core` - Rules and solver version hashes
+    /// * `options` - Expiry or ttl, schedule, secondary digest
+    ///
+    /// # Events
+    ///
+    /// Emits `RouteCommitted`, plus `("dual", route_hash)` when a secondary digest is set
+    ///
+    /// # Errors
+    ///
+    /// * `ConflictingOptions` - both `expiry` and `ttl` are set
+    /// * Any error of the variant each option corresponds to
+    pub fn commit(
+        env: Env,
+        committer: Address,
+        route_hash: BytesN<32>,
+        core: CoreHashes,
+        options: CommitOptions,
+    ) -> Result<u64, RegistryError> {
+        committer.require_auth();
+        
+        Self::commit_with_options(&env, committer, route_hash, core, options)
+    }
+    
+    /// Commit several routes for one committer, all or nothing.
+    ///
+    /// Amortizes the per-transaction overhead for high-volume committers.
+    /// Every entry is validated exactly like `commit_route`, in order; if
+    /// any entry fails, the call returns its error and nothing from the
+    /// batch is stored. An entry repeating an earlier entry's route_hash
+    /// fails with `DuplicateCommitment`, like any other duplicate.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Soroban environment
+    /// * `committer` - Address credited with every commitment (must authorize)
+    /// * `entries` - Commitments to record, at most `max_batch_size`
+    ///
+    /// # Events
+    ///
+    /// Emits one `RouteCommitted` per entry, in entry order
+    ///
+    /// # Errors
+    ///
+    /// Same as `commit_route` for the first failing entry, plus:
+    ///
+    /// * `TooManyEntries` - more than `max_batch_size` entries
+    pub fn commit_routes(env: Env, committer: Address, entries: Vec<CommitEntry>) -> Result<(), RegistryError> {
+        committer.require_auth();
+        
+        guards::require_batch_size(&entries)?;
+        
+        // Returning an error rolls back the entries already recorded
+        for entry in entries.iter() {
+            Self::commit_entry(&env, &committer, entry)?;
+        }
+        
+        Ok(())
+    }
+    
+    /// Commit several routes for one committer, entry by entry.
+    ///
+    /// Unlike `commit_routes`, a failing entry does not sink the batch: it
+    /// is skipped, nothing of it is stored, and its error code is reported
+    /// in its slot. Entries are processed in order, so an entry repeating an
+    /// earlier entry's route_hash fails only if the earlier one succeeded.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(results)` - one `CommitResult` per entry, in entry order
+    /// * `Err(RegistryError::TooManyEntries)` - more than `max_batch_size` entries
+    ///
+    /// # Events
+    ///
+    /// Emits one `RouteCommitted` per stored entry, in entry order
+    pub fn commit_routes_partial(
+        env: Env,
+        committer: Address,
+        entries: Vec<CommitEntry>,
+    ) -> Result<Vec<CommitResult>, RegistryError> {
+        committer.require_auth();
+        
+        guards::require_batch_size(&entries)?;
+        
+        // record_commitment runs every fallible check, counter overflows
+        // included, before its first write, so a failed entry leaves nothing behind
+        let mut results = Vec::new(&env);
+        for entry in entries.iter() {
+            let result = match Self::commit_entry(&env, &committer, entry) {
+                Ok(_) => CommitResult::Committed(env.ledger().timestamp()),
+                Err(err) => CommitResult::Failed(err as u32),
+            };
+            results.push_back(result);
+        }
+        
+        Ok(results)
+    }
+    
+    /// Retrieve commitment metadata for a route hash.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Soroban environment
+    /// * `route_hash` - SHA-256 hash of the route to look up
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(RouteCommitment)` - Full commitment metadata
+    /// * `Err(RegistryError::NotFound)` - No commitment exists for this hash
+    ///
+    /// # Usage
+    ///
+    /// ```text
+    /// let commit = contract.get_commit(route_hash)?;
+    /// assert!(commit.rules_hash == expected_rules_hash);
+    /// assert!(commit.solver_version_hash == known_solver_commit);
+    /// ```
+    pub fn get_commit(
+        env: Env,
+        route_hash: BytesN<32>,
+    ) -> Result<RouteCommitment, RegistryError> {
+        Self::load_commitment(&env, &route_hash).ok_or(RegistryError::NotFound)
+    }
+    
+    /// Retrieve commitment metadata stamped with the current ledger.
+    ///
+    /// Same lookup as `get_commit`, wrapped in a [`CommitAt`] envelope whose
+    /// `at_ledger` / `at_timestamp` come from the same invocation, so cached
+    /// results carry their own freshness.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(CommitAt)` - Commitment plus the ledger it was read at
+    /// * `Err(RegistryError::NotFound)` - No commitment exists for this hash
+    pub fn get_commit_at(
+        env: Env,
+        route_hash: BytesN<32>,
+    ) -> Result<CommitAt, RegistryError> {
+        let value = Self::get_commit(env.clone(), route_hash)?;
+        
+        Ok(CommitAt {
+            value,
+            at_ledger: env.ledger().sequence(),
+            at_timestamp: env.ledger().timestamp(),
+        })
+    }
+    
+    /// Retrieve only the committer of a route hash.
+    ///
+    /// For allowlist checks that need nothing else: the commitment entry is
+    /// still read whole, but only the address is returned to the caller.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Address)` - Committer of the commitment
+    /// * `Err(RegistryError::NotFound)` - No commitment exists for this hash
+    pub fn get_committer(env: Env, route_hash: BytesN<32>) -> Result<Address, RegistryError> {
+        Self::get_commit(env, route_hash).map(|commitment| commitment.committer)
+    }
+    
+    /// Retrieve only the quote expiry of a route hash.
+    ///
+    /// For wallets polling whether a quote is still good; one storage read
+    /// and a single `u64` back.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(u64)` - Stored raw expiry (`0` = no expiry)
+    /// * `Err(RegistryError::NotFound)` - No commitment exists for this hash
+    pub fn get_expiry(env: Env, route_hash: BytesN<32>) -> Result<u64, RegistryError> {
+        Self::get_commit(env, route_hash).map(|commitment| commitment.expiry)
+    }
+    
+    /// Whether a commitment's quote has expired at the current ledger time.
+    ///
+    /// Uses [`Expiry::is_expired_at`]: an expiry equal to the ledger
+    /// timestamp counts as expired, and no-expiry commitments never do.
+    ///
+    /// # Errors
+    ///
+    /// * `NotFound` - No commitment exists for this hash
+    pub fn is_expired(env: Env, route_hash: BytesN<32>) -> Result<bool, RegistryError> {
+        let expiry = Self::get_expiry(env.clone(), route_hash)?;
+        Ok(Expiry::from_raw(expiry).is_expired_at(env.ledger().timestamp()))
+    }
+    
+    /// Seconds of ledger time until a commitment's quote expires.
+    ///
+    /// Reaches `0` exactly when `is_expired` turns true and stays there.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(u64)` - Seconds remaining, `0` once expired, `u64::MAX` for
+    ///   no-expiry commitments
+    /// * `Err(RegistryError::NotFound)` - No commitment exists for this hash
+    pub fn time_remaining(env: Env, route_hash: BytesN<32>) -> Result<u64, RegistryError> {
+        let expiry = Self::get_expiry(env.clone(), route_hash)?;
+        Ok(match Expiry::from_raw(expiry) {
+            Expiry::Never => u64::MAX,
+            Expiry::At(ts) => ts.saturating_sub(env.ledger().timestamp()),
+        })
+    }
+    
+    /// Compact snapshot of a commitment for budget-constrained callers.
+    ///
+    /// Never fails: a missing route gives `exists: false` with the other
+    /// fields zeroed. Both flags come from `derive_status`: `expired` is
+    /// the status the commitment would have without its revocation or
+    /// cancellation, so a revoked commitment past its expiry sets both.
+    pub fn get_commit_summary(env: Env, route_hash: BytesN<32>) -> CommitSummary {
+        let commit = match Self::load_commitment(&env, &route_hash) {
+            Some(commit) => commit,
+            None => return CommitSummary { exists: false, expired: false, revoked: false, timestamp: 0 },
+        };
+        
+        let now = env.ledger().timestamp();
+        CommitSummary {
+            exists: true,
+            expired: Self::derive_status(&commit, false, false, now) == CommitmentStatus::Expired,
+            revoked: Self::current_status(&env, &route_hash, &commit) == CommitmentStatus::Revoked,
+            timestamp: commit.timestamp,
+        }
+    }
+    
+    /// Check if a route hash has been committed.
+    ///
+    /// Gas-efficient existence check without loading full commitment data.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Soroban environment
+    /// * `route_hash` - SHA-256 hash to check
+    ///
+    /// # Returns
+    ///
+    /// * `true` if commitment exists
+    /// * `false` otherwise
+    pub fn has_commit(env: Env, route_hash: BytesN<32>) -> bool {
+        Self::commitment_exists(&env, &route_hash)
+    }
+    
+    /// Retrieve several commitments in one call.
+    ///
+    /// Saves wallet backends one RPC round trip per route. Results are
+    /// positional; a missing commitment is `None` in its slot rather than
+    /// failing the whole call.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Option<RouteCommitment>>)` - one slot per requested hash
+    /// * `Err(RegistryError::TooManyEntries)` - more than `max_read_batch_size` hashes
+    pub fn get_commits(
+        env: Env,
+        route_hashes: Vec<BytesN<32>>,
+    ) -> Result<Vec<Option<RouteCommitment>>, RegistryError> {
+        guards::require_read_batch_size(&route_hashes)?;
+        
+        let mut commitments = Vec::new(&env);
+        for route_hash in route_hashes.iter() {
+            commitments.push_back(Self::load_commitment(&env, &route_hash));
+        }
+        Ok(commitments)
+    }
+    
+    /// Check existence of several commitments in one call.
+    ///
+    /// Positional batch form of `has_commit`: every hash is checked, and
+    /// commitments are never loaded, so it costs less than reading
+    /// them with `get_commits`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<bool>)` - one flag per requested hash
+    /// * `Err(RegistryError::TooManyEntries)` - more than `max_read_batch_size` hashes
+    pub fn has_commits(env: Env, route_hashes: Vec<BytesN<32>>) -> Result<Vec<bool>, RegistryError> {
+        guards::require_read_batch_size(&route_hashes)?;
+        
+        let mut flags = Vec::new(&env);
+        for route_hash in route_hashes.iter() {
+            flags.push_back(Self::commitment_exists(&env, &route_hash));
+        }
+        Ok(flags)
+    }
+    
+    /// Check whether any of several route hashes is already committed.
+    ///
+    /// For pre-submission duplicate detection; stops at the first hit.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(bool)` - whether at least one hash is committed
+    /// * `Err(RegistryError::TooManyEntries)` - more than `max_read_batch_size` hashes
+    pub fn exists_any(env: Env, route_hashes: Vec<BytesN<32>>) -> Result<bool, RegistryError> {
+        Ok(Self::first_existing(env, route_hashes)?.is_some())
+    }
+    
+    /// First of several route hashes that is already committed, in input order.
+    ///
+    /// Like `exists_any`, but names the colliding hash. Hashes after the
+    /// first hit are not read.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(route_hash))` - the first committed hash
+    /// * `Ok(None)` - none of the hashes is committed
+    /// * `Err(RegistryError::TooManyEntries)` - more than `max_read_batch_size` hashes
+    pub fn first_existing(env: Env, route_hashes: Vec<BytesN<32>>) -> Result<Option<BytesN<32>>, RegistryError> {
+        guards::require_read_batch_size(&route_hashes)?;
+        
+        Ok(route_hashes.iter().find(|route_hash| Self::commitment_exists(&env, route_hash)))
+    }
+    
+    /// Verify that a commitment matches expected values.
+    ///
+    /// Convenience function for on-chain verification by other contracts.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Soroban environment
+    /// * `route_hash` - Route hash to verify
+    /// * `expected_rules_hash` - Expected rules hash
+    /// * `expected_solver_hash` - Expected solver version hash
+    ///
+    /// # Returns
+    ///
+    /// * `true` if commitment exists AND all hashes match
+    /// * `false` otherwise
+    pub fn verify_commit(
+        env: Env,
+        route_hash: BytesN<32>,
+        expected_rules_hash: BytesN<32>,
+        expected_solver_hash: BytesN<32>,
+    ) -> bool {
+        match Self::get_commit(env, route_hash) {
+            Ok(commit) => {
+                commit.rules_hash == expected_rules_hash
+                    && commit.solver_version_hash == expected_solver_hash
+            }
+            Err(_) => false,
+        }
+    }
+    
+    /// Run several `verify_commit` checks in one call.
+    ///
+    /// For audit sweeps. Each item gets the same answer `verify_commit`
+    /// would give, in item order; missing commitments are `false`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<bool>)` - one result per item
+    /// * `Err(RegistryError::TooManyEntries)` - more than `max_read_batch_size` items
+    pub fn verify_commits(env: Env, items: Vec<VerifyItem>) -> Result<Vec<bool>, RegistryError> {
+        guards::require_read_batch_size(&items)?;
+        
+        let mut results = Vec::new(&env);
+        for item in items.iter() {
+            results.push_back(Self::verify_commit(
+                env.clone(),
+                item.route_hash,
+                item.expected_rules_hash,
+                item.expected_solver_hash,
+            ));
+        }
+        Ok(results)
+    }
+    
+    /// Verify a commitment, trusting only committers listed in `directory`.
+    ///
+    /// An opt-in overlay for verifiers that only accept a vetted set of
+    /// committers: same as `verify_commit`, plus a cross-contract check that
+    /// the committer is listed in the given `CommitterDirectory`. Committing
+    /// itself stays permissionless.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if `verify_commit` passes AND the committer is listed
+    /// * `false` otherwise, including when `directory` cannot be called
+    pub fn verify_commit_from_listed(
+        env: Env,
+        directory: Address,
+        route_hash: BytesN<32>,
+        expected_rules_hash: BytesN<32>,
+        expected_solver_hash: BytesN<32>,
+    ) -> bool {
+        let commit = match Self::get_commit(env.clone(), route_hash) {
+            Ok(commit) => commit,
+            Err(_) => return false,
+        };
+        if commit.rules_hash != expected_rules_hash || commit.solver_version_hash != expected_solver_hash {
+            return false;
+        }
+        
+        let directory = directory::DirectoryClient::new(&env, &directory);
+        matches!(directory.try_is_listed(&commit.committer), Ok(Ok(true)))
+    }
+    
+    /// Verify only the fields the caller actually holds.
+    ///
+    /// For wallets with partial data (e.g. just the rules document): fields
+    /// marked `Ignore` in `expected` are skipped instead of having to be filled
+    /// with the stored value. Fields are checked in the order rules, solver,
+    /// committer, and the first mismatch is reported.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(VerifyResult)` - `Match`, the first mismatching field, or
+    ///   `NotFound` / `ExistenceOnly` when there is no full commitment
+    /// * `Err(RegistryError::EmptyExpectation)` - every field is `Ignore`
+    pub fn verify_partial(
+        env: Env,
+        route_hash: BytesN<32>,
+        expected: PartialExpectation,
+    ) -> Result<VerifyResult, RegistryError> {
+        if expected.rules_hash == HashExpectation::Ignore
+            && expected.solver_version_hash == HashExpectation::Ignore
+            && expected.committer == AddressExpectation::Ignore
+        {
+            return Err(RegistryError::EmptyExpectation);
+        }
+        
+        let commit = match Self::load_commitment(&env, &route_hash) {
+            Some(commit) => commit,
+            None if env.storage().persistent().has(&DataKey::Existence(route_hash)) => {
+                return Ok(VerifyResult::ExistenceOnly);
+            }
+            None => return Ok(VerifyResult::NotFound),
+        };
+        
+        if let HashExpectation::Equals(hash) = expected.rules_hash {
+            if hash != commit.rules_hash {
+                return Ok(VerifyResult::RulesMismatch);
+            }
+        }
+        if let HashExpectation::Equals(hash) = expected.solver_version_hash {
+            if hash != commit.solver_version_hash {
+                return Ok(VerifyResult::SolverMismatch);
+            }
+        }
+        if let AddressExpectation::Equals(addr) = expected.committer {
+            if addr != commit.committer {
+                return Ok(VerifyResult::CommitterMismatch);
+            }
+        }
+        Ok(VerifyResult::Match)
+    }
+    
+    /// Retrieve the relayer that submitted a commitment, if any.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(relayer)` if the route was committed via `commit_route_relayed`
+    /// * `None` otherwise
+    pub fn get_relayer(env: Env, route_hash: BytesN<32>) -> Option<Address> {
+        let key = DataKey::Relayer(route_hash);
+        env.storage().persistent().get(&key)
+    }
+    
+    /// Check whether a commitment was submitted through a relayer.
+    pub fn is_relayed(env: Env, route_hash: BytesN<32>) -> bool {
+        Self::get_relayer(env, route_hash).is_some()
+    }
+    
+    /// Retrieve the secondary (keccak-256) digest of a dual-hashed route.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(digest)` if the route was committed via `commit_route_dual`
+    /// * `None` otherwise
+    pub fn get_secondary_hash(env: Env, route_hash: BytesN<32>) -> Option<BytesN<32>> {
+        let key = DataKey::SecondaryHash(route_hash);
+        env.storage().persistent().get(&key)
+    }
+    
+    /// Verify manifest bytes against both digests of a dual-hashed route.
+    ///
+    /// Computes SHA-256 and keccak-256 of `manifest` on-chain.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the route was dual-committed and both digests match
+    /// * `false` otherwise (including single-hash commitments)
+    pub fn verify_dual_manifest(env: Env, route_hash: BytesN<32>, manifest: Bytes) -> bool {
+        let secondary = match Self::get_secondary_hash(env.clone(), route_hash.clone()) {
+            Some(hash) => hash,
+            None => return false,
+        };
+        
+        let sha256: BytesN<32> = env.crypto().sha256(&manifest).into();
+        let keccak: BytesN<32> = env.crypto().keccak256(&manifest).into();
+        sha256 == route_hash && keccak == secondary
+    }
+    
+    /// Current lifecycle status of a commitment.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(CommitmentStatus)` - `Scheduled`, `Active`, `Expired`,
+    ///   `Revoked` or `Cancelled` at the current ledger timestamp
+    /// * `Err(RegistryError::NotFound)` - No commitment exists for this hash
+    pub fn get_status(
+        env: Env,
+        route_hash: BytesN<32>,
+    ) -> Result<CommitmentStatus, RegistryError> {
+        let commit = Self::load_commitment(&env, &route_hash).ok_or(RegistryError::NotFound)?;
+        Ok(Self::current_status(&env, &route_hash, &commit))
+    }
+    
+    /// Check if a commitment is currently active.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the commitment exists and its status is `Active`
+    /// * `false` if missing, not yet active, expired, revoked or cancelled
+    pub fn is_active(env: Env, route_hash: BytesN<32>) -> bool {
+        matches!(
+            Self::get_status(env, route_hash),
+            Ok(CommitmentStatus::Active)
+        )
+    }
+    
+    /// Verify hashes like `verify_commit`, and also require the commitment
+    /// to be usable right now.
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the hashes match and the status is `Active`
+    /// * `false` otherwise, including revoked and cancelled commitments
+    pub fn verify_commit_active(
+        env: Env,
+        route_hash: BytesN<32>,
+        expected_rules_hash: BytesN<32>,
+        expected_solver_hash: BytesN<32>,
+    ) -> bool {
+        Self::verify_commit(env.clone(), route_hash.clone(), expected_rules_hash, expected_solver_hash)
+            && Self::is_active(env, route_hash)
+    }
+    
+    /// Verify like `verify_commit_active`, reporting why verification failed.
+    ///
+    /// When several checks fail, the first in this order is returned:
+    /// `NotFound`, `RulesMismatch`, `SolverMismatch`, `Revoked`, `Cancelled`,
+    /// `Expired`, `Scheduled`. `Ok(())` exactly when `verify_commit_active` is `true`.
+    ///
+    /// # Errors
+    ///
+    /// * `VerifyFailure` - the first failing check
+    pub fn verify_commit_detailed(
+        env: Env,
+        route_hash: BytesN<32>,
+        expected_rules_hash: BytesN<32>,
+        expected_solver_hash: BytesN<32>,
+    ) -> Result<(), VerifyFailure> {
+        let commit = Self::load_commitment(&env, &route_hash).ok_or(VerifyFailure::NotFound)?;
+        if commit.rules_hash != expected_rules_hash {
+            return Err(VerifyFailure::RulesMismatch);
+        }
+        if commit.solver_version_hash != expected_solver_hash {
+            return Err(VerifyFailure::SolverMismatch);
+        }
+        match Self::current_status(&env, &route_hash, &commit) {
+            CommitmentStatus::Active => Ok(()),
+            CommitmentStatus::Revoked => Err(VerifyFailure::Revoked),
+            CommitmentStatus::Cancelled => Err(VerifyFailure::Cancelled),
+            CommitmentStatus::Expired => Err(VerifyFailure::Expired),
+            CommitmentStatus::Scheduled => Err(VerifyFailure::Scheduled),
+        }
+    }
+    
+    /// Publicly withdraw a commitment without modifying it.
+    ///
+    /// Keeps the registry append-only: the original record stays as it was,
+    /// and a `RevocationRecord` is written beside it. `verify_commit` keeps
+    /// answering on hash equality; `verify_commit_active` reports revoked
+    /// commitments as unusable.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Soroban environment
+    /// * `route_hash` - Commitment to revoke
+    /// * `committer` - Original committer (must authorize)
+    /// * `reason_hash` - Hash of the off-chain explanation
+    ///
+    /// # Events
+    ///
+    /// Emits `RouteRevoked` as `("withdrawn", route_hash)` with `(committer, reason_hash, timestamp)`
+    ///
+    /// # Errors
+    ///
+    /// * `EmptyReasonHash` - reason_hash is all zeros
+    /// * `NotFound` - no commitment exists for `route_hash`
+    /// * `Unauthorized` - caller is not the original committer
+    /// * `AlreadyRevoked` - the commitment was already revoked
+    pub fn revoke_commit(
+        env: Env,
+        route_hash: BytesN<32>,
+        committer: Address,
+        reason_hash: BytesN<32>,
+    ) -> Result<(), RegistryError> {
+        committer.require_auth();
+        
+        guards::require_nonzero_hash(&reason_hash, RegistryError::EmptyReasonHash)?;
+        Self::revoke_entry(&env, route_hash, &committer, &reason_hash)
+    }
+    
+    /// Revoke several of the caller's commitments in one call.
+    ///
+    /// For incident response. The committer authorizes once and every
+    /// route is revoked as in `revoke_commit`, with the same event, under
+    /// a shared `reason_hash`. Routes that are missing, belong to another
+    /// committer, or are already revoked are skipped and reported as `false`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<bool>)` - whether each route was revoked by this call
+    /// * `Err(RegistryError::EmptyReasonHash)` - reason_hash is all zeros
+    /// * `Err(RegistryError::TooManyEntries)` - more than `max_batch_size` hashes
+    pub fn revoke_commits(
+        env: Env,
+        committer: Address,
+        route_hashes: Vec<BytesN<32>>,
+        reason_hash: BytesN<32>,
+    ) -> Result<Vec<bool>, RegistryError> {
+        committer.require_auth();
+        
+        guards::require_batch_size(&route_hashes)?;
+        guards::require_nonzero_hash(&reason_hash, RegistryError::EmptyReasonHash)?;
+        
+        let mut flags = Vec::new(&env);
+        for route_hash in route_hashes.iter() {
+            flags.push_back(Self::revoke_entry(&env, route_hash, &committer, &reason_hash).is_ok());
+        }
+        Ok(flags)
+    }
+    
+    /// Check whether a commitment has been revoked by its committer
+    pub fn is_revoked(env: Env, route_hash: BytesN<32>) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Revocation(route_hash))
+    }
+    
+    /// Get the revocation marker of a commitment, if it was revoked
+    pub fn get_revocation(env: Env, route_hash: BytesN<32>) -> Option<RevocationRecord> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Revocation(route_hash))
+    }
+    
+    /// Cancel a still-valid quote the user abandoned.
+    ///
+    /// Distinct from `revoke_commit`, which withdraws a commitment made in
+    /// error: a cancellation says the quote was sound but must no longer be
+    /// honored. Append-only like revocation; a marker holding the ledger
+    /// timestamp is written beside the untouched record, and from then on
+    /// the status is `Cancelled`. `verify_commit` keeps answering on hash
+    /// equality, while `is_active` and `verify_commit_active` report the
+    /// quote as unusable. A cancelled quote can still be revoked.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Soroban environment
+    /// * `committer` - Original committer (must authorize)
+    /// * `route_hash` - Quote to cancel; scheduled or active
+    ///
+    /// # Events
+    ///
+    /// Emits `("cancelled", route_hash)` with `(committer, timestamp)`
+    ///
+    /// # Errors
+    ///
+    /// * `NotFound` - no commitment exists for `route_hash`
+    /// * `Unauthorized` - caller is not the original committer
+    /// * `AlreadyCancelled` - the quote was already cancelled
+    /// * `AlreadyInactive` - the quote has expired or been revoked
+    pub fn cancel_quote(env: Env, committer: Address, route_hash: BytesN<32>) -> Result<(), RegistryError> {
+        committer.require_auth();
+        
+        let commitment = Self::load_commitment(&env, &route_hash).ok_or(RegistryError::NotFound)?;
+        if commitment.committer != committer {
+            log!(&env, "Rejected: cancellation by someone other than the committer");
+            return Err(RegistryError::Unauthorized);
+        }
+        match Self::current_status(&env, &route_hash, &commitment) {
+            CommitmentStatus::Cancelled => return Err(RegistryError::AlreadyCancelled),
+            CommitmentStatus::Revoked | CommitmentStatus::Expired => return Err(RegistryError::AlreadyInactive),
+            CommitmentStatus::Scheduled | CommitmentStatus::Active => {}
+        }
+        
+        let timestamp = env.ledger().timestamp();
+        env.storage()
+            .persistent()
+            .set(&DataKey::Cancellation(route_hash.clone()), &timestamp);
+        
+        env.events().publish(
+            (symbol_short!("cancelled"), route_hash),
+            (committer, timestamp),
+        );
+        
+        Ok(())
+    }
+    
+    /// Check whether a quote has been cancelled by its committer
+    pub fn is_cancelled(env: Env, route_hash: BytesN<32>) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Cancellation(route_hash))
+    }
+    
+    /// Get the ledger timestamp a quote was cancelled at, if it was
+    pub fn get_cancellation(env: Env, route_hash: BytesN<32>) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Cancellation(route_hash))
+    }
+    
+    /// Commit a re-quote and link it to the commitment it replaces.
+    ///
+    /// Atomically commits `new_route_hash` and records the
+    /// `old_route_hash → new_route_hash` link in side entries, so auditors
+    /// can follow a quote through its re-quotes. The old commitment itself
+    /// is not modified. Each commitment can be superseded once, which keeps
+    /// the chain linear.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Soroban environment
+    /// * `old_route_hash` - Commitment being replaced
+    /// * `new_route_hash` - SHA-256 hash of the re-quoted route manifest
+    /// * `rules_hash` - SHA-256 hash of the routing rules configuration
+    /// * `solver_version_hash` - SHA-256 hash of the solver version/commit
+    /// * `expiry` - Unix timestamp when the new quote expires (0 = no expiry)
+    /// * `committer` - Original committer of `old_route_hash` (must authorize)
+    ///
+    /// # Events
+    ///
+    /// Emits `RouteCommitted` for the new commitment, then
+    /// `("supersede", old_route_hash)` with `(new_route_hash, committer)`
+    ///
+    /// # Errors
+    ///
+    /// Same as `commit_route`, plus:
+    ///
+    /// * `NotFound` - no commitment exists for `old_route_hash`
+    /// * `Unauthorized` - caller is not the original committer
+    /// * `AlreadySuperseded` - `old_route_hash` already has a successor
+    pub fn supersede_commit(
+        env: Env,
+        old_route_hash: BytesN<32>,
+        new_route_hash: BytesN<32>,
+        rules_hash: BytesN<32>,
+        solver_version_hash: BytesN<32>,
+        expiry: u64,
+        committer: Address,
+    ) -> Result<u64, RegistryError> {
+        committer.require_auth();
+        
+        let old = Self::load_commitment(&env, &old_route_hash).ok_or(RegistryError::NotFound)?;
+        if old.committer != committer {
+            log!(&env, "Rejected: supersede by someone other than the committer");
+            return Err(RegistryError::Unauthorized);
+        }
+        
+        let successor_key = DataKey::Successor(old_route_hash.clone());
+        if env.storage().persistent().has(&successor_key) {
+            return Err(RegistryError::AlreadySuperseded);
+        }
+        
+        let options = CommitOptions { expiry: Expiry::from_raw(expiry), ..Default::default() };
+        let committer_seq = Self::commit_with_options(
+            &env,
+            committer.clone(),
+            new_route_hash.clone(),
+            CoreHashes { rules_hash, solver_version_hash },
+            options,
+        )?;
+        
+        env.storage().persistent().set(&successor_key, &new_route_hash);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Predecessor(new_route_hash.clone()), &old_route_hash);
+        
+        env.events().publish(
+            (symbol_short!("supersede"), old_route_hash),
+            (new_route_hash, committer),
+        );
+        
+        Ok(committer_seq)
+    }
+    
+    /// Get the commitment that superseded `route_hash`, if any
+    pub fn get_successor(env: Env, route_hash: BytesN<32>) -> Option<BytesN<32>> {
+        env.storage().persistent().get(&DataKey::Successor(route_hash))
+    }
+    
+    /// Get the commitment `route_hash` superseded, if any
+    pub fn get_predecessor(env: Env, route_hash: BytesN<32>) -> Option<BytesN<32>> {
+        env.storage().persistent().get(&DataKey::Predecessor(route_hash))
+    }
+    
+    /// Commit the child routes a large transfer was split into, linked to their parent.
+    ///
+    /// Each child has its own manifest and is committed exactly like an
+    /// entry of `commit_routes`, all or nothing; then the family is linked
+    /// both ways in side entries, leaving every record untouched. Children
+    /// can be split again, down to `max_route_depth` ancestors, and further
+    /// calls add to a parent's children up to `max_children_per_route`.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Soroban environment
+    /// * `committer` - Committer of the parent, credited with every child (must authorize)
+    /// * `parent_route_hash` - Commitment being split
+    /// * `children` - Child commitments to record
+    ///
+    /// # Events
+    ///
+    /// Emits one `RouteCommitted` per child, in order, then
+    /// `("split", parent_route_hash)` with `(committer, child_route_hashes)`
+    ///
+    /// # Errors
+    ///
+    /// Same as `commit_routes` for the first failing child, plus:
+    ///
+    /// * `NotFound` - no commitment exists for `parent_route_hash`
+    /// * `Unauthorized` - caller is not the parent's committer
+    /// * `EmptyBatch` - no children
+    /// * `FamilyLimitExceeded` - the parent would exceed `max_children_per_route`
+    ///   children, or the children `max_route_depth` ancestors
+    pub fn commit_child_routes(
+        env: Env,
+        committer: Address,
+        parent_route_hash: BytesN<32>,
+        children: Vec<CommitEntry>,
+    ) -> Result<(), RegistryError> {
+        committer.require_auth();
+        
+        if children.is_empty() {
+            return Err(RegistryError::EmptyBatch);
+        }
+        let parent = Self::load_commitment(&env, &parent_route_hash).ok_or(RegistryError::NotFound)?;
+        if parent.committer != committer {
+            log!(&env, "Rejected: split by someone other than the committer");
+            return Err(RegistryError::Unauthorized);
+        }
+        
+        // Count the children's ancestors, walking no further up than the limit
+        let mut depth = 1;
+        let mut ancestor = parent_route_hash.clone();
+        while let Some(grandparent) = Self::get_parent(env.clone(), ancestor) {
+            if depth == MAX_ROUTE_DEPTH {
+                return Err(RegistryError::FamilyLimitExceeded);
+            }
+            depth += 1;
+            ancestor = grandparent;
+        }
+        
+        let children_key = DataKey::Children(parent_route_hash.clone());
+        let mut child_hashes = Self::get_children(env.clone(), parent_route_hash.clone());
+        let mut added = Vec::new(&env);
+        for child in children.iter() {
+            collections::bounded_push(&mut child_hashes, child.route_hash.clone(), MAX_CHILDREN_PER_ROUTE, RegistryError::FamilyLimitExceeded)?;
+            added.push_back(child.route_hash.clone());
+        }
+        
+        // Returning an error rolls back the children already recorded
+        for child in children.iter() {
+            let route_hash = child.route_hash.clone();
+            Self::commit_entry(&env, &committer, child)?;
+            env.storage()
+                .persistent()
+                .set(&DataKey::Parent(route_hash), &parent_route_hash);
+        }
+        env.storage().persistent().set(&children_key, &child_hashes);
+        
+        env.events().publish(
+            (symbol_short!("split"), parent_route_hash),
+            (committer, added),
+        );
+        
+        Ok(())
+    }
+    
+    /// Get the child routes `route_hash` was split into, in commit order
+    pub fn get_children(env: Env, route_hash: BytesN<32>) -> Vec<BytesN<32>> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Children(route_hash))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+    
+    /// Get the parent route `route_hash` was split from, if any
+    pub fn get_parent(env: Env, route_hash: BytesN<32>) -> Option<BytesN<32>> {
+        env.storage().persistent().get(&DataKey::Parent(route_hash))
+    }
+    
+    /// Retrieve the window during which a commitment counts as active.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ValidityWindow)` - `active_from` (inclusive) and `expiry`
+    /// * `Err(RegistryError::NotFound)` - No commitment exists for this hash
+    pub fn get_validity_window(
+        env: Env,
+        route_hash: BytesN<32>,
+    ) -> Result<ValidityWindow, RegistryError> {
+        let commit = Self::get_commit(env, route_hash)?;
+        Ok(ValidityWindow {
+            active_from: commit.active_from,
+            expiry: Expiry::from_raw(commit.expiry),
+        })
+    }
+    
+    /// Build the canonical attestation payload for a commitment.
+    ///
+    /// A stable byte string third parties (e.g. anchors in SEP flows) can
+    /// hash and countersign. Layout, 192 bytes, integers big-endian:
+    ///
+    /// ```text
+    /// "stella.attest.v1"   16  domain tag (suffix versions the layout)
+    /// network_id           32  env.ledger().network_id()
+    /// registry contract id 32  raw contract hash of this registry
+    /// route_hash           32
+    /// rules_hash           32
+    /// solver_version_hash  32
+    /// timestamp             8  u64
+    /// expiry                8  u64 (0 = no expiry)
+    /// ```
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Bytes)` - The payload
+    /// * `Err(RegistryError::NotFound)` - No commitment exists for this hash
+    pub fn attestation_payload(env: Env, route_hash: BytesN<32>) -> Result<Bytes, RegistryError> {
+        let commit = Self::get_commit(env.clone(), route_hash.clone())?;
+        
+        let mut payload = Bytes::from_array(&env, ATTESTATION_DOMAIN);
+        payload.append(&Bytes::from_array(&env, &env.ledger().network_id().to_array()));
+        payload.append(&Self::contract_id_bytes(&env));
+        payload.append(&Bytes::from_array(&env, &route_hash.to_array()));
+        payload.append(&Bytes::from_array(&env, &commit.rules_hash.to_array()));
+        payload.append(&Bytes::from_array(&env, &commit.solver_version_hash.to_array()));
+        payload.append(&Bytes::from_array(&env, &commit.timestamp.to_be_bytes()));
+        payload.append(&Bytes::from_array(&env, &commit.expiry.to_be_bytes()));
+        Ok(payload)
+    }
+    
+    /// SHA-256 of `attestation_payload(route_hash)`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(BytesN<32>)` - The payload hash
+    /// * `Err(RegistryError::NotFound)` - No commitment exists for this hash
+    pub fn attestation_payload_hash(
+        env: Env,
+        route_hash: BytesN<32>,
+    ) -> Result<BytesN<32>, RegistryError> {
+        let payload = Self::attestation_payload(env.clone(), route_hash)?;
+        Ok(env.crypto().sha256(&payload).into())
+    }
+    
+    /// Retrieve a committer's rolling digest for one day.
+    ///
+    /// The digest starts from 32 zero bytes each day and every commitment
+    /// folds in as `digest = sha256(digest || route_hash)`, in commit order.
+    /// Partners recompute it from the commitments they believe they made and
+    /// only need to investigate on mismatch.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Soroban environment
+    /// * `committer` - Address whose activity to look up
+    /// * `day` - Day index (`timestamp / 86400`)
+    ///
+    /// # Returns
+    ///
+    /// * `Some(digest)` if the committer committed on that day
+    /// * `None` if there was no activity, or the entry's TTL has lapsed
+    pub fn get_committer_day_digest(
+        env: Env,
+        committer: Address,
+        day: u64,
+    ) -> Option<BytesN<32>> {
+        let key = DataKey::DayDigest(committer, day);
+        env.storage().temporary().get(&key)
+    }
+    
+    /// Number of full commitments this registry holds.
+    ///
+    /// Counts the same paths as `get_commit_count_by_committer`, summed over
+    /// all committers: each stored commitment bumps it exactly once, and
+    /// failed or skipped batch entries not at all. Commitments stored
+    /// before the counter existed are not included.
+    pub fn total_commits(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalCommits)
+            .unwrap_or(0)
+    }
+    
+    /// Number of full commitments recorded for `committer`.
+    ///
+    /// Counts every path that stores a commitment (including existence
+    /// upgrades), but not sandbox, existence-only or event-only entries.
+    /// Commitments stored before the counter existed are not included.
+    ///
+    /// # Returns
+    ///
+    /// * The count, `0` for addresses that never committed
+    pub fn get_commit_count_by_committer(env: Env, committer: Address) -> u64 {
+        Self::get_committer_stats(env, committer).map_or(0, |stats| stats.count)
+    }
+    
+    /// Commitment count and first/last commit times of `committer`.
+    ///
+    /// Counts the same commit paths as `get_commit_count_by_committer`.
+    ///
+    /// # Returns
+    ///
+    /// * `Some(CommitterStats)` once the committer has committed
+    /// * `None` for addresses that never committed
+    pub fn get_committer_stats(env: Env, committer: Address) -> Option<CommitterStats> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CommitterStats(committer))
+    }
+    
+    /// Number of distinct addresses that have stored a full commitment.
+    pub fn committer_count(env: Env) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CommitterListCount)
+            .unwrap_or(0)
+    }
+    
+    /// Page through the distinct committers, in order of their first commitment.
+    ///
+    /// Each address is listed once, when its `CommitterStats` entry is
+    /// created. Resume from `offset + returned.len()` until an empty page.
+    ///
+    /// # Returns
+    ///
+    /// Up to `limit` addresses, capped at `max_range_commits_listed`
+    pub fn get_committers(env: Env, offset: u32, limit: u32) -> Vec<Address> {
+        let count = Self::committer_count(env.clone());
+        Self::page_index(&env, count, offset, limit, DataKey::CommitterListPage)
+    }
+    
+    /// Id of the most recently stored commitment (0 before the first).
+    ///
+    /// Ids run 1..=`latest_commit_id` with no gaps, so an indexer that has
+    /// seen fewer ids knows it missed commitments.
+    pub fn latest_commit_id(env: Env) -> u64 {
+        Self::total_commits(env)
+    }
+    
+    /// Retrieve a commitment by its registry-wide id.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(RouteCommitment)` - The commitment assigned `id`
+    /// * `Err(RegistryError::NotFound)` - No commitment has that id
+    pub fn get_commit_by_id(env: Env, id: u64) -> Result<RouteCommitment, RegistryError> {
+        let route_hash: BytesN<32> = Self::index_entry(&env, id, DataKey::CommitIdPage).ok_or(RegistryError::NotFound)?;
+        Self::load_commitment(&env, &route_hash).ok_or(RegistryError::NotFound)
+    }
+    
+    /// Page through every commitment by id.
+    ///
+    /// Returns the route hashes of ids `start_id, start_id + 1, ...` in id
+    /// order, so the next page starts at `start_id + returned.len()`. A
+    /// `start_id` of 0 or past `latest_commit_id` gives an empty page.
+    ///
+    /// # Returns
+    ///
+    /// Up to `limit` route hashes, capped at `max_range_commits_listed`
+    pub fn get_commit_hashes_range(env: Env, start_id: u64, limit: u32) -> Vec<BytesN<32>> {
+        if start_id == 0 {
+            return Vec::new(&env);
+        }
+        let latest = Self::latest_commit_id(env.clone());
+        let offset = u32::try_from(start_id - 1).unwrap_or(u32::MAX);
+        Self::page_index(&env, latest, offset, limit, DataKey::CommitIdPage)
+    }
+    
+    /// List the most recent commitments, newest first.
+    ///
+    /// A cheap "what happened lately" view for dashboards. There is no ring
+    /// buffer: it scans the commit id index backwards from
+    /// `latest_commit_id`, so it costs nothing on the write path.
+    /// Best-effort and bounded: at most `recent_capacity` commitments are
+    /// reachable this way; older ones need an indexer or
+    /// `get_commit_hashes_range`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<BytesN<32>>)` - up to `n` route hashes, newest first
+    /// * `Err(RegistryError::RecentCapacityExceeded)` - `n` is above `recent_capacity`
+    pub fn get_recent(env: Env, n: u32) -> Result<Vec<BytesN<32>>, RegistryError> {
+        if n > RECENT_CAPACITY {
+            return Err(RegistryError::RecentCapacityExceeded);
+        }
+        let latest = Self::latest_commit_id(env.clone());
+        let storage = env.storage().persistent();
+        let oldest = latest.saturating_sub(n as u64);
+        let hashes = Self::read_pages(&env, latest, oldest, n, |page| storage.get(&DataKey::CommitIdPage(page)));
+        Ok(collections::collect_bounded(&env, hashes.iter().rev(), n))
+    }
+    
+    /// Look up a committer's commitment by its sequence number.
+    ///
+    /// Sequence numbers run 1..=`get_commit_count_by_committer`, so a
+    /// committer can walk its own history and find commits missing from
+    /// its off-chain log.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(BytesN<32>)` - Route hash of the commitment
+    /// * `Err(RegistryError::NotFound)` - No commitment with that sequence number
+    pub fn get_commit_by_committer_seq(
+        env: Env,
+        committer: Address,
+        seq: u64,
+    ) -> Result<BytesN<32>, RegistryError> {
+        Self::index_entry(&env, seq, |page| DataKey::CommitterPage(committer.clone(), page)).ok_or(RegistryError::NotFound)
+    }
+    
+    /// Most recently stored commitment of `committer` and its timestamp.
+    ///
+    /// For monitoring committers that go silent. Read from the committer's
+    /// last sequence entry and the commitment it names, so it follows
+    /// every commit path, batch entries included, at no extra write cost.
+    /// The timestamp is that commitment's own, which for an upgrade or
+    /// reveal is the earlier claim's.
+    ///
+    /// # Returns
+    ///
+    /// * `Some((route_hash, timestamp))` once the committer has committed
+    /// * `None` for addresses that never committed
+    pub fn latest_commit_by_committer(env: Env, committer: Address) -> Option<(BytesN<32>, u64)> {
+        let seq = Self::get_commit_count_by_committer(env.clone(), committer.clone());
+        let route_hash = Self::get_commit_by_committer_seq(env.clone(), committer, seq).ok()?;
+        let commitment = Self::load_commitment(&env, &route_hash)?;
+        Some((route_hash, commitment.timestamp))
+    }
+    
+    /// Page through one committer's commitments in commit order.
+    ///
+    /// Reads the committer's index pages, `index_page_size` commitments per
+    /// storage entry, so no entry grows with the committer's history. Skips
+    /// the first `offset` commitments; the next page starts at
+    /// `offset + returned.len()`.
+    ///
+    /// # Returns
+    ///
+    /// Up to `limit` route hashes, capped at `max_range_commits_listed`
+    /// (empty once `offset` reaches the committer's count)
+    pub fn get_commits_by_committer(env: Env, committer: Address, offset: u32, limit: u32) -> Vec<BytesN<32>> {
+        let count = Self::get_commit_count_by_committer(env.clone(), committer.clone());
+        Self::page_index(&env, count, offset, limit, |page| DataKey::CommitterPage(committer.clone(), page))
+    }
+    
+    /// Number of commitments made under `rules_hash` (0 if unknown).
+    ///
+    /// Lets rules publishers measure adoption of each configuration.
+    /// Counts the same commit paths as `total_commits`.
+    pub fn count_by_rules(env: Env, rules_hash: BytesN<32>) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RulesCount(rules_hash))
+            .unwrap_or(0)
+    }
+    
+    /// Page through the commitments made under `rules_hash`, in commit order.
+    ///
+    /// Paged like `get_commits_by_committer`: resume from
+    /// `offset + returned.len()`.
+    ///
+    /// # Returns
+    ///
+    /// Up to `limit` route hashes, capped at `max_range_commits_listed`
+    /// (empty for unknown rules hashes)
+    pub fn get_commits_by_rules(env: Env, rules_hash: BytesN<32>, offset: u32, limit: u32) -> Vec<BytesN<32>> {
+        let count = Self::count_by_rules(env.clone(), rules_hash.clone());
+        Self::page_index(&env, count, offset, limit, |page| DataKey::RulesPage(rules_hash.clone(), page))
+    }
+    
+    /// Number of commitments made under `solver_hash` (0 if unknown).
+    ///
+    /// The solver-side counterpart of `count_by_rules`.
+    pub fn count_by_solver(env: Env, solver_hash: BytesN<32>) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::SolverCount(solver_hash))
+            .unwrap_or(0)
+    }
+    
+    /// Page through the commitments made under `solver_hash`, in commit order.
+    ///
+    /// Paged like `get_commits_by_rules`.
+    ///
+    /// # Returns
+    ///
+    /// Up to `limit` route hashes, capped at `max_range_commits_listed`
+    /// (empty for unknown solver hashes)
+    pub fn get_commits_by_solver(env: Env, solver_hash: BytesN<32>, offset: u32, limit: u32) -> Vec<BytesN<32>> {
+        let count = Self::count_by_solver(env.clone(), solver_hash.clone());
+        Self::page_index(&env, count, offset, limit, |page| DataKey::SolverPage(solver_hash.clone(), page))
+    }
+    
+    /// Page through the commitments stored on the days a time window covers.
+    ///
+    /// Whole days only: every commitment stored on days
+    /// `start_ts / 86400 ..= end_ts / 86400` is listed, in commit order.
+    /// Callers needing sub-day precision filter on `get_commit().timestamp`.
+    /// Resume from `offset + returned.len()` until an empty page.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<BytesN<32>>)` - up to `limit` route hashes, capped at
+    ///   `max_range_commits_listed`
+    /// * `Err(RegistryError::InvalidWindow)` - `end_ts` is before `start_ts`
+    /// * `Err(RegistryError::WindowTooLong)` - the window covers more than
+    ///   `max_window_days` days
+    pub fn get_commits_in_window(
+        env: Env,
+        start_ts: u64,
+        end_ts: u64,
+        offset: u32,
+        limit: u32,
+    ) -> Result<Vec<BytesN<32>>, RegistryError> {
+        if end_ts < start_ts {
+            return Err(RegistryError::InvalidWindow);
+        }
+        let (first_day, last_day) = (start_ts / SECONDS_PER_DAY, end_ts / SECONDS_PER_DAY);
+        if last_day - first_day >= MAX_WINDOW_DAYS as u64 {
+            return Err(RegistryError::WindowTooLong);
+        }
+        
+        let storage = env.storage().persistent();
+        let mut hashes = Vec::new(&env);
+        let mut skip = offset as u64;
+        let mut remaining = limit.min(MAX_RANGE_COMMITS_LISTED);
+        for day in first_day..=last_day {
+            if remaining == 0 {
+                break;
+            }
+            let count: u64 = storage.get(&DataKey::DayCount(day)).unwrap_or(0);
+            if skip >= count {
+                skip -= count;
+                continue;
+            }
+            let page = Self::page_index(&env, count, skip as u32, remaining, |page| DataKey::DayPage(day, page));
+            skip = 0;
+            remaining -= page.len();
+            hashes.append(&page);
+        }
+        Ok(hashes)
+    }
+    
+    /// Number of commitments stored on day `day_index` (`timestamp / 86400`).
+    ///
+    /// Reads the day bucket's counter only, never its route hashes.
+    pub fn commits_on_day(env: Env, day_index: u64) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::DayCount(day_index))
+            .unwrap_or(0)
+    }
+    
+    /// Daily commitment counts for the last `n` days, oldest first.
+    ///
+    /// The last element is today (by ledger time), so far. `n` is capped at
+    /// `max_stats_days`, and days before the Unix epoch are left out.
+    pub fn commits_last_n_days(env: Env, n: u32) -> Vec<u64> {
+        let today = env.ledger().timestamp() / SECONDS_PER_DAY;
+        let n = n.min(MAX_STATS_DAYS) as u64;
+        let mut counts = Vec::new(&env);
+        if n == 0 {
+            return counts;
+        }
+        for day in today.saturating_sub(n - 1)..=today {
+            counts.push_back(Self::commits_on_day(env.clone(), day));
+        }
+        counts
+    }
+    
+    /// Deterministic pseudo-random sample of the route hashes stored on a day.
+    ///
+    /// For statistical compliance audits: two auditors using the same seed
+    /// get the same sample without trusting each other's tooling. Draw `i`
+    /// (from 0) picks position `sha256(seed || i) mod n` of the day's bucket,
+    /// where `n` is the day's count, `i` is 4 bytes big-endian and the
+    /// digest's first 8 bytes are read as a big-endian integer. A position
+    /// already drawn is replaced by the next undrawn one after it, wrapping
+    /// around, so every draw adds a distinct hash. Results are in draw order.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Soroban environment
+    /// * `day` - Day index (`timestamp / 86400`)
+    /// * `seed` - Shared sampling seed
+    /// * `count` - Sample size, capped at `max_sample_size`
+    ///
+    /// # Returns
+    ///
+    /// The sampled route hashes; the whole day in storage order when `count`
+    /// covers it, and empty for a day with no commitments
+    pub fn sample_day(env: Env, day: u64, seed: BytesN<32>, count: u32) -> Vec<BytesN<32>> {
+        let population = Self::commits_on_day(env.clone(), day);
+        let count = count.min(MAX_SAMPLE_SIZE);
+        let storage = env.storage().persistent();
+        if count as u64 >= population {
+            return Self::read_pages(&env, population, 0, count, |page| storage.get(&DataKey::DayPage(day, page)));
+        }
+        
+        let mut pages: Map<u64, Vec<BytesN<32>>> = Map::new(&env);
+        let mut drawn: Vec<u64> = Vec::new(&env);
+        let mut sample = Vec::new(&env);
+        for i in 0..count {
+            let mut preimage = Bytes::from_array(&env, &seed.to_array());
+            preimage.extend_from_array(&i.to_be_bytes());
+            let digest = env.crypto().sha256(&preimage).to_array();
+            let mut position = u64::from_be_bytes(digest[..8].try_into().unwrap_optimized()) % population;
+            while drawn.contains(position) {
+                position = (position + 1) % population;
+            }
+            drawn.push_back(position);
+            
+            let page = position / INDEX_PAGE_SIZE as u64;
+            let hashes = pages.get(page).unwrap_or_else(|| {
+                let hashes: Vec<BytesN<32>> = storage.get(&DataKey::DayPage(day, page)).unwrap_or_else(|| Vec::new(&env));
+                pages.set(page, hashes.clone());
+                hashes
+            });
+            if let Some(route_hash) = hashes.get((position % INDEX_PAGE_SIZE as u64) as u32) {
+                sample.push_back(route_hash);
+            }
+        }
+        sample
+    }
+    
+    /// List the route hashes committed in one ledger, in commit order.
+    ///
+    /// Position `i` in the result is the commitment whose
+    /// `intra_ledger_index` is `i`. The listing lives in temporary storage
+    /// for ~1 day (`ledger_order_ttl_ledgers`); afterwards auditors rely on
+    /// the index stored on each record and in its event. Sandbox commits
+    /// are never listed.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Soroban environment
+    /// * `sequence` - Ledger sequence number
+    ///
+    /// # Returns
+    ///
+    /// Route hashes in commit order (empty if none, or already evicted),
+    /// capped at the first `max_ledger_commits_listed`
+    pub fn get_ledger_commits(env: Env, sequence: u32) -> Vec<BytesN<32>> {
+        let count: u32 = env
+            .storage()
+            .temporary()
+            .get(&DataKey::LedgerCount(sequence))
+            .unwrap_or(0);
+        
+        let storage = env.storage().temporary();
+        let limit = count.min(MAX_LEDGER_COMMITS_LISTED);
+        Self::read_pages(&env, count as u64, 0, limit, |page| storage.get(&DataKey::LedgerPage(sequence, page as u32)))
+    }
+    
+    /// Report the build provenance baked into this wasm.
+    ///
+    /// Returns the same values embedded as `stella_git`, `stella_profile`,
+    /// `stella_features` and `stella_rustc` contract metadata, so auditors
+    /// can cross-check `stellar contract info meta` against a live call.
+    pub fn get_build_info(env: Env) -> BuildInfo {
+        BuildInfo {
+            git: String::from_str(&env, BUILD_GIT),
+            profile: String::from_str(&env, BUILD_PROFILE),
+            features: String::from_str(&env, BUILD_FEATURES),
+            rustc: String::from_str(&env, BUILD_RUSTC),
+        }
+    }
+    
+    /// Enumerate every numeric limit this deployment enforces.
+    ///
+    /// Keys are stable snake_case names (e.g. `max_expiry_duration`), values
+    /// are the exact constants the validation code uses. SDKs should read
+    /// this at startup instead of hard-coding limits.
+    pub fn get_limits(env: Env) -> Map<Symbol, u64> {
+        let mut map = Map::new(&env);
+        for (name, value) in limits::ALL {
+            map.set(Symbol::new(&env, name), *value);
+        }
+        map
+    }
+    
+    /// Maximum input length of the batch writes (`commit_routes`,
+    /// `commit_routes_partial`, `revoke_commits`, `extend_ttls`); longer
+    /// inputs fail with `TooManyEntries`
+    pub fn max_batch_size(_env: Env) -> u32 {
+        MAX_BATCH_SIZE
+    }
+    
+    /// Maximum input length of the batch reads (`get_commits`,
+    /// `has_commits`, `verify_commits`, `exists_any`, `first_existing`,
+    /// `attest_batch`); longer inputs fail with `TooManyEntries`
+    pub fn max_read_batch_size(_env: Env) -> u32 {
+        MAX_READ_BATCH_SIZE
+    }
+    
+    // ─────────────────────────────────────────────────────────────────
+    // Retention
+    // ─────────────────────────────────────────────────────────────────
+    
+    /// Extend the TTL of several commitments in one call.
+    ///
+    /// For retention policies that keep commitments readable for years.
+    /// Anyone may pay for the extension. Each existing commitment's TTL is
+    /// raised to `extend_to` ledgers if it is currently below that, together
+    /// with its side entries (revocation, cancellation, supersession and split links, scope, secondary
+    /// hash, relayer, signer, binding, consumptions, endorsements, anomaly
+    /// reports) and the index pages and counters listing it. Acks and
+    /// attestations, keyed by the acknowledging or attesting address, are
+    /// left to their owners. Hashes with no commitment are skipped.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(u32)` - number of commitments extended
+    /// * `Err(RegistryError::TtlTooLong)` - `extend_to` exceeds the network's maximum entry TTL
+    /// * `Err(RegistryError::TooManyEntries)` - more than `max_batch_size` hashes
+    pub fn extend_ttls(env: Env, route_hashes: Vec<BytesN<32>>, extend_to: u32) -> Result<u32, RegistryError> {
+        guards::require_batch_size(&route_hashes)?;
+        if extend_to > env.storage().max_ttl() {
+            return Err(RegistryError::TtlTooLong);
+        }
+        
+        let storage = env.storage().persistent();
+        let mut extended = 0;
+        for route_hash in route_hashes.iter() {
+            let key = DataKey::Commit(route_hash.clone());
+            let (commitment, slots) = match storage.get::<_, StoredCommitment>(&key) {
+                Some(stored) => {
+                    storage.extend_ttl(&key, extend_to, extend_to);
+                    let slots = match &stored {
+                        StoredCommitment::V3(_, slots) => Some(slots.clone()),
+                        _ => None,
+                    };
+                    (RouteCommitment::from(stored), slots)
+                }
+                None => {
+                    let legacy_key = LegacyCommitKey { route_hash: route_hash.clone() };
+                    let Some(legacy) = storage.get::<_, LegacyRouteCommitment>(&legacy_key) else {
+                        continue;
+                    };
+                    storage.extend_ttl(&legacy_key, extend_to, extend_to);
+                    (RouteCommitment::from(legacy), None)
+                }
+            };
+            Self::extend_related_entries(&env, &route_hash, &commitment, slots, extend_to);
+            extended += 1;
+        }
+        Ok(extended)
+    }
+    
+    // ─────────────────────────────────────────────────────────────────
+    // Sandbox namespace
+    // ─────────────────────────────────────────────────────────────────
+    
+    /// Commit routing metadata to the sandbox namespace.
+    ///
+    /// For integrators testing against a live deployment. Sandbox commitments
+    /// are validated like `commit_route` but written to temporary storage with
+    /// a fixed ~1 hour TTL, and never touch the real registry: they are
+    /// invisible to `get_commit`/`has_commit`/`verify_commit` and excluded from
+    /// the committer day digests.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Soroban environment
+    /// * `committer` - Address credited with the commitment (must authorize)
+    /// * `route_hash` - SHA-256 hash of the complete route manifest
+    /// * `rules_hash` - SHA-256 hash of the routing rules configuration
+    /// * `solver_version_hash` - SHA-256 hash of the solver version/commit
+    /// * `expiry` - Unix timestamp when quote expires (0 = no expiry)
+    ///
+    /// # Events
+    ///
+    /// Emits under the `sandbox` topic (never `commit`) with the same payload
+    ///
+    /// # Errors
+    ///
+    /// * `EmptyRouteHash` - route_hash is all zeros
+    /// * `EmptyRulesHash` / `EmptySolverHash` - rules or solver hash is all zeros
+    /// * `DuplicateCommitment` - route_hash already live in the sandbox
+    /// * `ExpiredTimestamp` - expiry is in the past
+    /// * `ExpiryTooFar` - expiry exceeds maximum duration
+    pub fn commit_route_sandbox(
+        env: Env,
+        committer: Address,
+        route_hash: BytesN<32>,
+        rules_hash: BytesN<32>,
+        solver_version_hash: BytesN<32>,
+        expiry: u64,
+    ) -> Result<(), RegistryError> {
+        committer.require_auth();
+        
+        let timestamp = env.ledger().timestamp();
+        
+        Self::validate_hashes(&route_hash, &rules_hash, &solver_version_hash)?;
+        
+        let key = DataKey::Sandbox(route_hash.clone());
+        if env.storage().temporary().has(&key) {
+            log!(&env, "Rejected: duplicate sandbox commitment for route_hash");
+            return Err(RegistryError::DuplicateCommitment);
+        }
+        
+        Self::validate_expiry(&env, Expiry::from_raw(expiry), timestamp)?;
+        
+        let commitment = RouteCommitment {
+            rules_hash: rules_hash.clone(),
+            solver_version_hash: solver_version_hash.clone(),
+            committer: committer.clone(),
+            timestamp,
+            expiry,
+            active_from: timestamp,
+            intra_ledger_index: 0,
+            committer_seq: 0,
+            commit_id: 0,
+        };
+        
+        // Temporary storage only: entries evaporate once the TTL lapses
+        env.storage().temporary().set(&key, &commitment);
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, SANDBOX_TTL_LEDGERS, SANDBOX_TTL_LEDGERS);
+        
+        env.events().publish(
+            (symbol_short!("sandbox"), route_hash),
+            (
+                rules_hash,
+                solver_version_hash,
+                committer,
+                timestamp,
+                expiry,
+            ),
+        );
+        
+        Ok(())
+    }
+    
+    /// Retrieve a sandbox commitment while it is still live.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(RouteCommitment)` - Sandbox commitment metadata
+    /// * `Err(RegistryError::NotFound)` - Never committed, or TTL lapsed
+    pub fn get_sandbox_commit(
+        env: Env,
+        route_hash: BytesN<32>,
+    ) -> Result<RouteCommitment, RegistryError> {
+        let key = DataKey::Sandbox(route_hash);
+        
+        env.storage()
+            .temporary()
+            .get(&key)
+            .ok_or(RegistryError::NotFound)
+    }
+    
+    /// Check if a route hash is live in the sandbox namespace.
+    pub fn has_sandbox_commit(env: Env, route_hash: BytesN<32>) -> bool {
+        let key = DataKey::Sandbox(route_hash);
+        env.storage().temporary().has(&key)
+    }
+    
+    // ─────────────────────────────────────────────────────────────────
+    // Corridor dictionary
+    // ─────────────────────────────────────────────────────────────────
+    
+    /// Register a canonical corridor symbol for a directional asset pair.
+    ///
+    /// Permissionless and write-once: the first registration of a symbol and
+    /// of a (source, dest) pair wins, so near-duplicate spellings cannot
+    /// fragment the same corridor. `A → B` and `B → A` are distinct pairs.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Soroban environment
+    /// * `registrar` - Address registering the corridor (must authorize)
+    /// * `corridor` - Canonical corridor symbol, e.g. `USDC_PHP`
+    /// * `source_asset_hash` - Hash identifying the source asset
+    /// * `dest_asset_hash` - Hash identifying the destination asset
+    ///
+    /// # Events
+    ///
+    /// Emits `("corridor", corridor)` with `(registrar, source_asset_hash, dest_asset_hash, registered_at)`
+    ///
+    /// # Errors
+    ///
+    /// * `EmptyAssetHash` - an asset hash is all zeros
+    /// * `IdenticalCorridorAssets` - source and destination are the same asset
+    /// * `CorridorAlreadyRegistered` - symbol already taken
+    /// * `CorridorPairAlreadyRegistered` - pair already has a symbol
+    pub fn register_corridor(
+        env: Env,
+        registrar: Address,
+        corridor: Symbol,
+        source_asset_hash: BytesN<32>,
+        dest_asset_hash: BytesN<32>,
+    ) -> Result<(), RegistryError> {
+        registrar.require_auth();
+        
+        guards::require_nonzero_hash(&source_asset_hash, RegistryError::EmptyAssetHash)?;
+        guards::require_nonzero_hash(&dest_asset_hash, RegistryError::EmptyAssetHash)?;
+        guards::require_distinct(&source_asset_hash, &dest_asset_hash, RegistryError::IdenticalCorridorAssets)?;
+        
+        let key = DataKey::Corridor(corridor.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(RegistryError::CorridorAlreadyRegistered);
+        }
+        let pair_key = DataKey::CorridorPair(source_asset_hash.clone(), dest_asset_hash.clone());
+        if env.storage().persistent().has(&pair_key) {
+            return Err(RegistryError::CorridorPairAlreadyRegistered);
+        }
+        
+        let registered_at = env.ledger().timestamp();
+        let entry = CorridorEntry {
+            registrar: registrar.clone(),
+            source_asset_hash: source_asset_hash.clone(),
+            dest_asset_hash: dest_asset_hash.clone(),
+            registered_at,
+        };
+        env.storage().persistent().set(&key, &entry);
+        env.storage().persistent().set(&pair_key, &corridor);
+        
+        env.events().publish(
+            (symbol_short!("corridor"), corridor),
+            (registrar, source_asset_hash, dest_asset_hash, registered_at),
+        );
+        
+        Ok(())
+    }
+    
+    /// Look up a registered corridor by symbol.
+    pub fn resolve_corridor(env: Env, corridor: Symbol) -> Option<CorridorEntry> {
+        env.storage().persistent().get(&DataKey::Corridor(corridor))
+    }
+    
+    /// Find the corridor symbol registered for a directional asset pair.
+    pub fn find_corridor_by_pair(
+        env: Env,
+        source_asset_hash: BytesN<32>,
+        dest_asset_hash: BytesN<32>,
+    ) -> Option<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CorridorPair(source_asset_hash, dest_asset_hash))
+    }
+    
+    // ─────────────────────────────────────────────────────────────────
+    // Watchtower anomaly reports
+    // ─────────────────────────────────────────────────────────────────
+    
+    /// Report an anomaly against a route hash or a committer.
+    ///
+    /// A sanctioned channel for independent watchtowers to publish red flags
+    /// on-chain. The registry records the report and emits an event; it
+    /// never judges whether the anomaly is real.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Soroban environment
+    /// * `reporter` - Watchtower address (must authorize)
+    /// * `subject` - Route hash or committer the report concerns
+    /// * `evidence_hash` - Hash of the off-chain evidence
+    ///
+    /// # Events
+    ///
+    /// Emits `("anomaly", reporter)` with `(subject, evidence_hash, timestamp)`
+    ///
+    /// # Errors
+    ///
+    /// * `EmptyEvidenceHash` / `EmptyRouteHash` - a hash parameter is all zeros
+    /// * `SelfReferentialAddress` - reporter is the registry or reports itself
+    /// * `AnomalyAlreadyReported` - reporter already reported this subject
+    /// * `TooManyAnomalies` - subject already has the maximum number of reports
+    pub fn report_anomaly(
+        env: Env,
+        reporter: Address,
+        subject: AnomalySubject,
+        evidence_hash: BytesN<32>,
+    ) -> Result<(), RegistryError> {
+        reporter.require_auth();
+        
+        guards::require_external(&env, &reporter, RegistryError::SelfReferentialAddress)?;
+        guards::require_nonzero_hash(&evidence_hash, RegistryError::EmptyEvidenceHash)?;
+        match &subject {
+            AnomalySubject::Route(route_hash) => {
+                guards::require_nonzero_hash(route_hash, RegistryError::EmptyRouteHash)?
+            }
+            AnomalySubject::Committer(committer) => {
+                guards::require_distinct(committer, &reporter, RegistryError::SelfReferentialAddress)?
+            }
+        }
+        
+        let key = DataKey::Anomalies(subject.clone());
+        let mut reports: Vec<AnomalyReport> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Vec::new(&env));
+        
+        // One report per (subject, reporter)
+        if reports.iter().any(|r| r.reporter == reporter) {
+            return Err(RegistryError::AnomalyAlreadyReported);
+        }
+        
+        let timestamp = env.ledger().timestamp();
+        let report = AnomalyReport {
+            reporter: reporter.clone(),
+            evidence_hash: evidence_hash.clone(),
+            timestamp,
+        };
+        collections::bounded_push(&mut reports, report, MAX_ANOMALIES_PER_SUBJECT, RegistryError::TooManyAnomalies)?;
+        env.storage().persistent().set(&key, &reports);
+        
+        env.events().publish(
+            (symbol_short!("anomaly"), reporter),
+            (subject, evidence_hash, timestamp),
+        );
+        
+        Ok(())
+    }
+    
+    /// List the anomaly reports filed against a subject, oldest first.
+    ///
+    /// Returns an empty list when nothing has been reported. Bounded by
+    /// `MAX_ANOMALIES_PER_SUBJECT` entries.
+    pub fn get_anomalies(env: Env, subject: AnomalySubject) -> Vec<AnomalyReport> {
+        let key = DataKey::Anomalies(subject);
+        let reports: Vec<AnomalyReport> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Vec::new(&env));
+        collections::collect_bounded(&env, reports, MAX_ANOMALIES_PER_SUBJECT)
+    }
+    
+    // ─────────────────────────────────────────────────────────────────
+    // Existence-only records
+    // ─────────────────────────────────────────────────────────────────
+    
+    /// Record only that `route_hash` existed now, as claimed by `committer`.
+    ///
+    /// The cheapest record the registry offers, for high-volume commodity
+    /// routes: no rules hash, solver hash or expiry, no ledger ordering and
+    /// no day digest. Existence records live in their own key space and are
+    /// invisible to `get_commit`, `has_commit` and `verify_commit`.
+    ///
+    /// # Events
+    ///
+    /// Emits `("exist", route_hash)` with `(committer, timestamp)`
+    ///
+    /// # Errors
+    ///
+    /// * `EmptyRouteHash` - route_hash is all zeros
+    /// * `DuplicateCommitment` - route_hash already has a full or existence record
+    pub fn commit_existence(
+        env: Env,
+        committer: Address,
+        route_hash: BytesN<32>,
+    ) -> Result<(), RegistryError> {
+        committer.require_auth();
+        
+        guards::require_nonzero_hash(&route_hash, RegistryError::EmptyRouteHash)?;
+        
+        let key = DataKey::Existence(route_hash.clone());
+        if env.storage().persistent().has(&key) || Self::commitment_exists(&env, &route_hash) {
+            log!(&env, "Rejected: duplicate existence record for route_hash");
+            return Err(RegistryError::DuplicateCommitment);
+        }
+        
+        let timestamp = env.ledger().timestamp();
+        let record = ExistenceRecord { committer: committer.clone(), timestamp };
+        env.storage().persistent().set(&key, &record);
+        
+        env.events().publish(
+            (symbol_short!("exist"), route_hash),
+            (committer, timestamp),
+        );
+        
+        Ok(())
+    }
+    
+    /// Retrieve an existence-only record as `(committer, timestamp)`.
+    ///
+    /// Returns `None` once the claimant has turned the claim into a full
+    /// commitment, through `upgrade_existence` or any other commit.
+    pub fn get_existence(env: Env, route_hash: BytesN<32>) -> Option<(Address, u64)> {
+        let key = DataKey::Existence(route_hash);
+        env.storage()
+            .persistent()
+            .get::<_, ExistenceRecord>(&key)
+            .map(|record| (record.committer, record.timestamp))
+    }
+    
+    /// Convert an existence-only record into a full commitment.
+    ///
+    /// Validated like `commit_route`, but the stored record and its event
+    /// carry the original existence claim's timestamp. The day bucket, day
+    /// digest and committer stats follow the upgrade's ledger time, the
+    /// commitment is active from the upgrade onwards and the existence
+    /// record is removed.
+    ///
+    /// # Events
+    ///
+    /// Emits `RouteCommitted`, then `("upgrade", route_hash)` with `(committer, original_timestamp)`
+    ///
+    /// # Errors
+    ///
+    /// * `DuplicateCommitment` - route_hash already has a full commitment
+    /// * `NotFound` - no existence record for route_hash
+    /// * `Unauthorized` - `committer` did not make the existence claim
+    /// * Any `commit_route` validation error
+    pub fn upgrade_existence(
+        env: Env,
+        committer: Address,
+        route_hash: BytesN<32>,
+        rules_hash: BytesN<32>,
+        solver_version_hash: BytesN<32>,
+        expiry: u64,
+    ) -> Result<u64, RegistryError> {
+        committer.require_auth();
+        
+        if Self::commitment_exists(&env, &route_hash) {
+            return Err(RegistryError::DuplicateCommitment);
+        }
+        
+        let key = DataKey::Existence(route_hash.clone());
+        let claim: ExistenceRecord = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(RegistryError::NotFound)?;
+        if claim.committer != committer {
+            log!(&env, "Rejected: existence record belongs to another committer");
+            return Err(RegistryError::Unauthorized);
+        }
+        
+        // Record the full commitment at the original claim's timestamp
+        let committer_seq = Self::record_commitment(
+            &env,
+            committer.clone(),
+            route_hash.clone(),
+            CoreHashes { rules_hash, solver_version_hash },
+            Expiry::from_raw(expiry),
+            None,
+            claim.timestamp,
+        )?;
+        
+        env.events().publish(
+            (symbol_short!("upgrade"), route_hash),
+            (committer, claim.timestamp),
+        );
+        
+        Ok(committer_seq)
+    }
+    
+    // ─────────────────────────────────────────────────────────────────
+    // Blinded commitments
+    // ─────────────────────────────────────────────────────────────────
+    
+    /// Commit a blinded digest now and reveal the route later.
+    ///
+    /// `blinded_hash` is `sha256(route_hash || rules_hash || solver_version_hash || salt)`,
+    /// so the route stays private until `reveal`. Validated like
+    /// `commit_route`; blinded digests live in their own namespace.
+    ///
+    /// # Events
+    ///
+    /// Emits `("blinded", blinded_hash)` with `(committer, timestamp, expiry)`
+    ///
+    /// # Errors
+    ///
+    /// * `EmptyRouteHash` - blinded_hash is all zeros
+    /// * `DuplicateCommitment` - blinded_hash already committed
+    /// * `ExpiredTimestamp` / `ExpiryTooFar` - expiry out of bounds
+    pub fn commit_blinded(
+        env: Env,
+        committer: Address,
+        blinded_hash: BytesN<32>,
+        expiry: u64,
+    ) -> Result<(), RegistryError> {
+        committer.require_auth();
+        
+        guards::require_nonzero_hash(&blinded_hash, RegistryError::EmptyRouteHash)?;
+        let key = DataKey::Blinded(blinded_hash.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(RegistryError::DuplicateCommitment);
+        }
+        let timestamp = env.ledger().timestamp();
+        Self::validate_expiry(&env, Expiry::from_raw(expiry), timestamp)?;
+        
+        let record = BlindedRecord { committer: committer.clone(), timestamp, expiry };
+        env.storage().persistent().set(&key, &record);
+        
+        env.events().publish(
+            (symbol_short!("blinded"), blinded_hash),
+            (committer, timestamp, expiry),
+        );
+        
+        Ok(())
+    }
+    
+    /// Retrieve a blinded commitment that has not been revealed yet
+    pub fn get_blinded(env: Env, blinded_hash: BytesN<32>) -> Option<BlindedRecord> {
+        env.storage().persistent().get(&DataKey::Blinded(blinded_hash))
+    }
+    
+    /// Reveal a blinded commitment as a full route commitment.
+    ///
+    /// Recomputes the blinded digest from the preimage and, if it matches,
+    /// commits the route for the blinded committer (who must authorize) with
+    /// the blinded entry's expiry. As in `upgrade_existence`, the record and
+    /// its event carry the blinded commit's timestamp while the indexes
+    /// follow the reveal's ledger time, and the blinded entry is removed.
+    ///
+    /// # Events
+    ///
+    /// Emits `RouteCommitted`, then `("revealed", blinded_hash)` with `(route_hash, original_timestamp)`
+    ///
+    /// # Errors
+    ///
+    /// * `NotFound` - no blinded entry for blinded_hash
+    /// * `QuoteExpired` - the blinded entry has expired
+    /// * `RevealMismatch` - the preimage does not hash to blinded_hash
+    /// * Any `commit_route` validation error
+    pub fn reveal(
+        env: Env,
+        blinded_hash: BytesN<32>,
+        route_hash: BytesN<32>,
+        rules_hash: BytesN<32>,
+        solver_version_hash: BytesN<32>,
+        salt: BytesN<32>,
+    ) -> Result<u64, RegistryError> {
+        let key = DataKey::Blinded(blinded_hash.clone());
+        let record: BlindedRecord = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(RegistryError::NotFound)?;
+        record.committer.require_auth();
+        
+        if Expiry::from_raw(record.expiry).is_expired_at(env.ledger().timestamp()) {
+            return Err(RegistryError::QuoteExpired);
+        }
+        
+        let mut preimage = Bytes::new(&env);
+        preimage.append(&route_hash.clone().into());
+        preimage.append(&rules_hash.clone().into());
+        preimage.append(&solver_version_hash.clone().into());
+        preimage.append(&salt.into());
+        let digest: BytesN<32> = env.crypto().sha256(&preimage).into();
+        if digest != blinded_hash {
+            log!(&env, "Rejected: reveal does not match blinded digest");
+            return Err(RegistryError::RevealMismatch);
+        }
+        
+        // Record the full commitment at the blinded commit's timestamp
+        let committer_seq = Self::record_commitment(
+            &env,
+            record.committer,
+            route_hash.clone(),
+            CoreHashes { rules_hash, solver_version_hash },
+            Expiry::from_raw(record.expiry),
+            None,
+            record.timestamp,
+        )?;
+        env.storage().persistent().remove(&key);
+        
+        env.events().publish(
+            (symbol_short!("revealed"), blinded_hash),
+            (route_hash, record.timestamp),
+        );
+        
+        Ok(committer_seq)
+    }
+    
+    // ─────────────────────────────────────────────────────────────────
+    // Event-only commitments
+    // ─────────────────────────────────────────────────────────────────
+    
+    /// Publish a commitment as an event only, without storing it.
+    ///
+    /// For partners who need just the timing proof (the event and its ledger
+    /// close time) and archive it themselves. Hashes are validated as in
+    /// `commit_route`, but there is no duplicate check and no route entry is
+    /// written: `has_commit` stays `false`, and a later full commit of the
+    /// same hash is allowed. The only write is the instance-level counter.
+    ///
+    /// # Events
+    ///
+    /// Emits `("commit_eo", route_hash)` with
+    /// `(rules_hash, solver_version_hash, committer, timestamp, count)`,
+    /// where `count` is the running event-only total including this one
+    ///
+    /// # Errors
+    ///
+    /// * `EmptyRouteHash` / `EmptyRulesHash` / `EmptySolverHash` - a hash is all zeros
+    /// * `CommitCountOverflow` - the event-only counter cannot be incremented
+    pub fn emit_commit_only(
+        env: Env,
+        committer: Address,
+        route_hash: BytesN<32>,
+        rules_hash: BytesN<32>,
+        solver_version_hash: BytesN<32>,
+    ) -> Result<(), RegistryError> {
+        committer.require_auth();
+        
+        Self::validate_hashes(&route_hash, &rules_hash, &solver_version_hash)?;
+        
+        let count = Self::get_event_only_count(env.clone())
+            .checked_add(1)
+            .ok_or(RegistryError::CommitCountOverflow)?;
+        env.storage().instance().set(&DataKey::EventOnlyCount, &count);
+        
+        env.events().publish(
+            (symbol_short!("commit_eo"), route_hash),
+            (
+                rules_hash,
+                solver_version_hash,
+                committer,
+                env.ledger().timestamp(),
+                count,
+            ),
+        );
+        
+        Ok(())
+    }
+    
+    /// Number of event-only commitments emitted by this deployment.
+    pub fn get_event_only_count(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::EventOnlyCount)
+            .unwrap_or(0)
+    }
+    
+    // ─────────────────────────────────────────────────────────────────
+    // Merkle batch roots
+    // ─────────────────────────────────────────────────────────────────
+    
+    /// Commit a Merkle root covering a batch of route hashes.
+    ///
+    /// One write for thousands of quotes: the committer publishes the root
+    /// of a tree whose leaves are the batch's route hashes. Batch roots live
+    /// in their own namespace and are validated like `commit_route`.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Soroban environment
+    /// * `committer` - Address credited with the batch (must authorize)
+    /// * `batch_root` - Merkle root over the batch's route hashes
+    /// * `rules_hash` - Hash of the routing rules shared by the batch
+    /// * `solver_version_hash` - Hash of the solver version shared by the batch
+    /// * `expiry` - Unix timestamp when the batch's quotes expire (0 = no expiry)
+    /// * `leaf_count` - Number of route hashes under the root
+    ///
+    /// # Events
+    ///
+    /// Emits `BatchCommitted` as `("batch", batch_root)` with
+    /// `(rules_hash, solver_version_hash, committer, timestamp, expiry, leaf_count)`
+    ///
+    /// # Errors
+    ///
+    /// * `EmptyRouteHash` - batch_root is all zeros
+    /// * `EmptyRulesHash` / `EmptySolverHash` - rules or solver hash is all zeros
+    /// * `EmptyBatch` - leaf_count is zero
+    /// * `DuplicateCommitment` - batch_root already committed
+    /// * `ExpiredTimestamp` - expiry is in the past
+    /// * `ExpiryTooFar` - expiry exceeds maximum duration
+    pub fn commit_batch_root(
+        env: Env,
+        committer: Address,
+        batch_root: BytesN<32>,
+        rules_hash: BytesN<32>,
+        solver_version_hash: BytesN<32>,
+        expiry: u64,
+        leaf_count: u32,
+    ) -> Result<(), RegistryError> {
+        committer.require_auth();
+        
+        let timestamp = env.ledger().timestamp();
+        Self::validate_hashes(&batch_root, &rules_hash, &solver_version_hash)?;
+        if leaf_count == 0 {
+            return Err(RegistryError::EmptyBatch);
+        }
+        
+        let key = DataKey::Batch(batch_root.clone());
+        if env.storage().persistent().has(&key) {
+            log!(&env, "Rejected: duplicate batch root");
+            return Err(RegistryError::DuplicateCommitment);
+        }
+        
+        Self::validate_expiry(&env, Expiry::from_raw(expiry), timestamp)?;
+        
+        let batch = BatchCommitment {
+            rules_hash: rules_hash.clone(),
+            solver_version_hash: solver_version_hash.clone(),
+            committer: committer.clone(),
+            timestamp,
+            expiry,
+            leaf_count,
+        };
+        env.storage().persistent().set(&key, &batch);
+        
+        env.events().publish(
+            (symbol_short!("batch"), batch_root),
+            (rules_hash, solver_version_hash, committer, timestamp, expiry, leaf_count),
+        );
+        
+        Ok(())
+    }
+    
+    /// Retrieve a batch commitment by its Merkle root.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(BatchCommitment)` - The batch metadata
+    /// * `Err(RegistryError::NotFound)` - No batch exists for this root
+    pub fn get_batch(env: Env, batch_root: BytesN<32>) -> Result<BatchCommitment, RegistryError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Batch(batch_root))
+            .ok_or(RegistryError::NotFound)
+    }
+    
+    /// Check that `route_hash` is leaf `index` of a committed batch root.
+    ///
+    /// Leaves are route hashes and each parent is `sha256(left || right)`.
+    /// A level with an odd number of nodes pairs its last node with itself.
+    /// Bit `i` of `index` says whether the node at level `i` is a right child,
+    /// so the proof must hold exactly one sibling per level of the tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Soroban environment
+    /// * `batch_root` - Committed Merkle root
+    /// * `route_hash` - Leaf to prove
+    /// * `proof` - Sibling hashes from the leaf level up to the root
+    /// * `index` - Position of the leaf in the batch (0-based)
+    ///
+    /// # Returns
+    ///
+    /// * `true` if the batch exists, has not expired, and the proof hashes to its root
+    /// * `false` otherwise, including for an out-of-range index or a proof of the wrong length
+    pub fn verify_inclusion(
+        env: Env,
+        batch_root: BytesN<32>,
+        route_hash: BytesN<32>,
+        proof: Vec<BytesN<32>>,
+        index: u32,
+    ) -> bool {
+        let batch: BatchCommitment = match env.storage().persistent().get(&DataKey::Batch(batch_root.clone())) {
+            Some(batch) => batch,
+            None => return false,
+        };
+        if Expiry::from_raw(batch.expiry).is_expired_at(env.ledger().timestamp()) {
+            return false;
+        }
+        if index >= batch.leaf_count {
+            return false;
+        }
+        let depth = u32::BITS - (batch.leaf_count - 1).leading_zeros();
+        if proof.len() != depth {
+            return false;
+        }
+        
+        let mut node = route_hash;
+        for (level, sibling) in proof.iter().enumerate() {
+            let mut pair = Bytes::new(&env);
+            if (index >> level) & 1 == 0 {
+                pair.append(&node.into());
+                pair.append(&sibling.into());
+            } else {
+                pair.append(&sibling.into());
+                pair.append(&node.into());
+            }
+            node = env.crypto().sha256(&pair).into();
+        }
+        node == batch_root
+    }
+    
+    // ─────────────────────────────────────────────────────────────────
+    // Consume-once markers
+    // ─────────────────────────────────────────────────────────────────
+    
+    /// Mark an active commitment as consumed by `consumer`.
+    ///
+    /// Lets integrations such as escrows use a commitment at most once
+    /// without the registry taking sides: markers are kept per consumer, so
+    /// each consumer can consume a commitment once, independently of others.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Soroban environment
+    /// * `consumer` - Address or contract consuming the commitment (must authorize)
+    /// * `route_hash` - Commitment being consumed
+    /// * `consumption_ref` - Consumer-side reference for this use
+    ///
+    /// # Events
+    ///
+    /// Emits `("consumed", route_hash)` with `(consumer, consumption_ref, at)`
+    ///
+    /// # Errors
+    ///
+    /// * `EmptyRouteHash` / `EmptyConsumptionRef` - a hash parameter is all zeros
+    /// * `NotFound` - no commitment exists for `route_hash`
+    /// * `CommitmentNotActive` - commitment is scheduled, expired or revoked
+    /// * `AlreadyConsumed` - consumer already consumed this commitment
+    /// * `TooManyConsumptions` - commitment already has the maximum number of markers
+    pub fn mark_consumed(
+        env: Env,
+        consumer: Address,
+        route_hash: BytesN<32>,
+        consumption_ref: BytesN<32>,
+    ) -> Result<(), RegistryError> {
+        consumer.require_auth();
+        
+        guards::require_nonzero_hash(&route_hash, RegistryError::EmptyRouteHash)?;
+        guards::require_nonzero_hash(&consumption_ref, RegistryError::EmptyConsumptionRef)?;
+        
+        let at = env.ledger().timestamp();
+        let commitment = Self::load_commitment(&env, &route_hash).ok_or(RegistryError::NotFound)?;
+        if Self::current_status(&env, &route_hash, &commitment) != CommitmentStatus::Active {
+            return Err(RegistryError::CommitmentNotActive);
+        }
+        
+        let key = DataKey::Consumptions(route_hash.clone());
+        let mut records: Vec<ConsumptionRecord> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Vec::new(&env));
+        
+        // Once per (route_hash, consumer)
+        if records.iter().any(|r| r.consumer == consumer) {
+            return Err(RegistryError::AlreadyConsumed);
+        }
+        
+        let record = ConsumptionRecord {
+            consumer: consumer.clone(),
+            consumption_ref: consumption_ref.clone(),
+            at,
+        };
+        collections::bounded_push(&mut records, record, MAX_CONSUMPTIONS_PER_ROUTE, RegistryError::TooManyConsumptions)?;
+        env.storage().persistent().set(&key, &records);
+        
+        env.events().publish(
+            (symbol_short!("consumed"), route_hash),
+            (consumer, consumption_ref, at),
+        );
+        
+        Ok(())
+    }
+    
+    /// Get every consumption marker left on a commitment, oldest first
+    pub fn get_consumptions(env: Env, route_hash: BytesN<32>) -> Vec<ConsumptionRecord> {
+        let records: Vec<ConsumptionRecord> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Consumptions(route_hash))
+            .unwrap_or_else(|| Vec::new(&env));
+        collections::collect_bounded(&env, records, MAX_CONSUMPTIONS_PER_ROUTE)
+    }
+    
+    /// Check whether `consumer` has consumed the commitment
+    pub fn was_consumed_by(env: Env, route_hash: BytesN<32>, consumer: Address) -> bool {
+        Self::get_consumptions(env, route_hash)
+            .iter()
+            .any(|r| r.consumer == consumer)
+    }
+    
+    // ─────────────────────────────────────────────────────────────────
+    // Endorsements
+    // ─────────────────────────────────────────────────────────────────
+    
+    /// Endorse an existing commitment as a second, independent party.
+    ///
+    /// For routes that need more than one party to stand behind them (e.g.
+    /// solver operator and compliance reviewer). Endorsing a commitment the
+    /// endorser already endorsed is a no-op that keeps the original timestamp.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Soroban environment
+    /// * `route_hash` - Commitment being endorsed
+    /// * `endorser` - Endorsing party (must authorize, must not be the committer)
+    ///
+    /// # Events
+    ///
+    /// Emits `("endorsed", route_hash)` with `(endorser, timestamp)` on the first endorsement
+    ///
+    /// # Errors
+    ///
+    /// * `NotFound` - no commitment exists for `route_hash`
+    /// * `SelfReferentialAddress` - endorser is the committer
+    /// * `TooManyEndorsements` - commitment already has the maximum number of endorsers
+    pub fn endorse_commit(env: Env, route_hash: BytesN<32>, endorser: Address) -> Result<(), RegistryError> {
+        endorser.require_auth();
+        
+        let commitment = Self::load_commitment(&env, &route_hash).ok_or(RegistryError::NotFound)?;
+        guards::require_distinct(&commitment.committer, &endorser, RegistryError::SelfReferentialAddress)?;
+        
+        let key = DataKey::Endorsement(route_hash.clone(), endorser.clone());
+        if env.storage().persistent().has(&key) {
+            return Ok(());
+        }
+        
+        let list_key = DataKey::Endorsers(route_hash.clone());
+        let mut endorsers: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&list_key)
+            .unwrap_or_else(|| Vec::new(&env));
+        collections::bounded_push(&mut endorsers, endorser.clone(), MAX_ENDORSEMENTS_PER_ROUTE, RegistryError::TooManyEndorsements)?;
+        
+        let timestamp = env.ledger().timestamp();
+        env.storage().persistent().set(&list_key, &endorsers);
+        env.storage().persistent().set(&key, &timestamp);
+        
+        env.events().publish(
+            (symbol_short!("endorsed"), route_hash),
+            (endorser, timestamp),
+        );
+        
+        Ok(())
+    }
+    
+    /// Get every endorser of a commitment, in endorsement order
+    pub fn get_endorsements(env: Env, route_hash: BytesN<32>) -> Vec<Address> {
+        let endorsers: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Endorsers(route_hash))
+            .unwrap_or_else(|| Vec::new(&env));
+        collections::collect_bounded(&env, endorsers, MAX_ENDORSEMENTS_PER_ROUTE)
+    }
+    
+    /// Check whether `endorser` has endorsed the commitment
+    pub fn is_endorsed_by(env: Env, route_hash: BytesN<32>, endorser: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Endorsement(route_hash, endorser))
+    }
+    
+    // ─────────────────────────────────────────────────────────────────
+    // Auditor attestations
+    // ─────────────────────────────────────────────────────────────────
+    
+    /// Record an attester's sign-off on an existing commitment.
+    ///
+    /// For audit jobs that re-verify commitments off-chain. Attesting again
+    /// refreshes the timestamp, so `get_attestation` shows the latest check.
+    ///
+    /// # Events
+    ///
+    /// Emits `("attested", route_hash)` with `(attester, timestamp)` on every attestation
+    ///
+    /// # Errors
+    ///
+    /// * `NotFound` - no commitment exists for `route_hash`
+    pub fn attest(env: Env, route_hash: BytesN<32>, attester: Address) -> Result<(), RegistryError> {
+        attester.require_auth();
+        
+        if !Self::record_attestation(&env, route_hash, &attester) {
+            return Err(RegistryError::NotFound);
+        }
+        Ok(())
+    }
+    
+    /// Record one attester's sign-off on several commitments in one call.
+    ///
+    /// The attester authorizes once. Each existing route is attested as in
+    /// `attest`, with its own event; missing routes are skipped and reported
+    /// as `false` instead of failing the batch.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<bool>)` - whether each route was attested
+    /// * `Err(RegistryError::TooManyEntries)` - more than `max_read_batch_size` hashes
+    pub fn attest_batch(
+        env: Env,
+        attester: Address,
+        route_hashes: Vec<BytesN<32>>,
+    ) -> Result<Vec<bool>, RegistryError> {
+        attester.require_auth();
+        guards::require_read_batch_size(&route_hashes)?;
+        
+        let mut flags = Vec::new(&env);
+        for route_hash in route_hashes.iter() {
+            flags.push_back(Self::record_attestation(&env, route_hash, &attester));
+        }
+        Ok(flags)
+    }
+    
+    /// Get when `attester` last attested a commitment, if it did
+    pub fn get_attestation(env: Env, route_hash: BytesN<32>, attester: Address) -> Option<u64> {
+        env.storage().persistent().get(&DataKey::Attestation(route_hash, attester))
+    }
+    
+    // ─────────────────────────────────────────────────────────────────
+    // User acknowledgments
+    // ─────────────────────────────────────────────────────────────────
+    
+    /// Record that `user` saw and accepted a quoted route before execution.
+    ///
+    /// Settles "I never agreed to that rate" disputes. Acknowledging a route
+    /// the user already acknowledged is a no-op that keeps the original
+    /// timestamp.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Soroban environment
+    /// * `route_hash` - Commitment of the quote being accepted
+    /// * `user` - End user accepting the quote (must authorize)
+    ///
+    /// # Events
+    ///
+    /// Emits `RouteAcked` as `("acked", route_hash)` with `(user, timestamp)` on the first acknowledgment
+    ///
+    /// # Errors
+    ///
+    /// * `NotFound` - no commitment exists for `route_hash`
+    /// * `CommitmentNotActive` - the commitment has been revoked or cancelled
+    /// * `QuoteExpired` - the commitment's expiry has passed
+    pub fn ack_route(env: Env, route_hash: BytesN<32>, user: Address) -> Result<(), RegistryError> {
+        user.require_auth();
+        
+        let commitment = Self::load_commitment(&env, &route_hash).ok_or(RegistryError::NotFound)?;
+        let timestamp = env.ledger().timestamp();
+        // A scheduled quote can be accepted ahead of its window
+        match Self::current_status(&env, &route_hash, &commitment) {
+            CommitmentStatus::Revoked | CommitmentStatus::Cancelled => return Err(RegistryError::CommitmentNotActive),
+            CommitmentStatus::Expired => return Err(RegistryError::QuoteExpired),
+            CommitmentStatus::Scheduled | CommitmentStatus::Active => {}
+        }
+        
+        let key = DataKey::Ack(route_hash.clone(), user.clone());
+        if env.storage().persistent().has(&key) {
+            return Ok(());
+        }
+        env.storage().persistent().set(&key, &timestamp);
+        
+        env.events().publish((symbol_short!("acked"), route_hash), (user, timestamp));
+        
+        Ok(())
+    }
+    
+    /// Get when `user` acknowledged a route, if they did
+    pub fn get_ack(env: Env, route_hash: BytesN<32>, user: Address) -> Option<u64> {
+        env.storage().persistent().get(&DataKey::Ack(route_hash, user))
+    }
+    
+    // ─────────────────────────────────────────────────────────────────
+    // Delegated committing
+    // ─────────────────────────────────────────────────────────────────
+    
+    /// Allow `delegate` to commit on `owner`'s behalf until revoked.
+    ///
+    /// Lets owners rotate hot keys without signing an auth entry for every
+    /// commit. The grant is unrestricted; approving an existing delegation
+    /// replaces its grant and refreshes its timestamp.
+    ///
+    /// # Events
+    ///
+    /// Emits `("delegate", owner)` with `(delegate, approved_at)`
+    ///
+    /// # Errors
+    ///
+    /// * `SelfReferentialAddress` - owner and delegate are the same address
+    pub fn approve_delegate(env: Env, owner: Address, delegate: Address) -> Result<(), RegistryError> {
+        owner.require_auth();
+        
+        guards::require_distinct(&owner, &delegate, RegistryError::SelfReferentialAddress)?;
+        
+        let approved_at = env.ledger().timestamp();
+        let grant = DelegationGrant {
+            delegate: delegate.clone(),
+            approved_at,
+            expires_at: 0,
+            allowed_namespace: None,
+            allowed_corridor: None,
+            remaining_commits: UNLIMITED_COMMITS,
+        };
+        let key = DataKey::Delegation(owner.clone(), delegate.clone());
+        env.storage().persistent().set(&key, &grant);
+        
+        env.events().publish(
+            (symbol_short!("delegate"), owner),
+            (delegate, approved_at),
+        );
+        
+        Ok(())
+    }
+    
+    /// Allow `delegate` to commit on `owner`'s behalf within limits.
+    ///
+    /// For time-boxed, scope-limited hot keys. Every restriction is checked
+    /// on each `commit_as` / `commit_as_scoped`, and each commit counts
+    /// down `max_commits`; a grant that runs out stays readable but no
+    /// longer works. Granting again replaces the grant; `revoke_delegate`
+    /// withdraws it.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Soroban environment
+    /// * `owner` - Address commitments are credited to (must authorize)
+    /// * `delegate` - Address allowed to commit
+    /// * `expires_at` - Unix timestamp the grant stops working at (0 = never)
+    /// * `allowed_namespace` - Only commits declared in this namespace, if set
+    /// * `allowed_corridor` - Only commits declared for this registered corridor, if set
+    /// * `max_commits` - Commits allowed; `UNLIMITED_COMMITS` for no cap
+    ///
+    /// # Events
+    ///
+    /// Emits `("grant", owner)` with the stored `DelegationGrant`
+    ///
+    /// # Errors
+    ///
+    /// * `SelfReferentialAddress` - owner and delegate are the same address
+    /// * `EmptyBatch` - `max_commits` is zero
+    /// * `ExpiredTimestamp` / `ExpiryTooFar` - `expires_at` is past or too far out
+    /// * `NotFound` - `allowed_corridor` is not a registered corridor
+    pub fn grant_delegate(
+        env: Env,
+        owner: Address,
+        delegate: Address,
+        expires_at: u64,
+        allowed_namespace: Option<Symbol>,
+        allowed_corridor: Option<Symbol>,
+        max_commits: u32,
+    ) -> Result<(), RegistryError> {
+        owner.require_auth();
+        
+        guards::require_distinct(&owner, &delegate, RegistryError::SelfReferentialAddress)?;
+        if max_commits == 0 {
+            return Err(RegistryError::EmptyBatch);
+        }
+        let approved_at = env.ledger().timestamp();
+        Self::validate_expiry(&env, Expiry::from_raw(expires_at), approved_at)?;
+        if let Some(corridor) = &allowed_corridor {
+            Self::resolve_corridor(env.clone(), corridor.clone()).ok_or(RegistryError::NotFound)?;
+        }
+        
+        let grant = DelegationGrant {
+            delegate: delegate.clone(),
+            approved_at,
+            expires_at,
+            allowed_namespace,
+            allowed_corridor,
+            remaining_commits: max_commits,
+        };
+        let key = DataKey::Delegation(owner.clone(), delegate);
+        env.storage().persistent().set(&key, &grant);
+        
+        env.events().publish((symbol_short!("grant"), owner), grant);
+        
+        Ok(())
+    }
+    
+    /// Withdraw a delegation made with `approve_delegate`.
+    ///
+    /// # Events
+    ///
+    /// Emits `("revoked", owner)` with `delegate`
+    ///
+    /// # Errors
+    ///
+    /// * `NotFound` - `delegate` holds no delegation from `owner`
+    pub fn revoke_delegate(env: Env, owner: Address, delegate: Address) -> Result<(), RegistryError> {
+        owner.require_auth();
+        
+        let key = DataKey::Delegation(owner.clone(), delegate.clone());
+        if !env.storage().persistent().has(&key) {
+            return Err(RegistryError::NotFound);
+        }
+        env.storage().persistent().remove(&key);
+        
+        env.events().publish((symbol_short!("revoked"), owner), delegate);
+        
+        Ok(())
+    }
+    
+    /// Check whether `delegate` may currently commit for `owner`: it holds
+    /// a grant that has neither expired nor run out
+    pub fn is_delegate(env: Env, owner: Address, delegate: Address) -> bool {
+        Self::get_grant(env.clone(), owner, delegate).is_some_and(|grant| Self::grant_usable(&env, &grant))
+    }
+    
+    /// Get the grant `delegate` holds from `owner`, if any, including
+    /// expired and exhausted ones
+    pub fn get_grant(env: Env, owner: Address, delegate: Address) -> Option<DelegationGrant> {
+        env.storage().persistent().get(&DataKey::Delegation(owner, delegate))
+    }
+    
+    /// Commit a route as `owner` through an approved delegate.
+    ///
+    /// Only `delegate` authorizes; the commitment credits `owner`. Arguments
+    /// after `delegate` are those of `commit_route`. The commit declares no
+    /// scope, so it needs a grant without namespace or corridor restrictions.
+    ///
+    /// # Events
+    ///
+    /// Emits `RouteCommitted` with `owner` as the committer, then
+    /// `("delegated", route_hash)` with `(delegate, remaining_commits, None)`,
+    /// and `("grant_exhausted", owner)` with `delegate` if that was the
+    /// grant's last commit
+    ///
+    /// # Errors
+    ///
+    /// Same as `commit_route`, plus:
+    ///
+    /// * `Unauthorized` - `delegate` holds no usable grant from `owner`, or
+    ///   the grant restricts the namespace or corridor
+    pub fn commit_as(
+        env: Env,
+        owner: Address,
+        delegate: Address,
+        route_hash: BytesN<32>,
+        rules_hash: BytesN<32>,
+        solver_version_hash: BytesN<32>,
+        expiry: u64,
+    ) -> Result<u64, RegistryError> {
+        delegate.require_auth();
+        
+        let core = CoreHashes { rules_hash, solver_version_hash };
+        Self::commit_delegated(&env, owner, delegate, route_hash, core, expiry, None)
+    }
+    
+    /// Commit a route as `owner` through a delegate, declaring its scope.
+    ///
+    /// Like `commit_as`, for grants restricted to a namespace or corridor:
+    /// the declared `scope` must satisfy every restriction of the grant,
+    /// and is stored beside the commitment, readable with `get_scope`.
+    /// The commitment itself is passed as a `CommitEntry`.
+    ///
+    /// # Events
+    ///
+    /// As `commit_as`, with `Some(scope)` in the `delegated` event
+    ///
+    /// # Errors
+    ///
+    /// Same as `commit_as`, plus:
+    ///
+    /// * `NotFound` - `scope.corridor` is not a registered corridor
+    /// * `Unauthorized` - the scope is outside the grant's namespace or corridor
+    pub fn commit_as_scoped(
+        env: Env,
+        owner: Address,
+        delegate: Address,
+        scope: CommitScope,
+        entry: CommitEntry,
+    ) -> Result<u64, RegistryError> {
+        delegate.require_auth();
+        
+        Self::resolve_corridor(env.clone(), scope.corridor.clone()).ok_or(RegistryError::NotFound)?;
+        let core = CoreHashes { rules_hash: entry.rules_hash, solver_version_hash: entry.solver_version_hash };
+        Self::commit_delegated(&env, owner, delegate, entry.route_hash, core, entry.expiry, Some(scope))
+    }
+    
+    /// Get the scope a delegate declared for a commitment, if any
+    pub fn get_scope(env: Env, route_hash: BytesN<32>) -> Option<CommitScope> {
+        env.storage().persistent().get(&DataKey::Scope(route_hash))
+    }
+    
+    // ─────────────────────────────────────────────────────────────────
+    // Committer profiles
+    // ─────────────────────────────────────────────────────────────────
+    
+    /// Publish or update `committer`'s display profile.
+    ///
+    /// Gives wallets something more meaningful than a raw address to show.
+    /// Only the committer itself can write its profile; commitments do not
+    /// require one.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Soroban environment
+    /// * `committer` - Profile owner (must authorize)
+    /// * `name_hash` - Hash of the display name
+    /// * `metadata_uri` - URI of the off-chain metadata document
+    ///
+    /// # Events
+    ///
+    /// Emits `ProfileUpdated` as `("profile", committer)` with `(name_hash, metadata_uri, updated_at)`
+    ///
+    /// # Errors
+    ///
+    /// * `EmptyNameHash` - name_hash is all zeros
+    /// * `ProfileUriTooLong` - metadata_uri is longer than `max_profile_uri_bytes`
+    pub fn register_profile(
+        env: Env,
+        committer: Address,
+        name_hash: BytesN<32>,
+        metadata_uri: Bytes,
+    ) -> Result<(), RegistryError> {
+        committer.require_auth();
+        
+        guards::require_nonzero_hash(&name_hash, RegistryError::EmptyNameHash)?;
+        if metadata_uri.len() > MAX_PROFILE_URI_BYTES {
+            return Err(RegistryError::ProfileUriTooLong);
+        }
+        
+        let updated_at = env.ledger().timestamp();
+        let profile = CommitterProfile {
+            name_hash: name_hash.clone(),
+            metadata_uri: metadata_uri.clone(),
+            updated_at,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Profile(committer.clone()), &profile);
+        
+        env.events().publish(
+            (symbol_short!("profile"), committer),
+            (name_hash, metadata_uri, updated_at),
+        );
+        
+        Ok(())
+    }
+    
+    /// Get `committer`'s display profile, if it published one
+    pub fn get_profile(env: Env, committer: Address) -> Option<CommitterProfile> {
+        env.storage().persistent().get(&DataKey::Profile(committer))
+    }
+    
+    // ─────────────────────────────────────────────────────────────────
+    // Committer key rotation
+    // ─────────────────────────────────────────────────────────────────
+    
+    /// Retire `old` in favour of `new`, keeping one audit trail across both.
+    ///
+    /// Records a one-way `old → new` pointer. Commitments stay credited to
+    /// the key that made them; `resolve_committer` maps every key in a chain
+    /// to its newest one, and the canonical variants below treat all keys in
+    /// a chain as the same committer. `new` must be a fresh key, so chains
+    /// are linear and cannot loop.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Soroban environment
+    /// * `old` - Key being retired (must authorize)
+    /// * `new` - Key taking over
+    ///
+    /// # Events
+    ///
+    /// Emits `("rotated", old)` with `new`
+    ///
+    /// # Errors
+    ///
+    /// * `SelfReferentialAddress` - `old` and `new` are the same address
+    /// * `AlreadyRotated` - `old` already has a successor, or `new` already
+    ///   appears in a rotation
+    /// * `TooManyRotations` - the chain would exceed `max_rotation_hops`
+    pub fn register_successor(env: Env, old: Address, new: Address) -> Result<(), RegistryError> {
+        old.require_auth();
+        
+        guards::require_distinct(&old, &new, RegistryError::SelfReferentialAddress)?;
+        
+        let storage = env.storage().persistent();
+        if storage.has(&DataKey::CommitterSuccessor(old.clone()))
+            || storage.has(&DataKey::CommitterSuccessor(new.clone()))
+            || storage.has(&DataKey::CommitterPredecessor(new.clone()))
+        {
+            return Err(RegistryError::AlreadyRotated);
+        }
+        
+        // `old` is the chain's newest key, so the chain is its keys plus `new`
+        if Self::rotation_keys(&env, &old).len() > MAX_ROTATION_HOPS {
+            return Err(RegistryError::TooManyRotations);
+        }
+        
+        storage.set(&DataKey::CommitterSuccessor(old.clone()), &new);
+        storage.set(&DataKey::CommitterPredecessor(new.clone()), &old);
+        
+        env.events().publish((symbol_short!("rotated"), old), new);
+        
+        Ok(())
+    }
+    
+    /// Follow `addr`'s rotations to the newest key in its chain.
+    ///
+    /// Returns `addr` itself if it was never rotated. Stops after
+    /// `max_rotation_hops` hops.
+    pub fn resolve_committer(env: Env, addr: Address) -> Address {
+        let storage = env.storage().persistent();
+        let mut current = addr;
+        for _ in 0..MAX_ROTATION_HOPS {
+            match storage.get(&DataKey::CommitterSuccessor(current.clone())) {
+                Some(next) => current = next,
+                None => break,
+            }
+        }
+        current
+    }
+    
+    /// Verify hashes like `verify_commit`, and also require the commitment
+    /// to come from `committer` or any key in the same rotation chain.
+    pub fn verify_commit_canonical(
+        env: Env,
+        route_hash: BytesN<32>,
+        expected_rules_hash: BytesN<32>,
+        expected_solver_hash: BytesN<32>,
+        committer: Address,
+    ) -> bool {
+        match Self::load_commitment(&env, &route_hash) {
+            Some(commitment) => {
+                commitment.rules_hash == expected_rules_hash
+                    && commitment.solver_version_hash == expected_solver_hash
+                    && Self::resolve_committer(env.clone(), commitment.committer)
+                        == Self::resolve_committer(env, committer)
+            }
+            None => false,
+        }
+    }
+    
+    /// Count commitments made by every key in `committer`'s rotation chain
+    pub fn get_canonical_commit_count(env: Env, committer: Address) -> u64 {
+        let canonical = Self::resolve_committer(env.clone(), committer);
+        Self::rotation_keys(&env, &canonical)
+            .iter()
+            .map(|key| Self::get_commit_count_by_committer(env.clone(), key))
+            .sum()
+    }
+    
+    /// List commitments made by every key in `committer`'s rotation chain.
+    ///
+    /// Keys are walked oldest first, and each key's commitments in sequence
+    /// order, so the result reads as one history.
+    ///
+    /// # Returns
+    ///
+    /// Route hashes, capped at the first `max_canonical_commits_listed`
+    pub fn get_canonical_commits(env: Env, committer: Address) -> Vec<BytesN<32>> {
+        let canonical = Self::resolve_committer(env.clone(), committer);
+        let storage = env.storage().persistent();
+        let mut hashes = Vec::new(&env);
+        for key in Self::rotation_keys(&env, &canonical).iter() {
+            let remaining = MAX_CANONICAL_COMMITS_LISTED - hashes.len();
+            if remaining == 0 {
+                break;
+            }
+            let count = Self::get_commit_count_by_committer(env.clone(), key.clone());
+            hashes.append(&Self::read_pages(&env, count, 0, remaining, |page| {
+                storage.get(&DataKey::CommitterPage(key.clone(), page))
+            }));
+        }
+        hashes
+    }
+    
+    // ─────────────────────────────────────────────────────────────────
+    // Internal helpers
+    // ─────────────────────────────────────────────────────────────────
+    
+    /// Reject all-zero route, rules or solver hashes
+    fn validate_hashes(
+        route_hash: &BytesN<32>,
+        rules_hash: &BytesN<32>,
+        solver_version_hash: &BytesN<32>,
+    ) -> Result<(), RegistryError> {
+        guards::require_nonzero_hash(route_hash, RegistryError::EmptyRouteHash)?;
+        guards::require_nonzero_hash(rules_hash, RegistryError::EmptyRulesHash)?;
+        guards::require_nonzero_hash(solver_version_hash, RegistryError::EmptySolverHash)
+    }
+    
+    /// Resolve and validate `options` as a whole, then record the commitment
+    ///
+    /// Returns the commitment's per-committer sequence number.
+    fn commit_with_options(
+        env: &Env,
+        committer: Address,
+        route_hash: BytesN<32>,
+        core: CoreHashes,
+        options: CommitOptions,
+    ) -> Result<u64, RegistryError> {
+        let expiry = match (options.expiry, options.ttl) {
+            (Expiry::At(_), TtlOption::Seconds(_)) => return Err(RegistryError::ConflictingOptions),
+            (expiry, TtlOption::Unset) => expiry,
+            (Expiry::Never, TtlOption::Seconds(ttl)) => {
+                Expiry::At(env.ledger().timestamp().saturating_add(ttl))
+            }
+        };
+        
+        let active_from = match options.schedule {
+            ScheduleOption::Immediate => None,
+            ScheduleOption::From(ts) => Some(ts),
+        };
+        
+        if let SecondaryHashOption::Keccak(keccak) = &options.secondary_hash {
+            guards::require_nonzero_hash(keccak, RegistryError::EmptySecondaryHash)?;
+            guards::require_distinct(&route_hash, keccak, RegistryError::DualHashesEqual)?;
+        }
+        
+        let committer_seq = Self::record_commitment(
+            env,
+            committer,
+            route_hash.clone(),
+            core,
+            expiry,
+            active_from,
+            env.ledger().timestamp(),
+        )?;
+        
+        if let SecondaryHashOption::Keccak(keccak) = options.secondary_hash {
+            env.storage()
+                .persistent()
+                .set(&DataKey::SecondaryHash(route_hash.clone()), &keccak);
+            env.events().publish((symbol_short!("dual"), route_hash), keccak);
+        }
+        
+        Ok(committer_seq)
+    }
+    
+    /// Validate and store a commitment credited to `committer`, then emit its event
+    ///
+    /// `timestamp` is the commitment's recorded time: the current ledger
+    /// time, or the earlier claim a backdated commitment upgrades. It is
+    /// kept only on the record and its event; expiry and activation are
+    /// validated, and the stats, day bucket and day digest keyed, on the
+    /// current ledger time.
+    fn record_commitment(
+        env: &Env,
+        committer: Address,
+        route_hash: BytesN<32>,
+        core: CoreHashes,
+        expiry: Expiry,
+        active_from: Option<u64>,
+        timestamp: u64,
+    ) -> Result<u64, RegistryError> {
+        let CoreHashes { rules_hash, solver_version_hash } = core;
+        let now = env.ledger().timestamp();
+        
+        // Validate: no hash may be empty (all zeros)
+        Self::validate_hashes(&route_hash, &rules_hash, &solver_version_hash)?;
+        
+        // Validate: commitment must not already exist
+        if Self::commitment_exists(env, &route_hash) {
+            log!(env, "Rejected: duplicate commitment for route_hash");
+            return Err(RegistryError::DuplicateCommitment);
+        }
+        
+        // Validate: an existence claim reserves the hash for its claimant
+        let claim_key = DataKey::Existence(route_hash.clone());
+        let claimed = match env.storage().persistent().get::<_, ExistenceRecord>(&claim_key) {
+            Some(claim) if claim.committer != committer => {
+                log!(env, "Rejected: route_hash is claimed by another committer");
+                return Err(RegistryError::DuplicateCommitment);
+            }
+            claim => claim.is_some(),
+        };
+        
+        // Validate: expiry (if any) must be in the future and within bounds
+        Self::validate_expiry(env, expiry, now)?;
+        
+        // Validate: activation (if scheduled) must fall inside the window
+        let active_from = active_from.unwrap_or(now);
+        Self::validate_activation(active_from, expiry, now)?;
+        
+        // Validate: every counter the commitment bumps has room. All checks
+        // end here; nothing below can fail, so a rejected commitment
+        // leaves no writes behind
+        let stats = Self::next_committer_stats(env, &committer, now)?;
+        let listed_seq = match stats.count {
+            1 => Some(Self::next_index_seq(env, &DataKey::CommitterListCount)?),
+            _ => None,
+        };
+        let commit_id = Self::total_commits(env.clone())
+            .checked_add(1)
+            .ok_or(RegistryError::CommitCountOverflow)?;
+        let day = now / SECONDS_PER_DAY;
+        let rules_key = DataKey::RulesCount(rules_hash.clone());
+        let solver_key = DataKey::SolverCount(solver_version_hash.clone());
+        let day_key = DataKey::DayCount(day);
+        let rules_seq = Self::next_index_seq(env, &rules_key)?;
+        let solver_seq = Self::next_index_seq(env, &solver_key)?;
+        let day_seq = Self::next_index_seq(env, &day_key)?;
+        
+        // Count the commitment against its committer; the new count is its sequence number
+        let committer_seq = stats.count;
+        env.storage()
+            .persistent()
+            .set(&DataKey::CommitterStats(committer.clone()), &stats);
+        if let Some(seq) = listed_seq {
+            // First commitment: list the committer once
+            Self::append_index(env, DataKey::CommitterListCount, seq, &committer, DataKey::CommitterListPage);
+        }
+        Self::append_to_page(env, committer_seq, &route_hash, |page| DataKey::CommitterPage(committer.clone(), page));
+        
+        // The registry-wide count doubles as the commitment's id
+        env.storage().instance().set(&DataKey::TotalCommits, &commit_id);
+        Self::append_to_page(env, commit_id, &route_hash, DataKey::CommitIdPage);
+        
+        // Index the commitment under its rules and solver version hashes
+        Self::append_index(env, rules_key, rules_seq, &route_hash, |page| DataKey::RulesPage(rules_hash.clone(), page));
+        Self::append_index(env, solver_key, solver_seq, &route_hash, |page| {
+            DataKey::SolverPage(solver_version_hash.clone(), page)
+        });
+        
+        // Bucket the commitment under the day it was stored
+        Self::append_index(env, day_key, day_seq, &route_hash, |page| DataKey::DayPage(day, page));
+        
+        // Claim the next position within this ledger
+        let intra_ledger_index = Self::append_ledger_commit(env, &route_hash);
+        
+        // Create commitment struct
+        let commitment = RouteCommitment {
+            rules_hash: rules_hash.clone(),
+            solver_version_hash: solver_version_hash.clone(),
+            committer: committer.clone(),
+            timestamp,
+            expiry: expiry.to_raw(),
+            active_from,
+            intra_ledger_index,
+            committer_seq,
+            commit_id,
+        };
+        
+        // Store commitment (persistent storage for long-term retention)
+        let slots = IndexSlots { rules_seq, solver_seq, day, day_seq };
+        Self::store_commitment(env, &route_hash, &commitment, slots);
+        
+        // The full record replaces the claimant's own existence claim
+        if claimed {
+            env.storage().persistent().remove(&claim_key);
+        }
+        
+        // Fold the route into the committer's rolling digest for today
+        Self::update_day_digest(env, &committer, now, &route_hash);
+        
+        // Emit RouteCommitted event for indexers and auditors
+        env.events().publish(
+            (symbol_short!("commit"), route_hash.clone()),
+            (
+                rules_hash,
+                solver_version_hash,
+                committer,
+                timestamp,
+                expiry.to_raw(),
+                active_from,
+                intra_ledger_index,
+                committer_seq,
+                commit_id,
+            ),
+        );
+        
+        log!(env, "RouteCommitted: hash={:?}, timestamp={}", route_hash, timestamp);
+        
+        Ok(committer_seq)
+    }
+    
+    /// Keys rotated into `newest`, oldest first, ending with `newest` itself
+    fn rotation_keys(env: &Env, newest: &Address) -> Vec<Address> {
+        let storage = env.storage().persistent();
+        let mut keys = Vec::from_array(env, [newest.clone()]);
+        for _ in 0..MAX_ROTATION_HOPS {
+            let current = keys.get_unchecked(0);
+            match storage.get(&DataKey::CommitterPredecessor(current)) {
+                Some(prev) => keys.push_front(prev),
+                None => break,
+            }
+        }
+        keys
+    }
+    
+    /// Commit for `owner` under `delegate`'s grant, counting the commit against it
+    fn commit_delegated(
+        env: &Env,
+        owner: Address,
+        delegate: Address,
+        route_hash: BytesN<32>,
+        core: CoreHashes,
+        expiry: u64,
+        scope: Option<CommitScope>,
+    ) -> Result<u64, RegistryError> {
+        let mut grant = Self::get_grant(env.clone(), owner.clone(), delegate.clone()).ok_or(RegistryError::Unauthorized)?;
+        if !Self::grant_usable(env, &grant) {
+            log!(env, "Rejected: delegation grant expired or exhausted");
+            return Err(RegistryError::Unauthorized);
+        }
+        let allows = |allowed: &Option<Symbol>, declared: Option<&Symbol>| allowed.as_ref().is_none_or(|a| Some(a) == declared);
+        if !allows(&grant.allowed_namespace, scope.as_ref().map(|s| &s.namespace)) {
+            log!(env, "Rejected: commit outside the grant's namespace");
+            return Err(RegistryError::Unauthorized);
+        }
+        if !allows(&grant.allowed_corridor, scope.as_ref().map(|s| &s.corridor)) {
+            log!(env, "Rejected: commit outside the grant's corridor");
+            return Err(RegistryError::Unauthorized);
+        }
+        
+        let options = CommitOptions { expiry: Expiry::from_raw(expiry), ..Default::default() };
+        let committer_seq = Self::commit_with_options(env, owner.clone(), route_hash.clone(), core, options)?;
+        
+        if let Some(scope) = &scope {
+            env.storage().persistent().set(&DataKey::Scope(route_hash.clone()), scope);
+        }
+        if grant.remaining_commits != UNLIMITED_COMMITS {
+            grant.remaining_commits -= 1;
+            env.storage()
+                .persistent()
+                .set(&DataKey::Delegation(owner.clone(), delegate.clone()), &grant);
+        }
+        env.events().publish(
+            (symbol_short!("delegated"), route_hash),
+            (delegate.clone(), grant.remaining_commits, scope),
+        );
+        if grant.remaining_commits == 0 {
+            env.events().publish((Symbol::new(env, "grant_exhausted"), owner), delegate);
+        }
+        
+        Ok(committer_seq)
+    }
+    
+    /// Whether a grant can still be used at the current ledger time
+    fn grant_usable(env: &Env, grant: &DelegationGrant) -> bool {
+        grant.remaining_commits > 0 && !Expiry::from_raw(grant.expires_at).is_expired_at(env.ledger().timestamp())
+    }
+    
+    /// Record one batch entry exactly as `commit_route` would
+    fn commit_entry(env: &Env, committer: &Address, entry: CommitEntry) -> Result<u64, RegistryError> {
+        let options = CommitOptions { expiry: Expiry::from_raw(entry.expiry), ..Default::default() };
+        Self::commit_with_options(
+            env,
+            committer.clone(),
+            entry.route_hash,
+            CoreHashes { rules_hash: entry.rules_hash, solver_version_hash: entry.solver_version_hash },
+            options,
+        )
+    }
+    
+    /// Store a commitment in the current record layout
+    fn store_commitment(env: &Env, route_hash: &BytesN<32>, commitment: &RouteCommitment, slots: IndexSlots) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Commit(route_hash.clone()), &StoredCommitment::V3(commitment.clone(), slots));
+    }
+    
+    /// Extend what is stored alongside a commitment: the side entries keyed
+    /// by its route hash, and the index pages and counters that list it.
+    /// Records from before an index existed are not listed in it.
+    fn extend_related_entries(
+        env: &Env,
+        route_hash: &BytesN<32>,
+        commitment: &RouteCommitment,
+        slots: Option<IndexSlots>,
+        extend_to: u32,
+    ) {
+        let storage = env.storage().persistent();
+        let extend = |key: DataKey| {
+            if storage.has(&key) {
+                storage.extend_ttl(&key, extend_to, extend_to);
+            }
+        };
+        
+        extend(DataKey::SecondaryHash(route_hash.clone()));
+        extend(DataKey::Relayer(route_hash.clone()));
+        extend(DataKey::Signer(route_hash.clone()));
+        extend(DataKey::UserBinding(route_hash.clone()));
+        extend(DataKey::Revocation(route_hash.clone()));
+        extend(DataKey::Cancellation(route_hash.clone()));
+        extend(DataKey::Successor(route_hash.clone()));
+        extend(DataKey::Predecessor(route_hash.clone()));
+        extend(DataKey::Parent(route_hash.clone()));
+        extend(DataKey::Children(route_hash.clone()));
+        extend(DataKey::Scope(route_hash.clone()));
+        extend(DataKey::Consumptions(route_hash.clone()));
+        extend(DataKey::Anomalies(AnomalySubject::Route(route_hash.clone())));
+        let endorsers: Vec<Address> = storage.get(&DataKey::Endorsers(route_hash.clone())).unwrap_or_else(|| Vec::new(env));
+        for endorser in endorsers.iter() {
+            extend(DataKey::Endorsement(route_hash.clone(), endorser));
+        }
+        extend(DataKey::Endorsers(route_hash.clone()));
+        
+        let page = |seq: u64| (seq - 1) / INDEX_PAGE_SIZE as u64;
+        if commitment.commit_id > 0 {
+            let committer = &commitment.committer;
+            extend(DataKey::CommitterStats(committer.clone()));
+            extend(DataKey::CommitterPage(committer.clone(), page(commitment.committer_seq)));
+            extend(DataKey::CommitIdPage(page(commitment.commit_id)));
+        }
+        if let Some(slots) = slots {
+            let (rules_hash, solver_hash) = (&commitment.rules_hash, &commitment.solver_version_hash);
+            extend(DataKey::RulesCount(rules_hash.clone()));
+            extend(DataKey::RulesPage(rules_hash.clone(), page(slots.rules_seq)));
+            extend(DataKey::SolverCount(solver_hash.clone()));
+            extend(DataKey::SolverPage(solver_hash.clone(), page(slots.solver_seq)));
+            extend(DataKey::DayCount(slots.day));
+            extend(DataKey::DayPage(slots.day, page(slots.day_seq)));
+        }
+    }
+    
+    /// Load a commitment in the current layout, upgrading older records and
+    /// falling back to its pre-`DataKey` location
+    fn load_commitment(env: &Env, route_hash: &BytesN<32>) -> Option<RouteCommitment> {
+        let storage = env.storage().persistent();
+        storage
+            .get::<_, StoredCommitment>(&DataKey::Commit(route_hash.clone()))
+            .map(RouteCommitment::from)
+            .or_else(|| {
+                storage
+                    .get::<_, LegacyRouteCommitment>(&LegacyCommitKey { route_hash: route_hash.clone() })
+                    .map(RouteCommitment::from)
+            })
+    }
+    
+    /// Check both the current and the pre-`DataKey` commitment locations
+    fn commitment_exists(env: &Env, route_hash: &BytesN<32>) -> bool {
+        let storage = env.storage().persistent();
+        storage.has(&DataKey::Commit(route_hash.clone()))
+            || storage.has(&LegacyCommitKey { route_hash: route_hash.clone() })
+    }
+    
+    /// Write and announce one revocation marker, after the ownership checks
+    fn revoke_entry(
+        env: &Env,
+        route_hash: BytesN<32>,
+        committer: &Address,
+        reason_hash: &BytesN<32>,
+    ) -> Result<(), RegistryError> {
+        let commitment = Self::load_commitment(env, &route_hash).ok_or(RegistryError::NotFound)?;
+        if commitment.committer != *committer {
+            log!(env, "Rejected: revocation by someone other than the committer");
+            return Err(RegistryError::Unauthorized);
+        }
+        
+        let key = DataKey::Revocation(route_hash.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(RegistryError::AlreadyRevoked);
+        }
+        
+        let timestamp = env.ledger().timestamp();
+        let record = RevocationRecord { reason_hash: reason_hash.clone(), timestamp };
+        env.storage().persistent().set(&key, &record);
+        
+        env.events().publish(
+            (symbol_short!("withdrawn"), route_hash),
+            (committer.clone(), reason_hash.clone(), timestamp),
+        );
+        
+        Ok(())
+    }
+    
+    /// Store and announce an attestation; `false` if the commitment is missing
+    fn record_attestation(env: &Env, route_hash: BytesN<32>, attester: &Address) -> bool {
+        if !Self::commitment_exists(env, &route_hash) {
+            return false;
+        }
+        let timestamp = env.ledger().timestamp();
+        env.storage()
+            .persistent()
+            .set(&DataKey::Attestation(route_hash.clone(), attester.clone()), &timestamp);
+        env.events().publish(
+            (symbol_short!("attested"), route_hash),
+            (attester.clone(), timestamp),
+        );
+        true
+    }
+    
+    /// Message signed for `commit_route_signed`
+    fn signed_commit_message(env: &Env, route_hash: &BytesN<32>, core: &CoreHashes, expiry: u64) -> Bytes {
+        let mut message = Bytes::from_array(env, SIGNED_COMMIT_DOMAIN);
+        message.append(&Bytes::from_array(env, &env.ledger().network_id().to_array()));
+        message.append(&Self::contract_id_bytes(env));
+        message.append(&Bytes::from_array(env, &route_hash.to_array()));
+        message.append(&Bytes::from_array(env, &core.rules_hash.to_array()));
+        message.append(&Bytes::from_array(env, &core.solver_version_hash.to_array()));
+        message.append(&Bytes::from_array(env, &expiry.to_be_bytes()));
+        message
+    }
+    
+    /// Message signed for `register_signer`
+    fn signer_registration_message(env: &Env, committer: &Address) -> Bytes {
+        let mut message = Bytes::from_array(env, SIGNER_REGISTRATION_DOMAIN);
+        message.append(&Bytes::from_array(env, &env.ledger().network_id().to_array()));
+        message.append(&Self::contract_id_bytes(env));
+        message.append(&committer.clone().to_xdr(env));
+        message
+    }
+    
+    /// Stellar account whose ed25519 key is `pubkey`
+    ///
+    /// Decoded from the XDR of an account `ScVal::Address`: the 4-byte
+    /// value type, 4-byte address type and 4-byte key type, then the key.
+    fn signer_account(env: &Env, pubkey: &BytesN<32>) -> Address {
+        let mut xdr = Bytes::from_array(env, &[0, 0, 0, 18, 0, 0, 0, 0, 0, 0, 0, 0]);
+        xdr.append(&Bytes::from_array(env, &pubkey.to_array()));
+        Address::from_xdr(env, &xdr).unwrap_optimized()
+    }
+    
+    /// Check an expiry against the current ledger timestamp
+    fn validate_expiry(env: &Env, expiry: Expiry, timestamp: u64) -> Result<(), RegistryError> {
+        match expiry {
+            Expiry::Never => Ok(()),
+            Expiry::At(ts) => {
+                if ts <= timestamp {
+                    log!(env, "Rejected: expiry {} is not after timestamp {}", ts, timestamp);
+                    return Err(RegistryError::ExpiredTimestamp);
+                }
+                
+                // Sanity check: expiry not too far in future
+                if ts > timestamp + MAX_EXPIRY_DURATION {
+                    log!(env, "Rejected: expiry too far in future");
+                    return Err(RegistryError::ExpiryTooFar);
+                }
+                Ok(())
+            }
+        }
+    }
+    
+    /// Record `route_hash` at the next position of the current ledger
+    ///
+    /// Both entries are temporary: the counter restarts at zero for every
+    /// new ledger sequence and the listing expires on its own.
+    fn append_ledger_commit(env: &Env, route_hash: &BytesN<32>) -> u32 {
+        let sequence = env.ledger().sequence();
+        let count_key = DataKey::LedgerCount(sequence);
+        let index: u32 = env.storage().temporary().get(&count_key).unwrap_or(0);
+        let entry_key = DataKey::LedgerPage(sequence, index / INDEX_PAGE_SIZE);
+        let mut page: Vec<BytesN<32>> = env.storage().temporary().get(&entry_key).unwrap_or_else(|| Vec::new(env));
+        page.push_back(route_hash.clone());
+        
+        env.storage().temporary().set(&count_key, &(index + 1));
+        env.storage().temporary().set(&entry_key, &page);
+        env.storage()
+            .temporary()
+            .extend_ttl(&count_key, LEDGER_ORDER_TTL_LEDGERS, LEDGER_ORDER_TTL_LEDGERS);
+        env.storage()
+            .temporary()
+            .extend_ttl(&entry_key, LEDGER_ORDER_TTL_LEDGERS, LEDGER_ORDER_TTL_LEDGERS);
+        
+        index
+    }
+    
+    /// Check `timestamp <= active_from < expiry` and the activation horizon
+    fn validate_activation(active_from: u64, expiry: Expiry, timestamp: u64) -> Result<(), RegistryError> {
+        if active_from < timestamp || expiry.is_expired_at(active_from) {
+            return Err(RegistryError::InvalidActivationWindow);
+        }
+        if active_from > timestamp + MAX_EXPIRY_DURATION {
+            return Err(RegistryError::ActivationTooFar);
+        }
+        Ok(())
+    }
+    
+    /// Status of a stored commitment now, reading its side entries from storage
+    fn current_status(env: &Env, route_hash: &BytesN<32>, commitment: &RouteCommitment) -> CommitmentStatus {
+        let revoked = Self::is_revoked(env.clone(), route_hash.clone());
+        let cancelled = Self::is_cancelled(env.clone(), route_hash.clone());
+        Self::derive_status(commitment, revoked, cancelled, env.ledger().timestamp())
+    }
+    
+    /// Status of a stored commitment at ledger time `now`
+    ///
+    /// The single source of truth for `CommitmentStatus`. `revoked` and
+    /// `cancelled` are whether the matching marker exists beside the record.
+    /// Conditions are checked in precedence order; the first that holds
+    /// decides:
+    ///
+    /// 1. `Revoked` - the committer withdrew the commitment
+    /// 2. `Cancelled` - the committer cancelled the quote before expiry
+    /// 3. `Expired` - expiry has passed
+    /// 4. `Scheduled` - `active_from` not reached yet
+    /// 5. `Active` - otherwise
+    fn derive_status(commitment: &RouteCommitment, revoked: bool, cancelled: bool, now: u64) -> CommitmentStatus {
+        if revoked {
+            CommitmentStatus::Revoked
+        } else if cancelled {
+            CommitmentStatus::Cancelled
+        } else if Expiry::from_raw(commitment.expiry).is_expired_at(now) {
+            CommitmentStatus::Expired
+        } else if now < commitment.active_from {
+            CommitmentStatus::Scheduled
+        } else {
+            CommitmentStatus::Active
+        }
+    }
+    
+    /// Raw 32-byte contract hash of this registry
+    ///
+    /// The XDR of a contract `ScVal::Address` is the 4-byte value type and
+    /// 4-byte address type followed by the hash.
+    fn contract_id_bytes(env: &Env) -> Bytes {
+        env.current_contract_address().to_xdr(env).slice(8..40)
+    }
+    
+    /// The committer's stats with one more commitment at `timestamp`, not yet stored
+    fn next_committer_stats(env: &Env, committer: &Address, timestamp: u64) -> Result<CommitterStats, RegistryError> {
+        match Self::get_committer_stats(env.clone(), committer.clone()) {
+            Some(stats) => Ok(CommitterStats {
+                count: stats.count.checked_add(1).ok_or(RegistryError::CommitCountOverflow)?,
+                first_ts: stats.first_ts.min(timestamp),
+                last_ts: stats.last_ts.max(timestamp),
+            }),
+            None => Ok(CommitterStats { count: 1, first_ts: timestamp, last_ts: timestamp }),
+        }
+    }
+    
+    /// Sequence number the next entry of the paged index counted under
+    /// `count_key` would take
+    fn next_index_seq(env: &Env, count_key: &DataKey) -> Result<u64, RegistryError> {
+        env.storage()
+            .persistent()
+            .get::<_, u64>(count_key)
+            .unwrap_or(0)
+            .checked_add(1)
+            .ok_or(RegistryError::CommitCountOverflow)
+    }
+    
+    /// Store `value` as entry `seq` of the paged index counted under
+    /// `count_key`, where `seq` comes from `next_index_seq`
+    fn append_index<T: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone>(
+        env: &Env,
+        count_key: DataKey,
+        seq: u64,
+        value: &T,
+        page_key: impl FnOnce(u64) -> DataKey,
+    ) {
+        env.storage().persistent().set(&count_key, &seq);
+        Self::append_to_page(env, seq, value, page_key);
+    }
+    
+    /// Store `value` as entry `seq` (1-based) of a paged index. Entry `seq`
+    /// lives on page `(seq - 1) / index_page_size`, so consecutive appends
+    /// share one storage entry until the page fills.
+    fn append_to_page<T: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone>(
+        env: &Env,
+        seq: u64,
+        value: &T,
+        page_key: impl FnOnce(u64) -> DataKey,
+    ) {
+        let storage = env.storage().persistent();
+        let key = page_key((seq - 1) / INDEX_PAGE_SIZE as u64);
+        let mut page: Vec<T> = storage.get(&key).unwrap_or_else(|| Vec::new(env));
+        page.push_back(value.clone());
+        storage.set(&key, &page);
+    }
+    
+    /// Look up entry `seq` (1-based) of a paged index
+    fn index_entry<T: IntoVal<Env, Val> + TryFromVal<Env, Val>>(
+        env: &Env,
+        seq: u64,
+        page_key: impl FnOnce(u64) -> DataKey,
+    ) -> Option<T> {
+        let position = seq.checked_sub(1)?;
+        let page: Vec<T> = env.storage().persistent().get(&page_key(position / INDEX_PAGE_SIZE as u64))?;
+        page.get((position % INDEX_PAGE_SIZE as u64) as u32)
+    }
+    
+    /// Read entries `offset + 1..` of a paged index holding `count`
+    /// entries, capped at `max_range_commits_listed`
+    fn page_index<T: IntoVal<Env, Val> + TryFromVal<Env, Val>>(
+        env: &Env,
+        count: u64,
+        offset: u32,
+        limit: u32,
+        page_key: impl Fn(u64) -> DataKey,
+    ) -> Vec<T> {
+        let storage = env.storage().persistent();
+        let limit = limit.min(MAX_RANGE_COMMITS_LISTED);
+        Self::read_pages(env, count, offset as u64, limit, |page| storage.get(&page_key(page)))
+    }
+    
+    /// Read up to `limit` entries `offset + 1..` of a paged index holding
+    /// `count` entries, fetching each page it touches once through `load_page`
+    fn read_pages<T: IntoVal<Env, Val> + TryFromVal<Env, Val>>(
+        env: &Env,
+        count: u64,
+        offset: u64,
+        limit: u32,
+        load_page: impl Fn(u64) -> Option<Vec<T>>,
+    ) -> Vec<T> {
+        let end = count.min(offset.saturating_add(limit as u64));
+        let page_size = INDEX_PAGE_SIZE as u64;
+        let values = (offset / page_size..)
+            .take_while(|page| page * page_size < end)
+            .flat_map(|page| load_page(page).unwrap_or_else(|| Vec::new(env)))
+            .skip((offset % page_size) as usize);
+        collections::collect_bounded(env, values, end.saturating_sub(offset) as u32)
+    }
+    
+    /// Fold `route_hash` into the committer's digest for the day of `timestamp`
+    fn update_day_digest(env: &Env, committer: &Address, timestamp: u64, route_hash: &BytesN<32>) {
+        let key = DataKey::DayDigest(committer.clone(), timestamp / SECONDS_PER_DAY);
+        let prev: BytesN<32> = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .unwrap_or_else(|| BytesN::from_array(env, &[0u8; 32]));
+        
+        let mut preimage = Bytes::from_array(env, &prev.to_array());
+        preimage.append(&Bytes::from_array(env, &route_hash.to_array()));
+        let digest: BytesN<32> = env.crypto().sha256(&preimage).into();
+        
+        env.storage().temporary().set(&key, &digest);
+        env.storage()
+            .temporary()
+            .extend_ttl(&key, DAY_DIGEST_TTL_LEDGERS, DAY_DIGEST_TTL_LEDGERS);
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// UNIT TESTS
+// ═══════════════════════════════════════════════════════════════════════
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use soroban_sdk::testutils::{Address as _, Events, Ledger, LedgerInfo, MockAuth, MockAuthInvoke};
+    use soroban_sdk::{Env, IntoVal};
+    use soroban_env_host::storage::{AccessType, Footprint};
+
+    fn setup_env() -> Env {
+        let env = Env::default();
+        // Commit entry points require the committer's auth; tests that
+        // exercise missing auth install their own `mock_auths`
+        env.mock_all_auths();
+        env.ledger().set(LedgerInfo {
+            timestamp: 1700000000, // Fixed timestamp for tests
+            protocol_version: 21,
+            sequence_number: 100,
+            network_id: [0u8; 32],
+            base_reserve: 10,
+            min_temp_entry_ttl: 100,
+            min_persistent_entry_ttl: 100,
+            max_entry_ttl: 1000000,
+        });
+        env
+    }
+
+    fn test_hash(env: &Env, seed: u8) -> BytesN<32> {
+        let mut bytes = [0u8; 32];
+        bytes[0] = seed;
+        bytes[31] = seed;
+        BytesN::from_array(env, &bytes)
+    }
+
+    fn zero_hash(env: &Env) -> BytesN<32> {
+        BytesN::from_array(env, &[0u8; 32])
+    }
+
+    #[test]
+    fn test_successful_commit() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        let route_hash = test_hash(&env, 1);
+        let rules_hash = test_hash(&env, 2);
+        let solver_hash = test_hash(&env, 3);
+        let expiry = 1700001000u64; // 1000 seconds in future
+
+        // Commit should succeed
+        let result = client.try_commit_route(&committer, &route_hash, &rules_hash, &solver_hash, &expiry);
+        assert!(result.is_ok());
+
+        // Verify commitment stored correctly
+        let commit = client.get_commit(&route_hash);
+        assert_eq!(commit.rules_hash, rules_hash);
+        assert_eq!(commit.solver_version_hash, solver_hash);
+        assert_eq!(commit.expiry, expiry);
+        assert_eq!(commit.timestamp, 1700000000);
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_commit_no_expiry() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        let route_hash = test_hash(&env, 10);
+        let rules_hash = test_hash(&env, 20);
+        let solver_hash = test_hash(&env, 30);
+        let expiry = 0u64; // No expiry
+
+        let result = client.try_commit_route(&committer, &route_hash, &rules_hash, &solver_hash, &expiry);
+        assert!(result.is_ok());
+
+        let commit = client.get_commit(&route_hash);
+        assert_eq!(commit.expiry, 0);
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_reject_duplicate_route_hash() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        let route_hash = test_hash(&env, 5);
+        let rules_hash = test_hash(&env, 6);
+        let solver_hash = test_hash(&env, 7);
+        let expiry = 1700001000u64;
+
+        // First commit succeeds
+        assert!(client.try_commit_route(&committer, &route_hash, &rules_hash, &solver_hash, &expiry).is_ok());
+
+        // Second commit with same route_hash fails
+        let result = client.try_commit_route(&committer, &route_hash, &rules_hash, &solver_hash, &expiry);
+        assert_eq!(result, Err(Ok(RegistryError::DuplicateCommitment)));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_reject_empty_route_hash() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        let route_hash = zero_hash(&env);
+        let rules_hash = test_hash(&env, 2);
+        let solver_hash = test_hash(&env, 3);
+        let expiry = 1700001000u64;
+
+        let result = client.try_commit_route(&committer, &route_hash, &rules_hash, &solver_hash, &expiry);
+        assert_eq!(result, Err(Ok(RegistryError::EmptyRouteHash)));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_reject_expired_timestamp() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        let route_hash = test_hash(&env, 8);
+        let rules_hash = test_hash(&env, 9);
+        let solver_hash = test_hash(&env, 10);
+        let expiry = 1699999999u64; // In the past
+
+        let result = client.try_commit_route(&committer, &route_hash, &rules_hash, &solver_hash, &expiry);
+        assert_eq!(result, Err(Ok(RegistryError::ExpiredTimestamp)));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_reject_expiry_too_far() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        let route_hash = test_hash(&env, 11);
+        let rules_hash = test_hash(&env, 12);
+        let solver_hash = test_hash(&env, 13);
+        // More than 10 years in future
+        let expiry = 1700000000u64 + MAX_EXPIRY_DURATION + 1;
+
+        let result = client.try_commit_route(&committer, &route_hash, &rules_hash, &solver_hash, &expiry);
+        assert_eq!(result, Err(Ok(RegistryError::ExpiryTooFar)));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_get_nonexistent_commit() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+
+        let route_hash = test_hash(&env, 99);
+        let result = client.try_get_commit(&route_hash);
+        assert_eq!(result, Err(Ok(RegistryError::NotFound)));
+    }
+
+    #[test]
+    fn test_has_commit() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        let route_hash = test_hash(&env, 15);
+        let rules_hash = test_hash(&env, 16);
+        let solver_hash = test_hash(&env, 17);
+
+        // Before commit
+        assert!(!client.has_commit(&route_hash));
+
+        // After commit
+        client.commit_route(&committer, &route_hash, &rules_hash, &solver_hash, &0u64);
+        assert!(client.has_commit(&route_hash));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_verify_commit() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        let route_hash = test_hash(&env, 20);
+        let rules_hash = test_hash(&env, 21);
+        let solver_hash = test_hash(&env, 22);
+
+        client.commit_route(&committer, &route_hash, &rules_hash, &solver_hash, &0u64);
+
+        // Correct hashes
+        assert!(client.verify_commit(&route_hash, &rules_hash, &solver_hash));
+
+        // Wrong rules hash
+        assert!(!client.verify_commit(&route_hash, &test_hash(&env, 99), &solver_hash));
+
+        // Wrong solver hash
+        assert!(!client.verify_commit(&route_hash, &rules_hash, &test_hash(&env, 99)));
+
+        // Nonexistent route
+        assert!(!client.verify_commit(&test_hash(&env, 99), &rules_hash, &solver_hash));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    /// Off-chain recomputation of a committer day digest
+    fn offchain_day_digest(route_hashes: &[[u8; 32]]) -> [u8; 32] {
+        use sha2::{Digest, Sha256};
+        let mut digest = [0u8; 32];
+        for route_hash in route_hashes {
+            let mut hasher = Sha256::new();
+            hasher.update(digest);
+            hasher.update(route_hash);
+            digest = hasher.finalize().into();
+        }
+        digest
+    }
+
+    #[test]
+    fn test_committer_day_digest_matches_offchain() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        let day = 1700000000u64 / SECONDS_PER_DAY;
+        assert_eq!(client.get_committer_day_digest(&committer, &day), None);
+
+        let routes = [test_hash(&env, 40), test_hash(&env, 41), test_hash(&env, 42)];
+        for route_hash in routes.iter() {
+            client.commit_route(&committer, route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        }
+
+        let expected = offchain_day_digest(&[
+            routes[0].to_array(),
+            routes[1].to_array(),
+            routes[2].to_array(),
+        ]);
+        let digest = client.get_committer_day_digest(&committer, &day).unwrap();
+        assert_eq!(digest.to_array(), expected);
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_committer_day_digest_isolated_per_committer() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        client.commit_route(&committer, &test_hash(&env, 43), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+
+        let day = 1700000000u64 / SECONDS_PER_DAY;
+        let other = Address::generate(&env);
+        assert!(client.get_committer_day_digest(&committer, &day).is_some());
+        assert_eq!(client.get_committer_day_digest(&other, &day), None);
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_committer_day_digest_day_rollover() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        let day = 1700000000u64 / SECONDS_PER_DAY;
+        let first = test_hash(&env, 44);
+        let second = test_hash(&env, 45);
+
+        client.commit_route(&committer, &first, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+
+        // Jump to the first second of the next day
+        env.ledger().with_mut(|li| li.timestamp = (day + 1) * SECONDS_PER_DAY);
+        client.commit_route(&committer, &second, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+
+        let day_one = client.get_committer_day_digest(&committer, &day).unwrap();
+        let day_two = client.get_committer_day_digest(&committer, &(day + 1)).unwrap();
+        assert_eq!(day_one.to_array(), offchain_day_digest(&[first.to_array()]));
+        assert_eq!(day_two.to_array(), offchain_day_digest(&[second.to_array()]));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_expiry_raw_round_trip() {
+        assert_eq!(Expiry::from_raw(0), Expiry::Never);
+        assert_eq!(Expiry::from_raw(1700001000), Expiry::At(1700001000));
+        assert_eq!(Expiry::Never.to_raw(), 0);
+        assert_eq!(Expiry::At(1700001000).to_raw(), 1700001000);
+
+        for raw in [0u64, 1, 1700001000, u64::MAX] {
+            assert_eq!(Expiry::from_raw(raw).to_raw(), raw);
+        }
+    }
+
+    #[test]
+    fn test_commit_route_expiring_variants() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        let rules_hash = test_hash(&env, 2);
+        let solver_hash = test_hash(&env, 3);
+
+        client.commit_route_expiring(&committer, &test_hash(&env, 50), &rules_hash, &solver_hash, &Expiry::Never);
+        assert_eq!(client.get_commit(&test_hash(&env, 50)).expiry, 0);
+
+        client.commit_route_expiring(&committer, 
+            &test_hash(&env, 51),
+            &rules_hash,
+            &solver_hash,
+            &Expiry::At(1700001000),
+        );
+        assert_eq!(client.get_commit(&test_hash(&env, 51)).expiry, 1700001000);
+
+        // Boundary: expiring exactly now is already expired
+        let result = client.try_commit_route_expiring(&committer, 
+            &test_hash(&env, 52),
+            &rules_hash,
+            &solver_hash,
+            &Expiry::At(1700000000),
+        );
+        assert_eq!(result, Err(Ok(RegistryError::ExpiredTimestamp)));
+
+        let result = client.try_commit_route_expiring(&committer, 
+            &test_hash(&env, 53),
+            &rules_hash,
+            &solver_hash,
+            &Expiry::At(1700000000 + MAX_EXPIRY_DURATION + 1),
+        );
+        assert_eq!(result, Err(Ok(RegistryError::ExpiryTooFar)));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    /// A record with its sequence number and id cleared, for comparing two
+    /// commits by the same committer
+    fn without_seq(commitment: RouteCommitment) -> RouteCommitment {
+        RouteCommitment { committer_seq: 0, commit_id: 0, ..commitment }
+    }
+
+    #[test]
+    fn test_legacy_and_expiring_commits_store_identical_records() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        let rules_hash = test_hash(&env, 2);
+        let solver_hash = test_hash(&env, 3);
+
+        for (raw, seed) in [(0u64, 54u8), (1700001000u64, 56u8)] {
+            let legacy = test_hash(&env, seed);
+            let explicit = test_hash(&env, seed + 1);
+            // Separate ledgers (same timestamp) so both take intra-ledger index 0
+            env.ledger().with_mut(|li| li.sequence_number += 1);
+            client.commit_route(&committer, &legacy, &rules_hash, &solver_hash, &raw);
+            env.ledger().with_mut(|li| li.sequence_number += 1);
+            client.commit_route_expiring(&committer, &explicit, &rules_hash, &solver_hash, &Expiry::from_raw(raw));
+            assert_eq!(without_seq(client.get_commit(&legacy)), without_seq(client.get_commit(&explicit)));
+        }
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_sandbox_commit_readable_then_evaporates() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        let route_hash = test_hash(&env, 60);
+        client.commit_route_sandbox(&committer, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+
+        assert!(client.has_sandbox_commit(&route_hash));
+        assert_eq!(client.get_sandbox_commit(&route_hash).rules_hash, test_hash(&env, 2));
+
+        // Duplicates are rejected while the entry is live
+        let result = client.try_commit_route_sandbox(&committer, 
+            &route_hash,
+            &test_hash(&env, 2),
+            &test_hash(&env, 3),
+            &0u64,
+        );
+        assert_eq!(result, Err(Ok(RegistryError::DuplicateCommitment)));
+
+        // Keep the contract instance alive while the sandbox entry lapses
+        env.as_contract(&contract_id, || {
+            env.storage().instance().extend_ttl(10_000, 10_000);
+        });
+        env.ledger().with_mut(|li| li.sequence_number += SANDBOX_TTL_LEDGERS + 1);
+
+        assert!(!client.has_sandbox_commit(&route_hash));
+        assert_eq!(client.try_get_sandbox_commit(&route_hash), Err(Ok(RegistryError::NotFound)));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_sandbox_commit_leaves_registry_untouched() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        let route_hash = test_hash(&env, 61);
+        client.commit_route_sandbox(&committer, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+
+        let events = env.events().all();
+        assert_eq!(events.len(), 1);
+        let (_, topics, _) = events.get(0).unwrap();
+        let topic: soroban_sdk::Symbol = topics.get(0).unwrap().into_val(&env);
+        assert_eq!(topic, symbol_short!("sandbox"));
+
+        let day = 1700000000u64 / SECONDS_PER_DAY;
+        assert!(!client.has_commit(&route_hash));
+        assert_eq!(client.try_get_commit(&route_hash), Err(Ok(RegistryError::NotFound)));
+        assert_eq!(client.get_committer_day_digest(&committer, &day), None);
+
+        // The same hash can still be committed for real
+        client.commit_route(&committer, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        assert!(client.has_commit(&route_hash));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_report_anomaly_for_route_and_committer() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+
+        let watchtower = Address::generate(&env);
+        let route_subject = AnomalySubject::Route(test_hash(&env, 70));
+        let committer_subject = AnomalySubject::Committer(Address::generate(&env));
+
+        assert_eq!(client.get_anomalies(&route_subject).len(), 0);
+
+        client.report_anomaly(&watchtower, &route_subject, &test_hash(&env, 71));
+        client.report_anomaly(&watchtower, &committer_subject, &test_hash(&env, 72));
+
+        let route_reports = client.get_anomalies(&route_subject);
+        assert_eq!(route_reports.len(), 1);
+        assert_eq!(
+            route_reports.get(0).unwrap(),
+            AnomalyReport {
+                reporter: watchtower.clone(),
+                evidence_hash: test_hash(&env, 71),
+                timestamp: 1700000000,
+            }
+        );
+
+        let committer_reports = client.get_anomalies(&committer_subject);
+        assert_eq!(committer_reports.len(), 1);
+        assert_eq!(committer_reports.get(0).unwrap().evidence_hash, test_hash(&env, 72));
+    }
+
+    #[test]
+    fn test_report_anomaly_dedups_per_reporter() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+
+        let first = Address::generate(&env);
+        let second = Address::generate(&env);
+        let subject = AnomalySubject::Route(test_hash(&env, 73));
+
+        client.report_anomaly(&first, &subject, &test_hash(&env, 74));
+        let result = client.try_report_anomaly(&first, &subject, &test_hash(&env, 75));
+        assert_eq!(result, Err(Ok(RegistryError::AnomalyAlreadyReported)));
+
+        // A different watchtower may still report the same subject
+        client.report_anomaly(&second, &subject, &test_hash(&env, 75));
+        let reports = client.get_anomalies(&subject);
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports.get(0).unwrap().reporter, first);
+        assert_eq!(reports.get(1).unwrap().reporter, second);
+    }
+
+    #[test]
+    fn test_report_anomaly_bounded_per_subject() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+
+        let subject = AnomalySubject::Committer(Address::generate(&env));
+        for _ in 0..MAX_ANOMALIES_PER_SUBJECT {
+            client.report_anomaly(&Address::generate(&env), &subject, &test_hash(&env, 76));
+        }
+
+        let result = client.try_report_anomaly(&Address::generate(&env), &subject, &test_hash(&env, 76));
+        assert_eq!(result, Err(Ok(RegistryError::TooManyAnomalies)));
+        assert_eq!(client.get_anomalies(&subject).len(), MAX_ANOMALIES_PER_SUBJECT);
+    }
+
+    #[test]
+    fn test_report_anomaly_requires_reporter_auth() {
+        let env = setup_env();
+        env.set_auths(&[]);
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+
+        let subject = AnomalySubject::Route(test_hash(&env, 77));
+        let result = client.try_report_anomaly(&Address::generate(&env), &subject, &test_hash(&env, 78));
+        assert!(result.is_err());
+        assert_eq!(client.get_anomalies(&subject).len(), 0);
+    }
+
+    #[test]
+    fn test_get_commit_at_stamps_ledger() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        let route_hash = test_hash(&env, 80);
+        client.commit_route(&committer, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+
+        let read = client.get_commit_at(&route_hash);
+        assert_eq!(read.value, client.get_commit(&route_hash));
+        assert_eq!(read.at_ledger, 100);
+        assert_eq!(read.at_timestamp, 1700000000);
+
+        env.ledger().with_mut(|li| {
+            li.sequence_number = 105;
+            li.timestamp = 1700000030;
+        });
+        let later = client.get_commit_at(&route_hash);
+        assert_eq!(later.value, read.value);
+        assert_eq!(later.at_ledger, 105);
+        assert_eq!(later.at_timestamp, 1700000030);
+
+        assert_eq!(
+            client.try_get_commit_at(&test_hash(&env, 81)),
+            Err(Ok(RegistryError::NotFound))
+        );
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_degenerate_parameters_rejected_everywhere() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        let zero = zero_hash(&env);
+        let good = test_hash(&env, 90);
+        let watchtower = Address::generate(&env);
+
+        fn code<T, C, E>(r: Result<Result<T, C>, Result<RegistryError, E>>) -> Option<RegistryError> {
+            match r {
+                Err(Ok(err)) => Some(err),
+                _ => None,
+            }
+        }
+
+        // (parameter, observed rejection, expected rejection)
+        let cases = [
+            (
+                "commit_route.route_hash",
+                code(client.try_commit_route(&committer, &zero, &good, &good, &0)),
+                RegistryError::EmptyRouteHash,
+            ),
+            (
+                "commit_route.rules_hash",
+                code(client.try_commit_route(&committer, &good, &zero, &good, &0)),
+                RegistryError::EmptyRulesHash,
+            ),
+            (
+                "commit_route.solver_version_hash",
+                code(client.try_commit_route(&committer, &good, &good, &zero, &0)),
+                RegistryError::EmptySolverHash,
+            ),
+            (
+                "commit_route_expiring.route_hash",
+                code(client.try_commit_route_expiring(&committer, &zero, &good, &good, &Expiry::Never)),
+                RegistryError::EmptyRouteHash,
+            ),
+            (
+                "commit_route_expiring.rules_hash",
+                code(client.try_commit_route_expiring(&committer, &good, &zero, &good, &Expiry::Never)),
+                RegistryError::EmptyRulesHash,
+            ),
+            (
+                "commit_route_expiring.solver_version_hash",
+                code(client.try_commit_route_expiring(&committer, &good, &good, &zero, &Expiry::Never)),
+                RegistryError::EmptySolverHash,
+            ),
+            (
+                "commit_route_sandbox.route_hash",
+                code(client.try_commit_route_sandbox(&committer, &zero, &good, &good, &0)),
+                RegistryError::EmptyRouteHash,
+            ),
+            (
+                "commit_route_sandbox.rules_hash",
+                code(client.try_commit_route_sandbox(&committer, &good, &zero, &good, &0)),
+                RegistryError::EmptyRulesHash,
+            ),
+            (
+                "commit_route_sandbox.solver_version_hash",
+                code(client.try_commit_route_sandbox(&committer, &good, &good, &zero, &0)),
+                RegistryError::EmptySolverHash,
+            ),
+            (
+                "report_anomaly.evidence_hash",
+                code(client.try_report_anomaly(&watchtower, &AnomalySubject::Route(good.clone()), &zero)),
+                RegistryError::EmptyEvidenceHash,
+            ),
+            (
+                "report_anomaly.subject(route)",
+                code(client.try_report_anomaly(&watchtower, &AnomalySubject::Route(zero.clone()), &good)),
+                RegistryError::EmptyRouteHash,
+            ),
+            (
+                "report_anomaly.subject(committer)",
+                code(client.try_report_anomaly(
+                    &watchtower,
+                    &AnomalySubject::Committer(watchtower.clone()),
+                    &good,
+                )),
+                RegistryError::SelfReferentialAddress,
+            ),
+            (
+                "report_anomaly.reporter",
+                code(client.try_report_anomaly(&contract_id, &AnomalySubject::Route(good.clone()), &good)),
+                RegistryError::SelfReferentialAddress,
+            ),
+        ];
+
+        for (name, observed, expected) in cases.iter() {
+            assert_eq!(*observed, Some(*expected), "{}", name);
+        }
+
+        // Nothing degenerate was stored anywhere
+        env.as_contract(&contract_id, || {
+            use soroban_sdk::testutils::storage::{Persistent as _, Temporary as _};
+            assert_eq!(env.storage().persistent().all().len(), 0);
+            assert_eq!(env.storage().temporary().all().len(), 0);
+        });
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_get_limits_exposes_every_limit() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+
+        let map = client.get_limits();
+        assert_eq!(map.len() as usize, limits::ALL.len());
+        for (name, value) in limits::ALL {
+            assert_eq!(map.get(Symbol::new(&env, name)), Some(*value), "{}", name);
+        }
+
+        // Spot-check that the exposed values are the enforced ones
+        assert_eq!(
+            map.get(Symbol::new(&env, "max_expiry_duration")),
+            Some(MAX_EXPIRY_DURATION)
+        );
+        assert_eq!(
+            map.get(Symbol::new(&env, "max_anomalies_per_subject")),
+            Some(MAX_ANOMALIES_PER_SUBJECT as u64)
+        );
+    }
+
+    #[test]
+    fn test_intra_ledger_ordering() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        let rules_hash = test_hash(&env, 2);
+        let solver_hash = test_hash(&env, 3);
+        let first_ledger = [test_hash(&env, 100), test_hash(&env, 101), test_hash(&env, 102)];
+
+        for (i, route_hash) in first_ledger.iter().enumerate() {
+            client.commit_route(&committer, route_hash, &rules_hash, &solver_hash, &0u64);
+            assert_eq!(client.get_commit(route_hash).intra_ledger_index, i as u32);
+        }
+
+        // Sandbox commits do not take a position
+        client.commit_route_sandbox(&committer, &test_hash(&env, 103), &rules_hash, &solver_hash, &0u64);
+
+        // The next ledger restarts at zero
+        env.ledger().with_mut(|li| li.sequence_number = 101);
+        let next = test_hash(&env, 104);
+        client.commit_route(&committer, &next, &rules_hash, &solver_hash, &0u64);
+        assert_eq!(client.get_commit(&next).intra_ledger_index, 0);
+
+        let listed = client.get_ledger_commits(&100);
+        assert_eq!(listed.len(), 3);
+        for (i, route_hash) in first_ledger.iter().enumerate() {
+            assert_eq!(listed.get(i as u32).unwrap(), route_hash.clone());
+        }
+        assert_eq!(client.get_ledger_commits(&101).len(), 1);
+        assert_eq!(client.get_ledger_commits(&99).len(), 0);
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_intra_ledger_index_in_event() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        client.commit_route(&committer, &test_hash(&env, 105), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        client.commit_route(&committer, &test_hash(&env, 106), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+
+        let (_, _, data) = env.events().all().last().unwrap();
+        let payload: (BytesN<32>, BytesN<32>, Address, u64, u64, u64, u32, u64, u64) = data.into_val(&env);
+        assert_eq!(payload.6, 1);
+        assert_eq!(payload.7, 2);
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_expiry_is_expired_at_boundary() {
+        assert!(!Expiry::Never.is_expired_at(0));
+        assert!(!Expiry::Never.is_expired_at(u64::MAX));
+        assert!(!Expiry::At(1700001000).is_expired_at(1700000999));
+        assert!(Expiry::At(1700001000).is_expired_at(1700001000));
+        assert!(Expiry::At(1700001000).is_expired_at(1700001001));
+    }
+
+    #[test]
+    fn test_scheduled_commit_status_transitions() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        let route_hash = test_hash(&env, 110);
+        let rules_hash = test_hash(&env, 2);
+        let solver_hash = test_hash(&env, 3);
+        let active_from = 1700000500u64;
+        let expiry = 1700001000u64;
+
+        client.commit_route_scheduled(&committer, &route_hash, &rules_hash, &solver_hash, &active_from, &expiry);
+
+        assert_eq!(
+            client.get_validity_window(&route_hash),
+            ValidityWindow { active_from, expiry: Expiry::At(expiry) }
+        );
+
+        // Before activation: scheduled, but the raw hash check still passes
+        assert_eq!(client.get_status(&route_hash), CommitmentStatus::Scheduled);
+        assert!(!client.is_active(&route_hash));
+        assert!(client.verify_commit(&route_hash, &rules_hash, &solver_hash));
+
+        env.ledger().with_mut(|li| li.timestamp = active_from - 1);
+        assert_eq!(client.get_status(&route_hash), CommitmentStatus::Scheduled);
+
+        env.ledger().with_mut(|li| li.timestamp = active_from);
+        assert_eq!(client.get_status(&route_hash), CommitmentStatus::Active);
+        assert!(client.is_active(&route_hash));
+
+        env.ledger().with_mut(|li| li.timestamp = expiry - 1);
+        assert_eq!(client.get_status(&route_hash), CommitmentStatus::Active);
+
+        env.ledger().with_mut(|li| li.timestamp = expiry);
+        assert_eq!(client.get_status(&route_hash), CommitmentStatus::Expired);
+        assert!(!client.is_active(&route_hash));
+        assert!(client.verify_commit(&route_hash, &rules_hash, &solver_hash));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_scheduled_commit_window_validation() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        let rules_hash = test_hash(&env, 2);
+        let solver_hash = test_hash(&env, 3);
+        let now = 1700000000u64;
+
+        // Activation in the past
+        let result = client.try_commit_route_scheduled(&committer, 
+            &test_hash(&env, 111),
+            &rules_hash,
+            &solver_hash,
+            &(now - 1),
+            &0,
+        );
+        assert_eq!(result, Err(Ok(RegistryError::InvalidActivationWindow)));
+
+        // Activation at or after expiry
+        let result = client.try_commit_route_scheduled(&committer, 
+            &test_hash(&env, 111),
+            &rules_hash,
+            &solver_hash,
+            &(now + 10),
+            &(now + 10),
+        );
+        assert_eq!(result, Err(Ok(RegistryError::InvalidActivationWindow)));
+
+        // Activation beyond the horizon
+        let result = client.try_commit_route_scheduled(&committer, 
+            &test_hash(&env, 111),
+            &rules_hash,
+            &solver_hash,
+            &(now + MAX_EXPIRY_DURATION + 1),
+            &0,
+        );
+        assert_eq!(result, Err(Ok(RegistryError::ActivationTooFar)));
+
+        // Lower bound is inclusive and no-expiry is accepted
+        client.commit_route_scheduled(&committer, &test_hash(&env, 112), &rules_hash, &solver_hash, &now, &0);
+        assert_eq!(client.get_status(&test_hash(&env, 112)), CommitmentStatus::Active);
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_immediate_commit_active_from_is_timestamp() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        let route_hash = test_hash(&env, 113);
+        client.commit_route(&committer, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+
+        let commit = client.get_commit(&route_hash);
+        assert_eq!(commit.active_from, commit.timestamp);
+        assert_eq!(client.get_status(&route_hash), CommitmentStatus::Active);
+        assert!(!client.is_active(&test_hash(&env, 114)));
+        assert_eq!(client.try_get_status(&test_hash(&env, 114)), Err(Ok(RegistryError::NotFound)));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    /// Decode a 64-character hex string
+    fn hex32(hex: &str) -> [u8; 32] {
+        let mut out = [0u8; 32];
+        for (i, byte) in out.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).unwrap();
+        }
+        out
+    }
+
+    /// Registry address whose raw contract hash is 32 bytes of 0x5a
+    const GOLDEN_REGISTRY: &str = "CBNFUWS2LJNFUWS2LJNFUWS2LJNFUWS2LJNFUWS2LJNFUWS2LJNFU7WI";
+
+    /// Off-chain construction of the attestation payload (layout v1)
+    fn offchain_attestation_payload(
+        network_id: [u8; 32],
+        contract_id: [u8; 32],
+        route_hash: [u8; 32],
+        rules_hash: [u8; 32],
+        solver_hash: [u8; 32],
+        timestamp: u64,
+        expiry: u64,
+    ) -> std::vec::Vec<u8> {
+        let mut out = std::vec::Vec::new();
+        out.extend_from_slice(b"stella.attest.v1");
+        out.extend_from_slice(&network_id);
+        out.extend_from_slice(&contract_id);
+        out.extend_from_slice(&route_hash);
+        out.extend_from_slice(&rules_hash);
+        out.extend_from_slice(&solver_hash);
+        out.extend_from_slice(&timestamp.to_be_bytes());
+        out.extend_from_slice(&expiry.to_be_bytes());
+        out
+    }
+
+    #[test]
+    fn test_attestation_payload_golden_vector() {
+        let env = setup_env();
+        let registry = Address::from_string(&String::from_str(&env, GOLDEN_REGISTRY));
+        let contract_id = env.register_contract(&registry, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        let route_hash = test_hash(&env, 120);
+        client.commit_route(&committer, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &1700001000u64);
+
+        let payload = client.attestation_payload(&route_hash);
+        let expected = offchain_attestation_payload(
+            [0u8; 32],
+            [0x5a; 32],
+            route_hash.to_array(),
+            test_hash(&env, 2).to_array(),
+            test_hash(&env, 3).to_array(),
+            1700000000,
+            1700001000,
+        );
+        assert_eq!(payload.len(), 192);
+        let mut on_chain = std::vec![0u8; payload.len() as usize];
+        payload.copy_into_slice(&mut on_chain);
+        assert_eq!(on_chain, expected);
+
+        // Pinned: changing the layout must bump the domain tag version
+        assert_eq!(
+            client.attestation_payload_hash(&route_hash).to_array(),
+            hex32("17e9cf3c68b1f579753b5ee82a06212b9bec3ab3e459307552f22a088e7416a0")
+        );
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_attestation_payload_tracks_network_and_registry() {
+        let env = setup_env();
+        env.ledger().with_mut(|li| li.network_id = [7u8; 32]);
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        let route_hash = test_hash(&env, 121);
+        client.commit_route(&committer, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+
+        let mut raw_id = [0u8; 32];
+        contract_id.clone().to_xdr(&env).slice(8..40).copy_into_slice(&mut raw_id);
+        let expected = offchain_attestation_payload(
+            [7u8; 32],
+            raw_id,
+            route_hash.to_array(),
+            test_hash(&env, 2).to_array(),
+            test_hash(&env, 3).to_array(),
+            1700000000,
+            0,
+        );
+
+        use sha2::{Digest, Sha256};
+        let expected_hash: [u8; 32] = Sha256::digest(&expected).into();
+        assert_eq!(client.attestation_payload_hash(&route_hash).to_array(), expected_hash);
+
+        assert_eq!(
+            client.try_attestation_payload(&test_hash(&env, 122)),
+            Err(Ok(RegistryError::NotFound))
+        );
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_get_build_info_matches_baked_constants() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+
+        // Called in the contract's context rather than through the client,
+        // so the provenance strings (which change with every commit and
+        // toolchain) stay out of the recorded test snapshot
+        let info = env.as_contract(&contract_id, || RouteIntegrityRegistry::get_build_info(env.clone()));
+        assert_eq!(info.git, String::from_str(&env, env!("STELLA_BUILD_GIT")));
+        assert_eq!(info.profile, String::from_str(&env, env!("STELLA_BUILD_PROFILE")));
+        assert_eq!(info.features, String::from_str(&env, env!("STELLA_BUILD_FEATURES")));
+        assert_eq!(info.rustc, String::from_str(&env, env!("STELLA_BUILD_RUSTC")));
+        assert!(BUILD_RUSTC.starts_with("rustc "));
+        assert!(!BUILD_GIT.is_empty());
+    }
+
+    const DUAL_MANIFEST: &[u8] = b"{\"legs\":[{\"from\":\"USDC\",\"to\":\"EURC\"}],\"v\":1}";
+
+    fn manifest_digests(env: &Env, manifest: &[u8]) -> (BytesN<32>, BytesN<32>) {
+        use sha2::{Digest as _, Sha256};
+        use sha3::Keccak256;
+        let sha: [u8; 32] = Sha256::digest(manifest).into();
+        let keccak: [u8; 32] = Keccak256::digest(manifest).into();
+        (BytesN::from_array(env, &sha), BytesN::from_array(env, &keccak))
+    }
+
+    #[test]
+    fn test_commit_route_dual_and_verify_manifest() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+
+        let committer = Address::generate(&env);
+        let (sha, keccak) = manifest_digests(&env, DUAL_MANIFEST);
+        client.commit_route_dual(&committer, &sha, &keccak, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+
+        assert_eq!(client.get_commit(&sha).committer, committer);
+        assert_eq!(client.get_secondary_hash(&sha), Some(keccak.clone()));
+
+        let manifest = Bytes::from_slice(&env, DUAL_MANIFEST);
+        assert!(client.verify_dual_manifest(&sha, &manifest));
+
+        // Single-byte mutation breaks both digests
+        let mut tampered = manifest.clone();
+        tampered.set(0, b'[');
+        assert!(!client.verify_dual_manifest(&sha, &tampered));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_verify_dual_manifest_single_mismatches() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+
+        let committer = Address::generate(&env);
+        let manifest = Bytes::from_slice(&env, DUAL_MANIFEST);
+        let (sha, keccak) = manifest_digests(&env, DUAL_MANIFEST);
+
+        // Correct SHA-256, wrong keccak on record
+        client.commit_route_dual(&committer, &sha, &test_hash(&env, 130), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        assert!(!client.verify_dual_manifest(&sha, &manifest));
+
+        // Correct keccak on record, but keyed under a different SHA-256
+        let wrong_sha = test_hash(&env, 131);
+        client.commit_route_dual(&committer, &wrong_sha, &keccak, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        assert!(!client.verify_dual_manifest(&wrong_sha, &manifest));
+
+        // Plain commitments carry no secondary digest
+        let (plain_sha, _) = manifest_digests(&env, b"plain");
+        client.commit_route(&committer, &plain_sha, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        assert_eq!(client.get_secondary_hash(&plain_sha), None);
+        assert!(!client.verify_dual_manifest(&plain_sha, &Bytes::from_slice(&env, b"plain")));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_commit_route_dual_rejects_degenerate_hashes() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+
+        let committer = Address::generate(&env);
+        let rules_hash = test_hash(&env, 2);
+        let solver_hash = test_hash(&env, 3);
+
+        let result = client.try_commit_route_dual(&committer, &test_hash(&env, 132), &zero_hash(&env), &rules_hash, &solver_hash, &0);
+        assert_eq!(result, Err(Ok(RegistryError::EmptySecondaryHash)));
+
+        let result = client.try_commit_route_dual(&committer, &zero_hash(&env), &test_hash(&env, 132), &rules_hash, &solver_hash, &0);
+        assert_eq!(result, Err(Ok(RegistryError::EmptyRouteHash)));
+
+        let result = client.try_commit_route_dual(&committer, &test_hash(&env, 132), &test_hash(&env, 132), &rules_hash, &solver_hash, &0);
+        assert_eq!(result, Err(Ok(RegistryError::DualHashesEqual)));
+
+        assert!(!client.has_commit(&test_hash(&env, 132)));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_commit_existence_is_cheaper_than_full_commit() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+
+        let committer = Address::generate(&env);
+        let route_hash = test_hash(&env, 140);
+
+        env.budget().reset_default();
+        client.commit_existence(&committer, &route_hash);
+        let existence_cost = env.budget().cpu_instruction_cost();
+
+        env.budget().reset_default();
+        client.commit_route(&committer, &test_hash(&env, 141), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        let full_cost = env.budget().cpu_instruction_cost();
+
+        assert!(existence_cost < full_cost);
+        assert_eq!(client.get_existence(&route_hash), Some((committer, 1700000000)));
+
+        // Existence records are not commitments
+        assert!(!client.has_commit(&route_hash));
+        assert!(!client.verify_commit(&route_hash, &test_hash(&env, 2), &test_hash(&env, 3)));
+        assert_eq!(
+            client.try_commit_existence(&Address::generate(&env), &route_hash),
+            Err(Ok(RegistryError::DuplicateCommitment))
+        );
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_upgrade_existence_preserves_timestamp() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+
+        let committer = Address::generate(&env);
+        let route_hash = test_hash(&env, 142);
+        client.commit_existence(&committer, &route_hash);
+
+        env.ledger().with_mut(|li| {
+            li.timestamp += 2 * SECONDS_PER_DAY;
+            li.sequence_number += 1;
+        });
+        client.upgrade_existence(&committer, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+
+        let commitment = client.get_commit(&route_hash);
+        assert_eq!(commitment.timestamp, 1700000000);
+        assert_eq!(commitment.active_from, 1700000000 + 2 * SECONDS_PER_DAY);
+        assert_eq!(commitment.committer, committer);
+
+        // The event carries the claim's timestamp, but the day bucket, day
+        // digest and stats follow the upgrade two days later
+        replay::ReplayModel::from_events(&env, &contract_id).assert_matches(&env, &client);
+        let (claim_day, upgrade_day) = (1700000000 / SECONDS_PER_DAY, 1700000000 / SECONDS_PER_DAY + 2);
+        assert_eq!(client.commits_on_day(&claim_day), 0);
+        assert_eq!(client.commits_on_day(&upgrade_day), 1);
+        assert_eq!(client.get_committer_day_digest(&committer, &claim_day), None);
+        assert!(client.get_committer_day_digest(&committer, &upgrade_day).is_some());
+        let window = client.get_commits_in_window(&(1700000000 + 2 * SECONDS_PER_DAY), &(1700000000 + 2 * SECONDS_PER_DAY), &0, &10);
+        assert_eq!(window, Vec::from_array(&env, [route_hash.clone()]));
+        let stats = client.get_committer_stats(&committer).unwrap();
+        assert_eq!((stats.first_ts, stats.last_ts), (1700000000 + 2 * SECONDS_PER_DAY, 1700000000 + 2 * SECONDS_PER_DAY));
+        assert!(client.verify_commit(&route_hash, &test_hash(&env, 2), &test_hash(&env, 3)));
+        assert_eq!(client.get_existence(&route_hash), None);
+
+        // A second upgrade finds the full commitment already in place
+        let result = client.try_upgrade_existence(&committer, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        assert_eq!(result, Err(Ok(RegistryError::DuplicateCommitment)));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_upgrade_existence_rejects_other_committer() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+
+        let committer = Address::generate(&env);
+        let route_hash = test_hash(&env, 143);
+        client.commit_existence(&committer, &route_hash);
+
+        let result = client.try_upgrade_existence(
+            &Address::generate(&env),
+            &route_hash,
+            &test_hash(&env, 2),
+            &test_hash(&env, 3),
+            &0u64,
+        );
+        assert_eq!(result, Err(Ok(RegistryError::Unauthorized)));
+
+        let result = client.try_upgrade_existence(&committer, &test_hash(&env, 144), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        assert_eq!(result, Err(Ok(RegistryError::NotFound)));
+        assert!(!client.has_commit(&route_hash));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_existence_claim_blocks_front_running_full_commit() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+
+        let claimant = Address::generate(&env);
+        let attacker = Address::generate(&env);
+        let route_hash = test_hash(&env, 145);
+        let (rules, solver) = (test_hash(&env, 2), test_hash(&env, 3));
+        client.commit_existence(&claimant, &route_hash);
+
+        // Someone who saw the claim cannot take the hash with a full commit
+        let result = client.try_commit_route(&attacker, &route_hash, &rules, &solver, &0u64);
+        assert_eq!(result, Err(Ok(RegistryError::DuplicateCommitment)));
+        let result = client.try_commit_routes(&attacker, &Vec::from_array(&env, [entry(&env, 145, 0)]));
+        assert_eq!(result, Err(Ok(RegistryError::DuplicateCommitment)));
+        assert!(!client.has_commit(&route_hash));
+        assert_eq!(client.get_existence(&route_hash).map(|(c, _)| c), Some(claimant.clone()));
+
+        // The claimant may still commit it in full, which retires the claim
+        client.commit_route(&claimant, &route_hash, &rules, &solver, &0u64);
+        assert_eq!(client.get_commit(&route_hash).committer, claimant);
+        assert_eq!(client.get_existence(&route_hash), None);
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    /// Off-chain blinded digest: sha256(route_hash || rules_hash || solver_version_hash || salt)
+    fn blinded_digest(env: &Env, route_hash: &BytesN<32>, core: &CoreHashes, salt: &BytesN<32>) -> BytesN<32> {
+        use sha2::{Digest, Sha256};
+        let mut hasher = Sha256::new();
+        hasher.update(route_hash.to_array());
+        hasher.update(core.rules_hash.to_array());
+        hasher.update(core.solver_version_hash.to_array());
+        hasher.update(salt.to_array());
+        BytesN::from_array(env, &hasher.finalize().into())
+    }
+
+    #[test]
+    fn test_reveal_backdates_to_blinded_commit() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+
+        let committer = Address::generate(&env);
+        let route_hash = test_hash(&env, 150);
+        let salt = test_hash(&env, 151);
+        let blinded_hash = blinded_digest(&env, &route_hash, &core(&env), &salt);
+        client.commit_blinded(&committer, &blinded_hash, &1700086400);
+        assert_eq!(
+            client.get_blinded(&blinded_hash),
+            Some(BlindedRecord { committer: committer.clone(), timestamp: 1700000000, expiry: 1700086400 })
+        );
+        assert!(!client.has_commit(&route_hash));
+
+        env.ledger().with_mut(|li| {
+            li.timestamp += 3_600;
+            li.sequence_number += 1;
+        });
+        client.reveal(&blinded_hash, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &salt);
+
+        let commitment = client.get_commit(&route_hash);
+        assert_eq!(commitment.timestamp, 1700000000);
+        assert_eq!(commitment.expiry, 1700086400);
+        assert_eq!(commitment.committer, committer);
+        assert_eq!(client.get_blinded(&blinded_hash), None);
+        let stats = client.get_committer_stats(&committer).unwrap();
+        assert_eq!((stats.first_ts, stats.last_ts), (1700003600, 1700003600));
+        replay::ReplayModel::from_events(&env, &contract_id).assert_matches(&env, &client);
+        let (_, topics, data) = env.events().all().last().unwrap();
+        assert_eq!(topics, (symbol_short!("revealed"), blinded_hash.clone()).into_val(&env));
+        let data: (BytesN<32>, u64) = data.into_val(&env);
+        assert_eq!(data, (route_hash.clone(), 1700000000u64));
+
+        // The blinded entry is consumed by its reveal
+        let result = client.try_reveal(&blinded_hash, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &salt);
+        assert_eq!(result, Err(Ok(RegistryError::NotFound)));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_reveal_rejects_wrong_salt_and_expired_entries() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+
+        let committer = Address::generate(&env);
+        let route_hash = test_hash(&env, 152);
+        let salt = test_hash(&env, 153);
+        let blinded_hash = blinded_digest(&env, &route_hash, &core(&env), &salt);
+        client.commit_blinded(&committer, &blinded_hash, &1700003600);
+        assert_eq!(
+            client.try_commit_blinded(&committer, &blinded_hash, &0u64),
+            Err(Ok(RegistryError::DuplicateCommitment))
+        );
+        assert_eq!(
+            client.try_commit_blinded(&committer, &zero_hash(&env), &0u64),
+            Err(Ok(RegistryError::EmptyRouteHash))
+        );
+
+        let result = client.try_reveal(&blinded_hash, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &test_hash(&env, 154));
+        assert_eq!(result, Err(Ok(RegistryError::RevealMismatch)));
+        let result = client.try_reveal(&blinded_hash, &route_hash, &test_hash(&env, 2), &test_hash(&env, 4), &salt);
+        assert_eq!(result, Err(Ok(RegistryError::RevealMismatch)));
+        assert!(!client.has_commit(&route_hash));
+
+        env.ledger().with_mut(|li| li.timestamp = 1700003600);
+        let result = client.try_reveal(&blinded_hash, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &salt);
+        assert_eq!(result, Err(Ok(RegistryError::QuoteExpired)));
+        assert!(client.get_blinded(&blinded_hash).is_some());
+        assert!(!client.has_commit(&route_hash));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_derive_status_precedence_table() {
+        let env = setup_env();
+        let now = 1700000000u64;
+        let base = RouteCommitment {
+            rules_hash: test_hash(&env, 2),
+            solver_version_hash: test_hash(&env, 3),
+            committer: Address::generate(&env),
+            timestamp: now - 100,
+            expiry: 0,
+            active_from: now - 100,
+            intra_ledger_index: 0,
+            committer_seq: 1,
+            commit_id: 1,
+        };
+
+        // (scheduled, expired, revoked) -> status; precedence Revoked > Cancelled > Expired > Scheduled > Active
+        let table = [
+            (false, false, false, CommitmentStatus::Active),
+            (true, false, false, CommitmentStatus::Scheduled),
+            (false, true, false, CommitmentStatus::Expired),
+            (true, true, false, CommitmentStatus::Expired),
+            (false, false, true, CommitmentStatus::Revoked),
+            (true, false, true, CommitmentStatus::Revoked),
+            (false, true, true, CommitmentStatus::Revoked),
+            (true, true, true, CommitmentStatus::Revoked),
+        ];
+        for (scheduled, expired, revoked, expected) in table {
+            let mut commitment = base.clone();
+            if scheduled {
+                commitment.active_from = now + 1;
+            }
+            if expired {
+                commitment.expiry = now;
+            }
+            assert_eq!(RouteIntegrityRegistry::derive_status(&commitment, revoked, false, now), expected);
+            let cancelled = if revoked { expected } else { CommitmentStatus::Cancelled };
+            assert_eq!(RouteIntegrityRegistry::derive_status(&commitment, revoked, true, now), cancelled);
+        }
+    }
+
+    #[test]
+    fn test_commit_route_credits_authorizing_committer() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+
+        let committer = Address::generate(&env);
+        let route_hash = test_hash(&env, 148);
+        client.commit_route(&committer, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+
+        let authorized: std::vec::Vec<Address> = env.auths().into_iter().map(|(addr, _)| addr).collect();
+        assert_eq!(authorized, std::vec![committer.clone()]);
+
+        let commitment = client.get_commit(&route_hash);
+        assert_eq!(commitment.committer, committer);
+        assert_ne!(commitment.committer, contract_id);
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_commit_route_requires_committer_auth() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+
+        let committer = Address::generate(&env);
+        let impostor = Address::generate(&env);
+        let route_hash = test_hash(&env, 149);
+        let args = (committer.clone(), route_hash.clone(), test_hash(&env, 2), test_hash(&env, 3), 0u64);
+
+        // Someone else signs for the committer's arguments
+        env.mock_auths(&[MockAuth {
+            address: &impostor,
+            invoke: &MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "commit_route",
+                args: args.into_val(&env),
+                sub_invokes: &[],
+            },
+        }]);
+        client.commit_route(&committer, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+    }
+
+    #[test]
+    fn test_commit_route_rejects_missing_committer_auth() {
+        let env = setup_env();
+        env.set_auths(&[]);
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+
+        let route_hash = test_hash(&env, 147);
+        let result = client.try_commit_route(&Address::generate(&env), &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        assert!(result.is_err());
+        assert!(!client.has_commit(&route_hash));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_commit_route_for_credits_named_committer() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let protocol = Address::generate(&env);
+        let route_hash = test_hash(&env, 146);
+        let args = (protocol.clone(), route_hash.clone(), test_hash(&env, 2), test_hash(&env, 3), 0u64);
+
+        // Only the named committer's pre-signed entry; the operator submits
+        env.mock_auths(&[MockAuth {
+            address: &protocol,
+            invoke: &MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "commit_route_for",
+                args: args.into_val(&env),
+                sub_invokes: &[],
+            },
+        }]);
+        assert_eq!(client.commit_route_for(&protocol, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &0u64), 1);
+        let authorized: std::vec::Vec<Address> = env.auths().into_iter().map(|(addr, _)| addr).collect();
+        assert_eq!(authorized, std::vec![protocol.clone()]);
+        assert_eq!(client.get_commit(&route_hash).committer, protocol);
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_commit_route_for_rejects_missing_or_impersonated_auth() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let protocol = Address::generate(&env);
+        let impostor = Address::generate(&env);
+        let route_hash = test_hash(&env, 145);
+        let args = (protocol.clone(), route_hash.clone(), test_hash(&env, 2), test_hash(&env, 3), 0u64);
+
+        env.set_auths(&[]);
+        let missing = client.try_commit_route_for(&protocol, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        assert!(missing.is_err());
+
+        // Someone else signs for the committer's arguments
+        env.mock_auths(&[MockAuth {
+            address: &impostor,
+            invoke: &MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "commit_route_for",
+                args: args.into_val(&env),
+                sub_invokes: &[],
+            },
+        }]);
+        let impersonated = client.try_commit_route_for(&protocol, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        assert!(impersonated.is_err());
+        assert!(!client.has_commit(&route_hash));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_commit_route_relayed_credits_committer() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+
+        let relayer = Address::generate(&env);
+        let committer = Address::generate(&env);
+        let route_hash = test_hash(&env, 150);
+        client.commit_route_relayed(&relayer, &committer, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+
+        // Both parties had to authorize
+        let authorized: std::vec::Vec<Address> = env.auths().into_iter().map(|(addr, _)| addr).collect();
+        assert!(authorized.contains(&relayer));
+        assert!(authorized.contains(&committer));
+
+        assert_eq!(client.get_commit(&route_hash).committer, committer);
+        assert_eq!(client.get_relayer(&route_hash), Some(relayer.clone()));
+        assert!(client.is_relayed(&route_hash));
+
+        let (_, topics, data) = env.events().all().last().unwrap();
+        assert_eq!(topics, (symbol_short!("relayed"), route_hash).into_val(&env));
+        let payload: (Address, Address) = data.into_val(&env);
+        assert_eq!(payload, (relayer, committer));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_commit_route_relayed_rejects_self_relay() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+
+        let committer = Address::generate(&env);
+        let route_hash = test_hash(&env, 151);
+        let result = client.try_commit_route_relayed(&committer, &committer, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        assert_eq!(result, Err(Ok(RegistryError::SelfRelay)));
+
+        client.commit_route(&committer, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        assert!(!client.is_relayed(&route_hash));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_commit_route_relayed_requires_committer_auth() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+
+        let relayer = Address::generate(&env);
+        let committer = Address::generate(&env);
+        let route_hash = test_hash(&env, 152);
+        let args = (
+            relayer.clone(),
+            committer.clone(),
+            route_hash.clone(),
+            test_hash(&env, 2),
+            test_hash(&env, 3),
+            0u64,
+        );
+
+        // Only the relayer signs
+        env.mock_auths(&[MockAuth {
+            address: &relayer,
+            invoke: &MockAuthInvoke {
+                contract: &contract_id,
+                fn_name: "commit_route_relayed",
+                args: args.into_val(&env),
+                sub_invokes: &[],
+            },
+        }]);
+        client.commit_route_relayed(&relayer, &committer, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+    }
+
+    #[test]
+    fn test_data_keys_never_collide() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+
+        let hash = test_hash(&env, 160);
+        let committer = Address::generate(&env);
+        let keys = [
+            DataKey::Commit(hash.clone()),
+            DataKey::Sandbox(hash.clone()),
+            DataKey::SecondaryHash(hash.clone()),
+            DataKey::Relayer(hash.clone()),
+            DataKey::Existence(hash.clone()),
+            DataKey::LedgerCount(7),
+            DataKey::LedgerPage(7, 0),
+            DataKey::DayDigest(committer.clone(), 7),
+            DataKey::Anomalies(AnomalySubject::Route(hash.clone())),
+            DataKey::Anomalies(AnomalySubject::Committer(committer)),
+            DataKey::Corridor(Symbol::new(&env, "USDC_PHP")),
+            DataKey::CorridorPair(hash.clone(), hash.clone()),
+        ];
+
+        env.as_contract(&contract_id, || {
+            let storage = env.storage().persistent();
+            storage.set(&LegacyCommitKey { route_hash: hash.clone() }, &u32::MAX);
+            for (tag, key) in keys.iter().enumerate() {
+                storage.set(key, &(tag as u32));
+            }
+            for (tag, key) in keys.iter().enumerate() {
+                assert_eq!(storage.get::<_, u32>(key), Some(tag as u32));
+            }
+            assert_eq!(storage.get::<_, u32>(&LegacyCommitKey { route_hash: hash.clone() }), Some(u32::MAX));
+        });
+    }
+
+    #[test]
+    fn test_legacy_commit_key_still_readable() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        let route_hash = test_hash(&env, 161);
+        let legacy = LegacyRouteCommitment {
+            rules_hash: test_hash(&env, 2),
+            solver_version_hash: test_hash(&env, 3),
+            committer: contract_id.clone(),
+            timestamp: 1699990000,
+            expiry: 0,
+        };
+
+        // Fixture: a five-field record written under the pre-DataKey key shape
+        env.as_contract(&contract_id, || {
+            env.storage()
+                .persistent()
+                .set(&LegacyCommitKey { route_hash: route_hash.clone() }, &legacy);
+        });
+
+        assert!(client.has_commit(&route_hash));
+        let upgraded = client.get_commit(&route_hash);
+        assert_eq!(upgraded, RouteCommitment::from(legacy.clone()));
+        assert_eq!(
+            (upgraded.timestamp, upgraded.active_from, upgraded.commit_id),
+            (legacy.timestamp, legacy.timestamp, 0)
+        );
+        assert!(client.verify_commit(&route_hash, &test_hash(&env, 2), &test_hash(&env, 3)));
+        assert_eq!(client.get_status(&route_hash), CommitmentStatus::Active);
+
+        let result = client.try_commit_route(&committer, &route_hash, &test_hash(&env, 4), &test_hash(&env, 5), &0u64);
+        assert_eq!(result, Err(Ok(RegistryError::DuplicateCommitment)));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_v1_commit_record_still_readable() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        let route_hash = test_hash(&env, 162);
+        let v1 = LegacyRouteCommitment {
+            rules_hash: test_hash(&env, 2),
+            solver_version_hash: test_hash(&env, 3),
+            committer: committer.clone(),
+            timestamp: 1699990000,
+            expiry: 1700050000,
+        };
+
+        // Fixture: a record stored in the layout from before activation,
+        // ordering and id fields were added
+        env.as_contract(&contract_id, || {
+            env.storage()
+                .persistent()
+                .set(&DataKey::Commit(route_hash.clone()), &StoredCommitment::V1(v1.clone()));
+        });
+
+        let upgraded = client.get_commit(&route_hash);
+        assert_eq!(upgraded, RouteCommitment::from(v1.clone()));
+        assert_eq!(upgraded.active_from, v1.timestamp);
+        assert!(client.verify_commit(&route_hash, &test_hash(&env, 2), &test_hash(&env, 3)));
+        assert!(client.verify_commit_active(&route_hash, &test_hash(&env, 2), &test_hash(&env, 3)));
+        assert_eq!(client.get_status(&route_hash), CommitmentStatus::Active);
+        assert_eq!(client.get_validity_window(&route_hash).active_from, v1.timestamp);
+
+        client.revoke_commit(&route_hash, &committer, &test_hash(&env, 4));
+        assert!(client.is_revoked(&route_hash));
+        assert!(!client.verify_commit_active(&route_hash, &test_hash(&env, 2), &test_hash(&env, 3)));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_register_and_resolve_corridor() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+
+        let registrar = Address::generate(&env);
+        let usdc = test_hash(&env, 170);
+        let php = test_hash(&env, 171);
+        let corridor = Symbol::new(&env, "USDC_PHP");
+        client.register_corridor(&registrar, &corridor, &usdc, &php);
+
+        let entry = client.resolve_corridor(&corridor).unwrap();
+        assert_eq!(entry.registrar, registrar);
+        assert_eq!(entry.source_asset_hash, usdc);
+        assert_eq!(entry.dest_asset_hash, php);
+        assert_eq!(entry.registered_at, 1700000000);
+        assert_eq!(client.find_corridor_by_pair(&usdc, &php), Some(corridor));
+
+        // Pairs are directional
+        assert_eq!(client.find_corridor_by_pair(&php, &usdc), None);
+        assert_eq!(client.resolve_corridor(&Symbol::new(&env, "USDCPHP")), None);
+    }
+
+    #[test]
+    fn test_register_corridor_first_registration_wins() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+
+        let registrar = Address::generate(&env);
+        let latecomer = Address::generate(&env);
+        let usdc = test_hash(&env, 172);
+        let php = test_hash(&env, 173);
+        let eurc = test_hash(&env, 174);
+        let corridor = Symbol::new(&env, "USDC_PHP");
+        client.register_corridor(&registrar, &corridor, &usdc, &php);
+
+        // Same symbol, different pair
+        let result = client.try_register_corridor(&latecomer, &corridor, &usdc, &eurc);
+        assert_eq!(result, Err(Ok(RegistryError::CorridorAlreadyRegistered)));
+
+        // Same pair, near-duplicate symbol
+        let result = client.try_register_corridor(&latecomer, &Symbol::new(&env, "USDCPHP"), &usdc, &php);
+        assert_eq!(result, Err(Ok(RegistryError::CorridorPairAlreadyRegistered)));
+
+        assert_eq!(client.resolve_corridor(&corridor).unwrap().registrar, registrar);
+    }
+
+    #[test]
+    fn test_register_corridor_rejects_degenerate_assets() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+
+        let registrar = Address::generate(&env);
+        let corridor = Symbol::new(&env, "USDC_PHP");
+        let usdc = test_hash(&env, 175);
+
+        let result = client.try_register_corridor(&registrar, &corridor, &zero_hash(&env), &usdc);
+        assert_eq!(result, Err(Ok(RegistryError::EmptyAssetHash)));
+        let result = client.try_register_corridor(&registrar, &corridor, &usdc, &zero_hash(&env));
+        assert_eq!(result, Err(Ok(RegistryError::EmptyAssetHash)));
+        let result = client.try_register_corridor(&registrar, &corridor, &usdc, &usdc);
+        assert_eq!(result, Err(Ok(RegistryError::IdenticalCorridorAssets)));
+        assert_eq!(client.resolve_corridor(&corridor), None);
+    }
+
+    fn expect(
+        rules_hash: Option<BytesN<32>>,
+        solver_version_hash: Option<BytesN<32>>,
+        committer: Option<Address>,
+    ) -> PartialExpectation {
+        let hash = |h: Option<BytesN<32>>| h.map_or(HashExpectation::Ignore, HashExpectation::Equals);
+        PartialExpectation {
+            rules_hash: hash(rules_hash),
+            solver_version_hash: hash(solver_version_hash),
+            committer: committer.map_or(AddressExpectation::Ignore, AddressExpectation::Equals),
+        }
+    }
+
+    #[test]
+    fn test_verify_partial_single_fields() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+
+        let committer = Address::generate(&env);
+        let route_hash = test_hash(&env, 180);
+        let rules_hash = test_hash(&env, 2);
+        let solver_hash = test_hash(&env, 3);
+        client.commit_route_relayed(&Address::generate(&env), &committer, &route_hash, &rules_hash, &solver_hash, &0u64);
+
+        let cases = [
+            (expect(Some(rules_hash.clone()), None, None), VerifyResult::Match),
+            (expect(Some(test_hash(&env, 4)), None, None), VerifyResult::RulesMismatch),
+            (expect(None, Some(solver_hash.clone()), None), VerifyResult::Match),
+            (expect(None, Some(test_hash(&env, 4)), None), VerifyResult::SolverMismatch),
+            (expect(None, None, Some(committer.clone())), VerifyResult::Match),
+            (expect(None, None, Some(Address::generate(&env))), VerifyResult::CommitterMismatch),
+        ];
+        for (expected, result) in cases {
+            assert_eq!(client.verify_partial(&route_hash, &expected), result);
+        }
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_verify_partial_combinations() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        let route_hash = test_hash(&env, 181);
+        let rules_hash = test_hash(&env, 2);
+        let solver_hash = test_hash(&env, 3);
+        client.commit_route(&committer, &route_hash, &rules_hash, &solver_hash, &0u64);
+
+        let all = expect(Some(rules_hash.clone()), Some(solver_hash.clone()), Some(committer.clone()));
+        assert_eq!(client.verify_partial(&route_hash, &all), VerifyResult::Match);
+
+        // First mismatch in field order wins
+        let both_wrong = expect(Some(test_hash(&env, 4)), Some(test_hash(&env, 5)), None);
+        assert_eq!(client.verify_partial(&route_hash, &both_wrong), VerifyResult::RulesMismatch);
+        let solver_wrong = expect(Some(rules_hash.clone()), Some(test_hash(&env, 5)), Some(contract_id));
+        assert_eq!(client.verify_partial(&route_hash, &solver_wrong), VerifyResult::SolverMismatch);
+
+        let rules_only = expect(Some(rules_hash), None, None);
+        assert_eq!(client.verify_partial(&test_hash(&env, 182), &rules_only), VerifyResult::NotFound);
+        client.commit_existence(&Address::generate(&env), &test_hash(&env, 182));
+        assert_eq!(client.verify_partial(&test_hash(&env, 182), &rules_only), VerifyResult::ExistenceOnly);
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_verify_partial_rejects_empty_expectation() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        let route_hash = test_hash(&env, 183);
+        client.commit_route(&committer, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        let result = client.try_verify_partial(&route_hash, &expect(None, None, None));
+        assert_eq!(result, Err(Ok(RegistryError::EmptyExpectation)));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_lifecycle_state_replays_from_events() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        let partner = Address::generate(&env);
+        let relayer = Address::generate(&env);
+        let watchtower = Address::generate(&env);
+        let rules_hash = test_hash(&env, 2);
+        let solver_hash = test_hash(&env, 3);
+
+        client.commit_route(&committer, &test_hash(&env, 190), &rules_hash, &solver_hash, &0u64);
+        client.commit_route_expiring(&committer, &test_hash(&env, 191), &rules_hash, &solver_hash, &Expiry::At(1700003600));
+        client.commit_route_scheduled(&committer, &test_hash(&env, 192), &rules_hash, &solver_hash, &1700000600, &0u64);
+        let (sha, keccak) = manifest_digests(&env, DUAL_MANIFEST);
+        client.commit_route_dual(&partner, &sha, &keccak, &rules_hash, &solver_hash, &0u64);
+        client.commit_route_relayed(&relayer, &partner, &test_hash(&env, 193), &rules_hash, &solver_hash, &0u64);
+        client.commit_route_sandbox(&committer, &test_hash(&env, 194), &rules_hash, &solver_hash, &0u64);
+        client.commit_existence(&partner, &test_hash(&env, 195));
+        client.commit_existence(&partner, &test_hash(&env, 196));
+        client.register_corridor(&partner, &Symbol::new(&env, "USDC_PHP"), &test_hash(&env, 197), &test_hash(&env, 198));
+        client.report_anomaly(&watchtower, &AnomalySubject::Route(test_hash(&env, 190)), &test_hash(&env, 199));
+        client.report_anomaly(&watchtower, &AnomalySubject::Committer(partner.clone()), &test_hash(&env, 200));
+
+        // Next ledger, next day: upgrades and more commits
+        env.ledger().with_mut(|li| {
+            li.timestamp += SECONDS_PER_DAY;
+            li.sequence_number += 1;
+        });
+        client.upgrade_existence(&partner, &test_hash(&env, 195), &rules_hash, &solver_hash, &0u64);
+        client.commit_route_relayed(&relayer, &partner, &test_hash(&env, 201), &rules_hash, &solver_hash, &0u64);
+        client.report_anomaly(&Address::generate(&env), &AnomalySubject::Route(test_hash(&env, 190)), &test_hash(&env, 202));
+        client.emit_commit_only(&partner, &test_hash(&env, 203), &rules_hash, &solver_hash);
+        client.mark_consumed(&partner, &test_hash(&env, 190), &test_hash(&env, 204));
+        client.mark_consumed(&relayer, &test_hash(&env, 190), &test_hash(&env, 205));
+        client.approve_delegate(&partner, &relayer);
+        client.approve_delegate(&partner, &watchtower);
+        client.commit_as(&partner, &relayer, &test_hash(&env, 206), &rules_hash, &solver_hash, &0u64);
+        client.revoke_delegate(&partner, &watchtower);
+        let (pubkey, signature) = sign_commit(&env, &contract_id, &test_hash(&env, 207), 0);
+        client.commit_route_signed(&pubkey, &signature, &test_hash(&env, 207), &rules_hash, &solver_hash, &0u64);
+        client.endorse_commit(&test_hash(&env, 190), &watchtower);
+        client.endorse_commit(&test_hash(&env, 190), &partner);
+        client.endorse_commit(&test_hash(&env, 190), &watchtower);
+        client.register_profile(&partner, &test_hash(&env, 208), &Bytes::from_slice(&env, b"ipfs://partner-v1"));
+        client.register_profile(&partner, &test_hash(&env, 209), &Bytes::from_slice(&env, b"ipfs://partner-v2"));
+        client.revoke_commit(&test_hash(&env, 193), &partner, &test_hash(&env, 210));
+        client.supersede_commit(&test_hash(&env, 201), &test_hash(&env, 211), &rules_hash, &solver_hash, &0u64, &partner);
+        client.register_successor(&partner, &relayer);
+        client.ack_route(&test_hash(&env, 190), &watchtower);
+        client.ack_route(&test_hash(&env, 190), &watchtower);
+        client.commit_route_bound(&partner, &test_hash(&env, 212), &rules_hash, &solver_hash, &0u64, &test_hash(&env, 213));
+        client.commit_route_bound(&partner, &test_hash(&env, 214), &rules_hash, &solver_hash, &0u64, &zero_hash(&env));
+        client.commit_routes(&committer, &Vec::from_array(&env, [entry(&env, 215, 0), entry(&env, 216, 1700090000)]));
+        client.commit_routes_partial(&committer, &Vec::from_array(&env, [entry(&env, 217, 0), entry(&env, 215, 0)]));
+        client.commit_batch_root(&partner, &test_hash(&env, 218), &rules_hash, &solver_hash, &1700090000, &8);
+        let salt = test_hash(&env, 219);
+        let revealed = blinded_digest(&env, &test_hash(&env, 220), &core(&env), &salt);
+        client.commit_blinded(&partner, &revealed, &0u64);
+        client.commit_blinded(&partner, &test_hash(&env, 221), &1700090000);
+        client.reveal(&revealed, &test_hash(&env, 220), &test_hash(&env, 2), &test_hash(&env, 3), &salt);
+        client.attest(&test_hash(&env, 215), &relayer);
+        client.attest_batch(&relayer, &Vec::from_array(&env, [test_hash(&env, 215), test_hash(&env, 216), test_hash(&env, 222)]));
+        client.revoke_commits(&committer, &Vec::from_array(&env, [test_hash(&env, 216), test_hash(&env, 201)]), &test_hash(&env, 223));
+
+        replay::ReplayModel::from_events(&env, &contract_id).assert_matches(&env, &client);
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_collection_helpers_enforce_bounds() {
+        let env = setup_env();
+
+        let mut vec: Vec<u32> = Vec::new(&env);
+        for item in 0..3 {
+            assert_eq!(collections::bounded_push(&mut vec, item, 3, RegistryError::TooManyAnomalies), Ok(()));
+        }
+        assert_eq!(
+            collections::bounded_push(&mut vec, 3, 3, RegistryError::TooManyAnomalies),
+            Err(RegistryError::TooManyAnomalies)
+        );
+        assert_eq!(vec.len(), 3);
+
+        // Items past the bound are never pulled from the source
+        let mut pulled = 0u32;
+        let source = (0..100u32).inspect(|_| pulled += 1);
+        let collected = collections::collect_bounded(&env, source, 5);
+        assert_eq!(collected.len(), 5);
+        assert_eq!(collected.get(4), Some(4));
+        assert_eq!(pulled, 5);
+    }
+
+    #[test]
+    fn test_listings_never_exceed_documented_maximum() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+
+        let sequence = env.ledger().sequence();
+        let subject = AnomalySubject::Route(test_hash(&env, 210));
+        let committer = Address::generate(&env);
+
+        // Store more than any write path would allow
+        env.as_contract(&contract_id, || {
+            let oversized = MAX_LEDGER_COMMITS_LISTED + 50;
+            env.storage().temporary().set(&DataKey::LedgerCount(sequence), &oversized);
+            let mut page = Vec::new(&env);
+            for _ in 0..INDEX_PAGE_SIZE {
+                page.push_back(test_hash(&env, 211));
+            }
+            for page_no in 0..oversized.div_ceil(INDEX_PAGE_SIZE) {
+                env.storage().temporary().set(&DataKey::LedgerPage(sequence, page_no), &page);
+            }
+
+            let mut reports = Vec::new(&env);
+            for _ in 0..MAX_ANOMALIES_PER_SUBJECT + 5 {
+                reports.push_back(AnomalyReport {
+                    reporter: Address::generate(&env),
+                    evidence_hash: test_hash(&env, 212),
+                    timestamp: 1700000000,
+                });
+            }
+            env.storage().persistent().set(&DataKey::Anomalies(subject.clone()), &reports);
+
+            let mut records = Vec::new(&env);
+            for _ in 0..MAX_CONSUMPTIONS_PER_ROUTE + 5 {
+                records.push_back(ConsumptionRecord {
+                    consumer: Address::generate(&env),
+                    consumption_ref: test_hash(&env, 213),
+                    at: 1700000000,
+                });
+            }
+            env.storage().persistent().set(&DataKey::Consumptions(test_hash(&env, 210)), &records);
+
+            let oversized = (MAX_CANONICAL_COMMITS_LISTED + 5) as u64;
+            let stats = CommitterStats { count: oversized, first_ts: 1700000000, last_ts: 1700000000 };
+            env.storage().persistent().set(&DataKey::CommitterStats(committer.clone()), &stats);
+            let mut page = Vec::new(&env);
+            for _ in 0..INDEX_PAGE_SIZE {
+                page.push_back(test_hash(&env, 214));
+            }
+            for page_no in 0..oversized.div_ceil(INDEX_PAGE_SIZE as u64) {
+                env.storage().persistent().set(&DataKey::CommitterPage(committer.clone(), page_no), &page);
+            }
+        });
+
+        assert_eq!(client.get_ledger_commits(&sequence).len(), MAX_LEDGER_COMMITS_LISTED);
+        assert_eq!(client.get_anomalies(&subject).len(), MAX_ANOMALIES_PER_SUBJECT);
+        assert_eq!(client.get_consumptions(&test_hash(&env, 210)).len(), MAX_CONSUMPTIONS_PER_ROUTE);
+        // The seeding above is not part of the call; the listing alone must fit one budget
+        env.budget().reset_default();
+        assert_eq!(client.get_canonical_commits(&committer).len(), MAX_CANONICAL_COMMITS_LISTED);
+    }
+
+    #[test]
+    fn test_emit_commit_only_writes_no_route_entries() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+
+        let committer = Address::generate(&env);
+        let route_hash = test_hash(&env, 220);
+        client.emit_commit_only(&committer, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3));
+
+        let (_, topics, data) = env.events().all().last().unwrap();
+        assert_eq!(topics, (symbol_short!("commit_eo"), route_hash.clone()).into_val(&env));
+        let payload: (BytesN<32>, BytesN<32>, Address, u64, u64) = data.into_val(&env);
+        assert_eq!(payload, (test_hash(&env, 2), test_hash(&env, 3), committer.clone(), 1700000000, 1));
+
+        env.as_contract(&contract_id, || {
+            use soroban_sdk::testutils::storage::{Persistent as _, Temporary as _};
+            assert_eq!(env.storage().persistent().all().len(), 0);
+            assert_eq!(env.storage().temporary().all().len(), 0);
+        });
+        assert!(!client.has_commit(&route_hash));
+        assert_eq!(client.get_event_only_count(), 1);
+
+        // No duplicate check: the same hash can be emitted again
+        client.emit_commit_only(&committer, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3));
+        assert_eq!(client.get_event_only_count(), 2);
+
+        let result = client.try_emit_commit_only(&committer, &route_hash, &zero_hash(&env), &test_hash(&env, 3));
+        assert_eq!(result, Err(Ok(RegistryError::EmptyRulesHash)));
+    }
+
+    #[test]
+    fn test_emit_commit_only_then_full_commit() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        let route_hash = test_hash(&env, 221);
+        client.emit_commit_only(&Address::generate(&env), &route_hash, &test_hash(&env, 2), &test_hash(&env, 3));
+        client.commit_route(&committer, &route_hash, &test_hash(&env, 4), &test_hash(&env, 5), &0u64);
+
+        assert_eq!(client.get_commit(&route_hash).rules_hash, test_hash(&env, 4));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_emit_commit_only_counter_is_checked() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        env.as_contract(&contract_id, || env.storage().instance().set(&DataKey::EventOnlyCount, &u64::MAX));
+        let result = client.try_emit_commit_only(&committer, &test_hash(&env, 222), &test_hash(&env, 2), &test_hash(&env, 3));
+        assert_eq!(result, Err(Ok(RegistryError::CommitCountOverflow)));
+        assert_eq!(client.get_event_only_count(), u64::MAX);
+    }
+
+    #[test]
+    fn test_mark_consumed_once_per_consumer() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        let route_hash = test_hash(&env, 240);
+        client.commit_route(&committer, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+
+        let (first, second) = (Address::generate(&env), Address::generate(&env));
+        client.mark_consumed(&first, &route_hash, &test_hash(&env, 241));
+        client.mark_consumed(&second, &route_hash, &test_hash(&env, 242));
+
+        let (_, topics, data) = env.events().all().last().unwrap();
+        assert_eq!(topics, (Symbol::new(&env, "consumed"), route_hash.clone()).into_val(&env));
+        let payload: (Address, BytesN<32>, u64) = data.into_val(&env);
+        assert_eq!(payload, (second.clone(), test_hash(&env, 242), 1700000000));
+
+        let result = client.try_mark_consumed(&first, &route_hash, &test_hash(&env, 243));
+        assert_eq!(result, Err(Ok(RegistryError::AlreadyConsumed)));
+
+        let records = client.get_consumptions(&route_hash);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records.get(0).unwrap().consumption_ref, test_hash(&env, 241));
+        assert!(client.was_consumed_by(&route_hash, &first));
+        assert!(client.was_consumed_by(&route_hash, &second));
+        assert!(!client.was_consumed_by(&route_hash, &committer));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_mark_consumed_requires_active_commitment() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        let consumer = Address::generate(&env);
+        let consume = |seed: u8| client.try_mark_consumed(&consumer, &test_hash(&env, seed), &test_hash(&env, 250));
+
+        assert_eq!(consume(244), Err(Ok(RegistryError::NotFound)));
+
+        client.commit_existence(&committer, &test_hash(&env, 245));
+        assert_eq!(consume(245), Err(Ok(RegistryError::NotFound)));
+
+        client.commit_route_scheduled(&committer, &test_hash(&env, 246), &test_hash(&env, 2), &test_hash(&env, 3), &1700000600, &0u64);
+        assert_eq!(consume(246), Err(Ok(RegistryError::CommitmentNotActive)));
+
+        client.commit_route(&committer, &test_hash(&env, 247), &test_hash(&env, 2), &test_hash(&env, 3), &1700000300);
+        env.ledger().with_mut(|li| li.timestamp = 1700000300);
+        assert_eq!(consume(247), Err(Ok(RegistryError::CommitmentNotActive)));
+
+        let result = client.try_mark_consumed(&consumer, &test_hash(&env, 246), &BytesN::from_array(&env, &[0u8; 32]));
+        assert_eq!(result, Err(Ok(RegistryError::EmptyConsumptionRef)));
+        assert_eq!(client.get_consumptions(&test_hash(&env, 246)).len(), 0);
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    /// Minimal escrow that releases funds at most once per commitment
+    #[contract]
+    struct TestEscrow;
+
+    #[contractimpl]
+    impl TestEscrow {
+        /// Returns whether the release went through
+        pub fn release(env: Env, registry: Address, route_hash: BytesN<32>, escrow_id: BytesN<32>) -> bool {
+            let registry = RouteIntegrityRegistryClient::new(&env, &registry);
+            matches!(
+                registry.try_mark_consumed(&env.current_contract_address(), &route_hash, &escrow_id),
+                Ok(Ok(()))
+            )
+        }
+    }
+
+    #[test]
+    fn test_mark_consumed_guards_escrow_release() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        let route_hash = test_hash(&env, 248);
+        client.commit_route(&committer, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+
+        // From here on only the escrow's own invoker auth counts
+        env.set_auths(&[]);
+        let escrow_id = env.register_contract(None, TestEscrow);
+        let escrow = TestEscrowClient::new(&env, &escrow_id);
+
+        assert!(escrow.release(&contract_id, &route_hash, &test_hash(&env, 249)));
+        assert!(!escrow.release(&contract_id, &route_hash, &test_hash(&env, 249)));
+        assert!(!escrow.release(&contract_id, &test_hash(&env, 251), &test_hash(&env, 249)));
+
+        assert!(client.was_consumed_by(&route_hash, &escrow_id));
+        assert_eq!(client.get_consumptions(&route_hash).len(), 1);
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_commit_as_credits_owner_with_delegate_auth_only() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+
+        let owner = Address::generate(&env);
+        let delegate = Address::generate(&env);
+        client.approve_delegate(&owner, &delegate);
+        assert!(client.is_delegate(&owner, &delegate));
+
+        let (_, topics, data) = env.events().all().last().unwrap();
+        assert_eq!(topics, (symbol_short!("delegate"), owner.clone()).into_val(&env));
+        let payload: (Address, u64) = data.into_val(&env);
+        assert_eq!(payload, (delegate.clone(), 1700000000));
+
+        let route_hash = test_hash(&env, 252);
+        client.commit_as(&owner, &delegate, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+
+        let authorized: std::vec::Vec<Address> = env.auths().into_iter().map(|(addr, _)| addr).collect();
+        assert_eq!(authorized, std::vec![delegate.clone()]);
+        assert_eq!(client.get_commit(&route_hash).committer, owner);
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_commit_as_rejects_missing_or_revoked_delegation() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+
+        let owner = Address::generate(&env);
+        let delegate = Address::generate(&env);
+        let commit_as = |seed: u8| {
+            client.try_commit_as(&owner, &delegate, &test_hash(&env, seed), &test_hash(&env, 2), &test_hash(&env, 3), &0u64)
+        };
+
+        assert_eq!(commit_as(253), Err(Ok(RegistryError::Unauthorized)));
+
+        client.approve_delegate(&owner, &delegate);
+        assert_eq!(commit_as(253), Ok(Ok(1)));
+
+        client.revoke_delegate(&owner, &delegate);
+        let (_, topics, data) = env.events().all().last().unwrap();
+        assert_eq!(topics, (symbol_short!("revoked"), owner.clone()).into_val(&env));
+        let revoked: Address = data.into_val(&env);
+        assert_eq!(revoked, delegate);
+
+        assert!(!client.is_delegate(&owner, &delegate));
+        assert_eq!(commit_as(254), Err(Ok(RegistryError::Unauthorized)));
+        assert!(!client.has_commit(&test_hash(&env, 254)));
+
+        assert_eq!(client.try_revoke_delegate(&owner, &delegate), Err(Ok(RegistryError::NotFound)));
+        assert_eq!(client.try_approve_delegate(&owner, &owner), Err(Ok(RegistryError::SelfReferentialAddress)));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_approve_delegate_requires_owner_auth() {
+        let env = setup_env();
+        env.set_auths(&[]);
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+
+        let owner = Address::generate(&env);
+        let delegate = Address::generate(&env);
+        assert!(client.try_approve_delegate(&owner, &delegate).is_err());
+        assert!(!client.is_delegate(&owner, &delegate));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_grant_delegate_enforces_each_restriction_independently() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+        let (php, mxn) = (Symbol::new(&env, "USDC_PHP"), Symbol::new(&env, "USDC_MXN"));
+        client.register_corridor(&owner, &php, &test_hash(&env, 190), &test_hash(&env, 191));
+        client.register_corridor(&owner, &mxn, &test_hash(&env, 190), &test_hash(&env, 192));
+        let (payouts, retail) = (Symbol::new(&env, "payouts"), Symbol::new(&env, "retail"));
+        let scope = |namespace: &Symbol, corridor: &Symbol| CommitScope { namespace: namespace.clone(), corridor: corridor.clone() };
+        let (rules, solver) = (test_hash(&env, 2), test_hash(&env, 3));
+        let commit_as = |delegate: &Address, seed: u8| client.try_commit_as(&owner, delegate, &test_hash(&env, seed), &rules, &solver, &0u64);
+        let commit_scoped = |delegate: &Address, scope: &CommitScope, seed: u8| {
+            client.try_commit_as_scoped(&owner, delegate, scope, &entry(&env, seed, 0))
+        };
+        let unauthorized = Err(Ok(RegistryError::Unauthorized));
+
+        // Namespace only
+        let by_namespace = Address::generate(&env);
+        client.grant_delegate(&owner, &by_namespace, &0, &Some(payouts.clone()), &None, &UNLIMITED_COMMITS);
+        assert_eq!(commit_as(&by_namespace, 200), unauthorized);
+        assert_eq!(commit_scoped(&by_namespace, &scope(&retail, &php), 200), unauthorized);
+        assert_eq!(commit_scoped(&by_namespace, &scope(&payouts, &mxn), 200), Ok(Ok(1)));
+        assert_eq!(client.get_scope(&test_hash(&env, 200)), Some(scope(&payouts, &mxn)));
+        assert_eq!(client.get_grant(&owner, &by_namespace).unwrap().remaining_commits, UNLIMITED_COMMITS);
+
+        // Corridor only
+        let by_corridor = Address::generate(&env);
+        client.grant_delegate(&owner, &by_corridor, &0, &None, &Some(php.clone()), &UNLIMITED_COMMITS);
+        assert_eq!(commit_as(&by_corridor, 201), unauthorized);
+        assert_eq!(commit_scoped(&by_corridor, &scope(&payouts, &mxn), 201), unauthorized);
+        let unregistered = scope(&payouts, &Symbol::new(&env, "EUR_KES"));
+        assert_eq!(commit_scoped(&by_corridor, &unregistered, 201), Err(Ok(RegistryError::NotFound)));
+        assert_eq!(commit_scoped(&by_corridor, &scope(&retail, &php), 201), Ok(Ok(2)));
+
+        // Expiry only: usable strictly before expires_at
+        let by_time = Address::generate(&env);
+        client.grant_delegate(&owner, &by_time, &1700000100, &None, &None, &UNLIMITED_COMMITS);
+        env.ledger().with_mut(|li| li.timestamp = 1700000099);
+        assert!(client.is_delegate(&owner, &by_time));
+        assert_eq!(commit_as(&by_time, 203), Ok(Ok(3)));
+        env.ledger().with_mut(|li| li.timestamp = 1700000100);
+        assert!(!client.is_delegate(&owner, &by_time));
+        assert_eq!(commit_as(&by_time, 204), unauthorized);
+        assert_eq!(commit_scoped(&by_time, &scope(&payouts, &php), 204), unauthorized);
+        assert!(client.get_grant(&owner, &by_time).is_some());
+
+        // Grants that could never be used are refused
+        let other = Address::generate(&env);
+        assert_eq!(client.try_grant_delegate(&owner, &other, &0, &None, &None, &0), Err(Ok(RegistryError::EmptyBatch)));
+        assert_eq!(
+            client.try_grant_delegate(&owner, &other, &1700000100, &None, &None, &1),
+            Err(Ok(RegistryError::ExpiredTimestamp))
+        );
+        let unknown = Some(Symbol::new(&env, "EUR_KES"));
+        assert_eq!(client.try_grant_delegate(&owner, &other, &0, &None, &unknown, &1), Err(Ok(RegistryError::NotFound)));
+        assert_eq!(
+            client.try_grant_delegate(&owner, &owner, &0, &None, &None, &1),
+            Err(Ok(RegistryError::SelfReferentialAddress))
+        );
+        assert_eq!(client.get_grant(&owner, &other), None);
+        replay::ReplayModel::from_events(&env, &contract_id).assert_matches(&env, &client);
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_grant_delegate_counts_down_to_exhaustion() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
+        let delegate = Address::generate(&env);
+        let php = Symbol::new(&env, "USDC_PHP");
+        client.register_corridor(&owner, &php, &test_hash(&env, 190), &test_hash(&env, 191));
+        let (rules, solver) = (test_hash(&env, 2), test_hash(&env, 3));
+        let commit_as = |seed: u8| client.try_commit_as(&owner, &delegate, &test_hash(&env, seed), &rules, &solver, &0u64);
+
+        client.grant_delegate(&owner, &delegate, &0, &None, &None, &2);
+        let (_, topics, data) = env.events().all().last().unwrap();
+        assert_eq!(topics, (symbol_short!("grant"), owner.clone()).into_val(&env));
+        let granted: DelegationGrant = data.into_val(&env);
+        assert_eq!(Some(granted), client.get_grant(&owner, &delegate));
+
+        assert_eq!(commit_as(210), Ok(Ok(1)));
+        let (_, topics, data) = env.events().all().last().unwrap();
+        assert_eq!(topics, (symbol_short!("delegated"), test_hash(&env, 210)).into_val(&env));
+        let payload: (Address, u32, Option<CommitScope>) = data.into_val(&env);
+        assert_eq!(payload, (delegate.clone(), 1, None));
+
+        assert_eq!(commit_as(211), Ok(Ok(2)));
+        let (_, topics, data) = env.events().all().last().unwrap();
+        assert_eq!(topics, (Symbol::new(&env, "grant_exhausted"), owner.clone()).into_val(&env));
+        let exhausted: Address = data.into_val(&env);
+        assert_eq!(exhausted, delegate);
+
+        // An exhausted grant stays readable, but no longer works
+        assert_eq!(commit_as(212), Err(Ok(RegistryError::Unauthorized)));
+        assert_eq!(client.get_grant(&owner, &delegate).unwrap().remaining_commits, 0);
+        assert!(!client.is_delegate(&owner, &delegate));
+
+        // Combined restrictions: each must hold, and a failed commit spends nothing
+        let payouts = Symbol::new(&env, "payouts");
+        client.grant_delegate(&owner, &delegate, &1700000100, &Some(payouts.clone()), &Some(php.clone()), &1);
+        let commit_scoped = |namespace: &Symbol, seed: u8| {
+            let scope = CommitScope { namespace: namespace.clone(), corridor: php.clone() };
+            client.try_commit_as_scoped(&owner, &delegate, &scope, &entry(&env, seed, 0))
+        };
+        assert_eq!(commit_scoped(&Symbol::new(&env, "retail"), 213), Err(Ok(RegistryError::Unauthorized)));
+        assert_eq!(commit_as(213), Err(Ok(RegistryError::Unauthorized)));
+        let duplicate = commit_scoped(&payouts, 210);
+        assert_eq!(duplicate, Err(Ok(RegistryError::DuplicateCommitment)));
+        assert_eq!(client.get_grant(&owner, &delegate).unwrap().remaining_commits, 1);
+        assert_eq!(commit_scoped(&payouts, 213), Ok(Ok(3)));
+        assert_eq!(commit_scoped(&payouts, 214), Err(Ok(RegistryError::Unauthorized)));
+
+        // Revocation still withdraws the spent grant
+        client.revoke_delegate(&owner, &delegate);
+        assert_eq!(client.get_grant(&owner, &delegate), None);
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_verify_commit_from_listed_directory() {
+        use committer_directory::{CommitterDirectory, CommitterDirectoryClient};
+
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let directory_id = env.register_contract(None, CommitterDirectory);
+        let directory = CommitterDirectoryClient::new(&env, &directory_id);
+
+        let (rules_hash, solver_hash) = (test_hash(&env, 2), test_hash(&env, 3));
+        let listed = Address::generate(&env);
+        let unlisted = Address::generate(&env);
+        directory.register(&listed, &test_hash(&env, 255));
+
+        let (listed_route, unlisted_route) = (test_hash(&env, 20), test_hash(&env, 21));
+        client.commit_route(&listed, &listed_route, &rules_hash, &solver_hash, &0u64);
+        client.commit_route(&unlisted, &unlisted_route, &rules_hash, &solver_hash, &0u64);
+
+        assert!(client.verify_commit_from_listed(&directory_id, &listed_route, &rules_hash, &solver_hash));
+        assert!(!client.verify_commit_from_listed(&directory_id, &listed_route, &solver_hash, &solver_hash));
+        assert!(!client.verify_commit_from_listed(&directory_id, &unlisted_route, &rules_hash, &solver_hash));
+        assert!(!client.verify_commit_from_listed(&directory_id, &test_hash(&env, 22), &rules_hash, &solver_hash));
+
+        // The plain check is unaffected by the overlay
+        assert!(client.verify_commit(&unlisted_route, &rules_hash, &solver_hash));
+
+        directory.unregister(&listed);
+        assert!(!client.verify_commit_from_listed(&directory_id, &listed_route, &rules_hash, &solver_hash));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_verify_commit_from_listed_without_directory() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        let route_hash = test_hash(&env, 23);
+        client.commit_route(&committer, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+
+        // No contract is deployed at this address
+        let missing = Address::generate(&env);
+        assert!(!client.verify_commit_from_listed(&missing, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3)));
+        assert!(client.verify_commit(&route_hash, &test_hash(&env, 2), &test_hash(&env, 3)));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    /// Fixed test keypair, so signatures are reproducible across runs
+    fn signing_key() -> ed25519_dalek::SigningKey {
+        ed25519_dalek::SigningKey::from_bytes(&[7u8; 32])
+    }
+
+    /// Sign the `commit_route_signed` message for `route_hash` with
+    /// `core(env)`, addressed to `registry`
+    fn sign_commit(env: &Env, registry: &Address, route_hash: &BytesN<32>, expiry: u64) -> (BytesN<32>, BytesN<64>) {
+        sign_commit_with(env, registry, &signing_key(), route_hash, expiry)
+    }
+
+    /// `sign_commit` with another key
+    fn sign_commit_with(
+        env: &Env,
+        registry: &Address,
+        key: &ed25519_dalek::SigningKey,
+        route_hash: &BytesN<32>,
+        expiry: u64,
+    ) -> (BytesN<32>, BytesN<64>) {
+        let core = core(env);
+        let mut message = signed_prefix(env, registry, b"stella.signed.v1");
+        message.extend_from_slice(&route_hash.to_array());
+        message.extend_from_slice(&core.rules_hash.to_array());
+        message.extend_from_slice(&core.solver_version_hash.to_array());
+        message.extend_from_slice(&expiry.to_be_bytes());
+        sign_message(env, key, &message)
+    }
+
+    /// Sign the `register_signer` message for `committer` with `key`, addressed to `registry`
+    fn sign_registration(
+        env: &Env,
+        registry: &Address,
+        key: &ed25519_dalek::SigningKey,
+        committer: &Address,
+    ) -> (BytesN<32>, BytesN<64>) {
+        let mut message = signed_prefix(env, registry, b"stella.signer.v1");
+        let xdr = committer.clone().to_xdr(env);
+        let mut committer_xdr = std::vec![0u8; xdr.len() as usize];
+        xdr.copy_into_slice(&mut committer_xdr);
+        message.extend_from_slice(&committer_xdr);
+        sign_message(env, key, &message)
+    }
+
+    /// Domain tag, network id and raw registry id opening every signed message
+    fn signed_prefix(env: &Env, registry: &Address, domain: &[u8; 16]) -> std::vec::Vec<u8> {
+        let mut raw_id = [0u8; 32];
+        registry.clone().to_xdr(env).slice(8..40).copy_into_slice(&mut raw_id);
+
+        let mut message = std::vec::Vec::new();
+        message.extend_from_slice(domain);
+        message.extend_from_slice(&env.ledger().network_id().to_array());
+        message.extend_from_slice(&raw_id);
+        message
+    }
+
+    fn sign_message(env: &Env, key: &ed25519_dalek::SigningKey, message: &[u8]) -> (BytesN<32>, BytesN<64>) {
+        use ed25519_dalek::Signer as _;
+
+        let signature = key.sign(message);
+        (
+            BytesN::from_array(env, &key.verifying_key().to_bytes()),
+            BytesN::from_array(env, &signature.to_bytes()),
+        )
+    }
+
+    /// Stellar account whose ed25519 key is `pubkey`
+    fn signer_address(env: &Env, pubkey: &BytesN<32>) -> Address {
+        use soroban_sdk::xdr::{AccountId, PublicKey, ScAddress, ScVal, Uint256};
+
+        let account = ScAddress::Account(AccountId(PublicKey::PublicKeyTypeEd25519(Uint256(pubkey.to_array()))));
+        Address::try_from_val(env, &ScVal::Address(account)).unwrap()
+    }
+
+    #[test]
+    fn test_commit_route_signed_stores_signer() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let (rules, solver) = (test_hash(&env, 2), test_hash(&env, 3));
+
+        // Submitted without any auth: the signature alone credits the signer's account
+        env.set_auths(&[]);
+        let route_hash = test_hash(&env, 24);
+        let (pubkey, signature) = sign_commit(&env, &contract_id, &route_hash, 1700003600);
+        client.commit_route_signed(&pubkey, &signature, &route_hash, &rules, &solver, &1700003600);
+
+        let signer = signer_address(&env, &pubkey);
+        assert_eq!(client.get_signer(&route_hash), Some(pubkey.clone()));
+        assert_eq!(client.get_commit(&route_hash).committer, signer);
+        assert_eq!(client.get_commit_count_by_committer(&signer), 1);
+
+        let (_, topics, data) = env.events().all().last().unwrap();
+        assert_eq!(topics, (symbol_short!("signed"), route_hash.clone()).into_val(&env));
+        let event_key: BytesN<32> = data.into_val(&env);
+        assert_eq!(event_key, pubkey);
+
+        // The signer's account manages the commitment like any committer
+        env.mock_all_auths();
+        client.revoke_commit(&route_hash, &signer, &test_hash(&env, 4));
+        assert!(client.is_revoked(&route_hash));
+
+        // Plain commits carry no signer
+        client.commit_route(&signer, &test_hash(&env, 25), &rules, &solver, &0u64);
+        assert_eq!(client.get_signer(&test_hash(&env, 25)), None);
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_commit_route_signed_rejects_tampered_signatures() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let (rules, solver) = (test_hash(&env, 2), test_hash(&env, 3));
+
+        let route_hash = test_hash(&env, 26);
+        let (pubkey, signature) = sign_commit(&env, &contract_id, &route_hash, 0);
+
+        let mut flipped = signature.to_array();
+        flipped[0] ^= 1;
+        let flipped = BytesN::from_array(&env, &flipped);
+        let other_key = BytesN::from_array(&env, &ed25519_dalek::SigningKey::from_bytes(&[8u8; 32]).verifying_key().to_bytes());
+
+        let attempts = [
+            client.try_commit_route_signed(&pubkey, &flipped, &route_hash, &rules, &solver, &0u64),
+            client.try_commit_route_signed(&other_key, &signature, &route_hash, &rules, &solver, &0u64),
+            // Signed for a different expiry
+            client.try_commit_route_signed(&pubkey, &signature, &route_hash, &rules, &solver, &1700003600),
+            // Signed for a different route
+            client.try_commit_route_signed(&pubkey, &signature, &test_hash(&env, 27), &rules, &solver, &0u64),
+            // Signed for different rules
+            client.try_commit_route_signed(&pubkey, &signature, &route_hash, &test_hash(&env, 4), &solver, &0u64),
+        ];
+        for result in attempts {
+            // InvalidSignature is a host trap, not a RegistryError code
+            assert!(matches!(result, Err(Err(_))));
+        }
+        assert!(!client.has_commit(&route_hash));
+        assert_eq!(client.get_signer(&route_hash), None);
+
+        // Signed for another registry
+        let other_id = env.register_contract(None, RouteIntegrityRegistry);
+        let other = RouteIntegrityRegistryClient::new(&env, &other_id);
+        assert!(matches!(other.try_commit_route_signed(&pubkey, &signature, &route_hash, &rules, &solver, &0u64), Err(Err(_))));
+
+        // Signed for another network
+        env.ledger().with_mut(|li| li.network_id = [7u8; 32]);
+        assert!(matches!(client.try_commit_route_signed(&pubkey, &signature, &route_hash, &rules, &solver, &0u64), Err(Err(_))));
+        env.ledger().with_mut(|li| li.network_id = [0u8; 32]);
+
+        client.commit_route_signed(&pubkey, &signature, &route_hash, &rules, &solver, &0u64);
+        assert!(client.has_commit(&route_hash));
+
+        // A valid signature still goes through commit_route's validation
+        assert_eq!(
+            client.try_commit_route_signed(&pubkey, &signature, &route_hash, &rules, &solver, &0u64),
+            Err(Ok(RegistryError::DuplicateCommitment))
+        );
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_registered_signer_credits_its_committer() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let (rules, solver) = (test_hash(&env, 2), test_hash(&env, 3));
+        let committer = Address::generate(&env);
+        let offline = ed25519_dalek::SigningKey::from_bytes(&[9u8; 32]);
+
+        let (pubkey, proof) = sign_registration(&env, &contract_id, &offline, &committer);
+        // The key's consent names the committer it is registered to
+        let stranger = Address::generate(&env);
+        assert!(matches!(client.try_register_signer(&stranger, &pubkey, &proof), Err(Err(_))));
+        client.register_signer(&committer, &pubkey, &proof);
+        let registration = SignerRegistration { committer: committer.clone(), registered_at: 1700000000 };
+        assert_eq!(client.get_signer_registration(&pubkey), Some(registration));
+        assert_eq!(client.get_signers(&committer), Vec::from_array(&env, [pubkey.clone()]));
+        assert_eq!(client.get_signer_deactivation(&pubkey), None);
+
+        // A hot relayer submits the offline-signed payload without any auth
+        env.set_auths(&[]);
+        let route_hash = test_hash(&env, 28);
+        let (_, signature) = sign_commit_with(&env, &contract_id, &offline, &route_hash, 0);
+        client.commit_route_signed(&pubkey, &signature, &route_hash, &rules, &solver, &0u64);
+        assert_eq!(client.get_commit(&route_hash).committer, committer);
+        assert_eq!(client.get_signer(&route_hash), Some(pubkey.clone()));
+        assert_eq!(client.get_commit_count_by_committer(&signer_address(&env, &pubkey)), 0);
+
+        // An unregistered key only ever credits its own account
+        let unregistered = ed25519_dalek::SigningKey::from_bytes(&[10u8; 32]);
+        let other_route = test_hash(&env, 29);
+        let (other_pubkey, signature) = sign_commit_with(&env, &contract_id, &unregistered, &other_route, 0);
+        client.commit_route_signed(&other_pubkey, &signature, &other_route, &rules, &solver, &0u64);
+        assert_eq!(client.get_commit(&other_route).committer, signer_address(&env, &other_pubkey));
+        assert_eq!(client.get_commit_count_by_committer(&committer), 1);
+
+        // A key is registered once, to one committer
+        env.mock_all_auths();
+        let (_, stranger_proof) = sign_registration(&env, &contract_id, &offline, &stranger);
+        assert_eq!(
+            client.try_register_signer(&stranger, &pubkey, &stranger_proof),
+            Err(Ok(RegistryError::SignerAlreadyRegistered))
+        );
+        assert_eq!(
+            client.try_register_signer(&committer, &pubkey, &proof),
+            Err(Ok(RegistryError::SignerAlreadyRegistered))
+        );
+        replay::ReplayModel::from_events(&env, &contract_id).assert_matches(&env, &client);
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_deactivated_signer_is_rejected_from_then_on() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let (rules, solver) = (test_hash(&env, 2), test_hash(&env, 3));
+        let committer = Address::generate(&env);
+        let offline = ed25519_dalek::SigningKey::from_bytes(&[11u8; 32]);
+        let (pubkey, proof) = sign_registration(&env, &contract_id, &offline, &committer);
+        client.register_signer(&committer, &pubkey, &proof);
+
+        let (before, after) = (test_hash(&env, 30), test_hash(&env, 31));
+        let (_, before_signature) = sign_commit_with(&env, &contract_id, &offline, &before, 0);
+        // Signed offline ahead of the deactivation, submitted after it
+        let (_, after_signature) = sign_commit_with(&env, &contract_id, &offline, &after, 0);
+        client.commit_route_signed(&pubkey, &before_signature, &before, &rules, &solver, &0u64);
+
+        let unknown = BytesN::from_array(&env, &ed25519_dalek::SigningKey::from_bytes(&[12u8; 32]).verifying_key().to_bytes());
+        assert_eq!(client.try_deactivate_signer(&committer, &unknown), Err(Ok(RegistryError::NotFound)));
+        let stranger = Address::generate(&env);
+        assert_eq!(client.try_deactivate_signer(&stranger, &pubkey), Err(Ok(RegistryError::Unauthorized)));
+
+        env.ledger().with_mut(|li| li.timestamp += 60);
+        client.deactivate_signer(&committer, &pubkey);
+        assert_eq!(client.get_signer_deactivation(&pubkey), Some(1700000060));
+        let (_, topics, data) = env.events().all().last().unwrap();
+        assert_eq!(topics, (Symbol::new(&env, "signeroff"), committer.clone()).into_val(&env));
+        let payload: (BytesN<32>, u64) = data.into_val(&env);
+        assert_eq!(payload, (pubkey.clone(), 1700000060));
+
+        assert_eq!(
+            client.try_commit_route_signed(&pubkey, &after_signature, &after, &rules, &solver, &0u64),
+            Err(Ok(RegistryError::SignerDeactivated))
+        );
+        assert!(!client.has_commit(&after));
+        // Earlier commitments stand, and the key keeps its slot
+        assert_eq!(client.get_commit(&before).committer, committer);
+        assert_eq!(client.get_signers(&committer), Vec::from_array(&env, [pubkey.clone()]));
+        assert_eq!(client.try_deactivate_signer(&committer, &pubkey), Err(Ok(RegistryError::SignerDeactivated)));
+        assert_eq!(client.try_register_signer(&committer, &pubkey, &proof), Err(Ok(RegistryError::SignerAlreadyRegistered)));
+
+        // Deactivated keys count against max_signers_per_committer
+        for seed in 1..MAX_SIGNERS_PER_COMMITTER as u8 {
+            let key = ed25519_dalek::SigningKey::from_bytes(&[100 + seed; 32]);
+            let (pubkey, proof) = sign_registration(&env, &contract_id, &key, &committer);
+            client.register_signer(&committer, &pubkey, &proof);
+        }
+        let key = ed25519_dalek::SigningKey::from_bytes(&[200u8; 32]);
+        let (extra, proof) = sign_registration(&env, &contract_id, &key, &committer);
+        assert_eq!(client.try_register_signer(&committer, &extra, &proof), Err(Ok(RegistryError::TooManySigners)));
+        assert_eq!(client.get_signer_registration(&extra), None);
+        replay::ReplayModel::from_events(&env, &contract_id).assert_matches(&env, &client);
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_endorse_commit_is_idempotent_per_endorser() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        let route_hash = test_hash(&env, 28);
+        client.commit_route(&committer, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+
+        let (reviewer, auditor) = (Address::generate(&env), Address::generate(&env));
+        client.endorse_commit(&route_hash, &reviewer);
+        let (_, topics, data) = env.events().all().last().unwrap();
+        assert_eq!(topics, (symbol_short!("endorsed"), route_hash.clone()).into_val(&env));
+        let payload: (Address, u64) = data.into_val(&env);
+        assert_eq!(payload, (reviewer.clone(), 1700000000));
+
+        client.endorse_commit(&route_hash, &auditor);
+        let events_before = env.events().all().len();
+        client.endorse_commit(&route_hash, &reviewer);
+        assert_eq!(env.events().all().len(), events_before);
+
+        assert_eq!(client.get_endorsements(&route_hash), Vec::from_array(&env, [reviewer.clone(), auditor.clone()]));
+        assert!(client.is_endorsed_by(&route_hash, &reviewer));
+        assert!(client.is_endorsed_by(&route_hash, &auditor));
+        assert!(!client.is_endorsed_by(&route_hash, &committer));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_endorse_commit_rejects_missing_and_self_endorsement() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        let reviewer = Address::generate(&env);
+
+        assert_eq!(client.try_endorse_commit(&test_hash(&env, 29), &reviewer), Err(Ok(RegistryError::NotFound)));
+        client.commit_existence(&committer, &test_hash(&env, 29));
+        assert_eq!(client.try_endorse_commit(&test_hash(&env, 29), &reviewer), Err(Ok(RegistryError::NotFound)));
+
+        let route_hash = test_hash(&env, 30);
+        client.commit_route(&committer, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        let result = client.try_endorse_commit(&route_hash, &committer);
+        assert_eq!(result, Err(Ok(RegistryError::SelfReferentialAddress)));
+        assert_eq!(client.get_endorsements(&route_hash).len(), 0);
+
+        for _ in 0..MAX_ENDORSEMENTS_PER_ROUTE {
+            client.endorse_commit(&route_hash, &Address::generate(&env));
+        }
+        let result = client.try_endorse_commit(&route_hash, &reviewer);
+        assert_eq!(result, Err(Ok(RegistryError::TooManyEndorsements)));
+        assert!(!client.is_endorsed_by(&route_hash, &reviewer));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_register_profile_owner_updates() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        // Commitments do not need a profile
+        client.commit_route(&committer, &test_hash(&env, 31), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        assert_eq!(client.get_profile(&committer), None);
+
+        let uri = Bytes::from_slice(&env, b"https://stella.example/committer.json");
+        client.register_profile(&committer, &test_hash(&env, 32), &uri);
+        let (_, topics, data) = env.events().all().last().unwrap();
+        assert_eq!(topics, (symbol_short!("profile"), committer.clone()).into_val(&env));
+        let payload: (BytesN<32>, Bytes, u64) = data.into_val(&env);
+        assert_eq!(payload, (test_hash(&env, 32), uri.clone(), 1700000000));
+
+        env.ledger().with_mut(|li| li.timestamp += 60);
+        client.register_profile(&committer, &test_hash(&env, 33), &uri);
+        let authorized: std::vec::Vec<Address> = env.auths().into_iter().map(|(addr, _)| addr).collect();
+        assert_eq!(authorized, std::vec![committer.clone()]);
+
+        let profile = client.get_profile(&committer).unwrap();
+        assert_eq!(profile, CommitterProfile { name_hash: test_hash(&env, 33), metadata_uri: uri, updated_at: 1700000060 });
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_register_profile_validates_inputs() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        let at_limit = Bytes::from_array(&env, &[b'a'; MAX_PROFILE_URI_BYTES as usize]);
+        let over_limit = Bytes::from_array(&env, &[b'a'; MAX_PROFILE_URI_BYTES as usize + 1]);
+        let zero = BytesN::from_array(&env, &[0u8; 32]);
+
+        let result = client.try_register_profile(&committer, &test_hash(&env, 34), &over_limit);
+        assert_eq!(result, Err(Ok(RegistryError::ProfileUriTooLong)));
+        let result = client.try_register_profile(&committer, &zero, &at_limit);
+        assert_eq!(result, Err(Ok(RegistryError::EmptyNameHash)));
+        assert_eq!(client.get_profile(&committer), None);
+
+        client.register_profile(&committer, &test_hash(&env, 34), &at_limit);
+        assert_eq!(client.get_profile(&committer).unwrap().metadata_uri, at_limit);
+    }
+
+    #[test]
+    fn test_register_profile_requires_owner_auth() {
+        let env = setup_env();
+        env.set_auths(&[]);
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+
+        let committer = Address::generate(&env);
+        let uri = Bytes::from_slice(&env, b"ipfs://impostor");
+        assert!(client.try_register_profile(&committer, &test_hash(&env, 35), &uri).is_err());
+        assert_eq!(client.get_profile(&committer), None);
+    }
+
+    #[test]
+    fn test_commit_counts_stay_independent_per_committer() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+
+        let (alice, bob, carol) = (Address::generate(&env), Address::generate(&env), Address::generate(&env));
+        let order = [&alice, &bob, &alice, &carol, &alice, &bob];
+        for (seed, committer) in (36u8..).zip(order) {
+            client.commit_route(committer, &test_hash(&env, seed), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        }
+        let (pubkey, signature) = sign_commit(&env, &contract_id, &test_hash(&env, 42), 0);
+        client.commit_route_signed(&pubkey, &signature, &test_hash(&env, 42), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+
+        assert_eq!(client.get_commit_count_by_committer(&alice), 3);
+        assert_eq!(client.get_commit_count_by_committer(&bob), 2);
+        assert_eq!(client.get_commit_count_by_committer(&carol), 1);
+        assert_eq!(client.get_commit_count_by_committer(&signer_address(&env, &pubkey)), 1);
+        assert_eq!(client.get_commit_count_by_committer(&Address::generate(&env)), 0);
+
+        // Sandbox, existence-only and event-only entries are not counted
+        client.commit_route_sandbox(&bob, &test_hash(&env, 43), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        client.commit_existence(&bob, &test_hash(&env, 44));
+        client.emit_commit_only(&bob, &test_hash(&env, 45), &test_hash(&env, 2), &test_hash(&env, 3));
+        assert_eq!(client.get_commit_count_by_committer(&bob), 2);
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_commit_count_overflow_is_rejected() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        env.as_contract(&contract_id, || {
+            let stats = CommitterStats { count: u64::MAX, first_ts: 1700000000, last_ts: 1700000000 };
+            env.storage().persistent().set(&DataKey::CommitterStats(committer.clone()), &stats);
+        });
+
+        let route_hash = test_hash(&env, 46);
+        let result = client.try_commit_route(&committer, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        assert_eq!(result, Err(Ok(RegistryError::CommitCountOverflow)));
+        assert!(!client.has_commit(&route_hash));
+        assert_eq!(client.get_commit_count_by_committer(&committer), u64::MAX);
+
+        let other = Address::generate(&env);
+        env.as_contract(&contract_id, || env.storage().instance().set(&DataKey::TotalCommits, &u64::MAX));
+        let result = client.try_commit_route(&other, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        assert_eq!(result, Err(Ok(RegistryError::CommitCountOverflow)));
+        assert_eq!(client.get_commit_count_by_committer(&other), 0);
+        assert_eq!(client.total_commits(), u64::MAX);
+    }
+
+    #[test]
+    fn test_total_commits_counts_each_stored_commitment_once() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        assert_eq!(client.total_commits(), 0);
+
+        client.commit_route(&committer, &test_hash(&env, 47), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        assert_eq!(client.total_commits(), 1);
+
+        // Failed single and atomic batch commits leave the counter alone
+        let result = client.try_commit_route(&committer, &test_hash(&env, 47), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        assert_eq!(result, Err(Ok(RegistryError::DuplicateCommitment)));
+        let result = client.try_commit_routes(&committer, &Vec::from_array(&env, [entry(&env, 48, 0), entry(&env, 47, 0)]));
+        assert_eq!(result, Err(Ok(RegistryError::DuplicateCommitment)));
+        assert_eq!(client.total_commits(), 1);
+
+        client.commit_routes(&committer, &Vec::from_array(&env, [entry(&env, 48, 0), entry(&env, 49, 0)]));
+        assert_eq!(client.total_commits(), 3);
+
+        // Partial batches count only the entries that were stored
+        let results = client.commit_routes_partial(
+            &committer,
+            &Vec::from_array(&env, [entry(&env, 50, 0), entry(&env, 49, 0), entry(&env, 0, 0), entry(&env, 51, 0)]),
+        );
+        assert_eq!(results.len(), 4);
+        assert_eq!(client.total_commits(), 5);
+
+        // Entries outside the main namespace are not counted
+        client.commit_existence(&committer, &test_hash(&env, 52));
+        client.emit_commit_only(&committer, &test_hash(&env, 53), &test_hash(&env, 2), &test_hash(&env, 3));
+        client.commit_route_sandbox(&committer, &test_hash(&env, 54), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        assert_eq!(client.total_commits(), 5);
+        assert_eq!(client.total_commits(), client.get_commit_count_by_committer(&committer));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_commit_ids_are_dense_in_storage_order() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let (alice, bob) = (Address::generate(&env), Address::generate(&env));
+        assert_eq!(client.latest_commit_id(), 0);
+        assert_eq!(client.try_get_commit_by_id(&1), Err(Ok(RegistryError::NotFound)));
+
+        client.commit_route(&alice, &test_hash(&env, 160), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        let _ = client.try_commit_route(&bob, &test_hash(&env, 160), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        client.commit_route(&bob, &test_hash(&env, 161), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        client.commit_routes_partial(
+            &alice,
+            &Vec::from_array(&env, [entry(&env, 162, 0), entry(&env, 161, 0), entry(&env, 163, 0)]),
+        );
+        client.commit_routes(&bob, &Vec::from_array(&env, [entry(&env, 164, 0), entry(&env, 165, 0)]));
+
+        // Failed and skipped entries take no id; batch entries take ids in input order
+        let expected = [160u8, 161, 162, 163, 164, 165];
+        assert_eq!(client.latest_commit_id(), expected.len() as u64);
+        for (i, seed) in expected.into_iter().enumerate() {
+            let id = i as u64 + 1;
+            let commitment = client.get_commit_by_id(&id);
+            assert_eq!(commitment.commit_id, id);
+            assert_eq!(commitment, client.get_commit(&test_hash(&env, seed)));
+        }
+        assert_eq!(client.try_get_commit_by_id(&0), Err(Ok(RegistryError::NotFound)));
+        assert_eq!(client.try_get_commit_by_id(&7), Err(Ok(RegistryError::NotFound)));
+
+        let (_, _, data) = env.events().all().last().unwrap();
+        let payload: (BytesN<32>, BytesN<32>, Address, u64, u64, u64, u32, u64, u64) = data.into_val(&env);
+        assert_eq!(payload.8, 6);
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_commit_hashes_range_pages_through_registry() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        // The test host's per-call overhead grows with the whole test
+        // ledger; this test is about paging, not cost
+        env.budget().reset_unlimited();
+
+        // 250 commitments, seeds 1..=250, in batches of up to max_batch_size
+        for first in (1u8..=250).step_by(MAX_BATCH_SIZE as usize) {
+            let mut entries = Vec::new(&env);
+            for seed in first..(first + MAX_BATCH_SIZE as u8).min(251) {
+                entries.push_back(entry(&env, seed, 0));
+            }
+            client.commit_routes(&committer, &entries);
+        }
+        assert_eq!(client.latest_commit_id(), 250);
+
+        // Walk in pages of 100, resuming from start_id + returned.len()
+        let mut seen = std::vec::Vec::new();
+        let mut page_sizes = std::vec::Vec::new();
+        let mut start_id = 1u64;
+        loop {
+            let page = client.get_commit_hashes_range(&start_id, &100);
+            if page.is_empty() {
+                break;
+            }
+            page_sizes.push(page.len());
+            start_id += page.len() as u64;
+            seen.extend(page.iter());
+        }
+        assert_eq!(page_sizes, std::vec![100, 100, 50]);
+        let expected: std::vec::Vec<BytesN<32>> = (1u8..=250).map(|seed| test_hash(&env, seed)).collect();
+        assert_eq!(seen, expected);
+
+        // Limits are capped, and out-of-range starts give empty pages
+        assert_eq!(client.get_commit_hashes_range(&1, &u32::MAX).len(), MAX_RANGE_COMMITS_LISTED);
+        assert_eq!(
+            client.get_commit_hashes_range(&249, &100),
+            Vec::from_array(&env, [test_hash(&env, 249), test_hash(&env, 250)])
+        );
+        assert_eq!(client.get_commit_hashes_range(&5, &0), Vec::new(&env));
+        assert_eq!(client.get_commit_hashes_range(&0, &10), Vec::new(&env));
+        assert_eq!(client.get_commit_hashes_range(&251, &10), Vec::new(&env));
+        assert_eq!(client.get_commit_hashes_range(&u64::MAX, &10), Vec::new(&env));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_commits_by_committer_pages_through_history() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let (committer, other) = (Address::generate(&env), Address::generate(&env));
+
+        // Route hashes beyond the 255 distinct `test_hash` seeds
+        let wide_hash = |n: u16| {
+            let mut bytes = [0xabu8; 32];
+            bytes[..2].copy_from_slice(&n.to_be_bytes());
+            BytesN::from_array(&env, &bytes)
+        };
+
+        // The test host's per-call overhead grows with the whole test
+        // ledger; this test is about paging, not cost
+        env.budget().reset_unlimited();
+
+        // 300 commitments from one address, interleaved with another committer's
+        for first in (0u16..300).step_by(MAX_BATCH_SIZE as usize) {
+            let mut entries = Vec::new(&env);
+            for n in first..first + MAX_BATCH_SIZE as u16 {
+                entries.push_back(CommitEntry {
+                    route_hash: wide_hash(n),
+                    rules_hash: test_hash(&env, 2),
+                    solver_version_hash: test_hash(&env, 3),
+                    expiry: 0,
+                });
+            }
+            client.commit_routes(&committer, &entries);
+            client.commit_route(&other, &wide_hash(1000 + first), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        }
+
+        let mut seen = std::vec::Vec::new();
+        let mut offset = 0u32;
+        loop {
+            let page = client.get_commits_by_committer(&committer, &offset, &100);
+            if page.is_empty() {
+                break;
+            }
+            offset += page.len();
+            seen.extend(page.iter());
+        }
+        let expected: std::vec::Vec<BytesN<32>> = (0u16..300).map(wide_hash).collect();
+        assert_eq!(seen, expected);
+
+        assert_eq!(client.get_commits_by_committer(&committer, &0, &u32::MAX).len(), MAX_RANGE_COMMITS_LISTED);
+        assert_eq!(
+            client.get_commits_by_committer(&committer, &298, &100),
+            Vec::from_array(&env, [wide_hash(298), wide_hash(299)])
+        );
+        assert_eq!(client.get_commits_by_committer(&committer, &300, &100), Vec::new(&env));
+        assert_eq!(client.get_commits_by_committer(&committer, &u32::MAX, &100), Vec::new(&env));
+        assert_eq!(client.get_commits_by_committer(&committer, &0, &0), Vec::new(&env));
+        let batch = MAX_BATCH_SIZE as u16;
+        assert_eq!(
+            client.get_commits_by_committer(&other, &0, &3),
+            Vec::from_array(&env, [wide_hash(1000), wide_hash(1000 + batch), wide_hash(1000 + 2 * batch)])
+        );
+        assert_eq!(client.get_commits_by_committer(&Address::generate(&env), &0, &100), Vec::new(&env));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_get_recent_returns_newest_beyond_capacity() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        assert_eq!(client.get_recent(&10), Vec::new(&env));
+
+        client.commit_route(&committer, &test_hash(&env, 1), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        client.commit_route(&committer, &test_hash(&env, 2), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        assert_eq!(client.get_recent(&10), Vec::from_array(&env, [test_hash(&env, 2), test_hash(&env, 1)]));
+
+        // The test host's per-call overhead grows with the whole test
+        // ledger; this test is about ordering, not cost
+        env.budget().reset_unlimited();
+
+        // 130 commitments in all, seeds 1..=130, so the scan stops short of the oldest
+        for first in (3u8..=130).step_by(MAX_BATCH_SIZE as usize) {
+            let mut entries = Vec::new(&env);
+            for seed in first..(first + MAX_BATCH_SIZE as u8).min(131) {
+                entries.push_back(entry(&env, seed, 0));
+            }
+            client.commit_routes(&committer, &entries);
+        }
+        assert_eq!(client.latest_commit_id(), 130);
+
+        let recent = client.get_recent(&RECENT_CAPACITY);
+        let expected: std::vec::Vec<BytesN<32>> = (31u8..=130).rev().map(|seed| test_hash(&env, seed)).collect();
+        assert_eq!(recent.iter().collect::<std::vec::Vec<_>>(), expected);
+        assert_eq!(client.get_recent(&3), Vec::from_array(&env, [test_hash(&env, 130), test_hash(&env, 129), test_hash(&env, 128)]));
+        assert_eq!(client.get_recent(&0), Vec::new(&env));
+        assert_eq!(
+            client.try_get_recent(&(RECENT_CAPACITY + 1)),
+            Err(Ok(RegistryError::RecentCapacityExceeded))
+        );
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_rules_index_splits_commitments_by_rules_version() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        let (v1, v2) = (test_hash(&env, 170), test_hash(&env, 171));
+
+        // Interleave two rules versions across single and batch commits
+        let mut expected: [std::vec::Vec<BytesN<32>>; 2] = Default::default();
+        for seed in 1u8..=9 {
+            let which = (seed % 3 == 0) as usize;
+            let rules_hash = [&v1, &v2][which];
+            client.commit_route(&committer, &test_hash(&env, seed), rules_hash, &test_hash(&env, 3), &0u64);
+            expected[which].push(test_hash(&env, seed));
+        }
+        let mut batch = Vec::new(&env);
+        for seed in [10u8, 11] {
+            batch.push_back(CommitEntry { rules_hash: v2.clone(), ..entry(&env, seed, 0) });
+            expected[1].push(test_hash(&env, seed));
+        }
+        client.commit_routes(&committer, &batch);
+
+        // A failed commit leaves the index alone
+        let result = client.try_commit_route(&committer, &test_hash(&env, 1), &v2, &test_hash(&env, 3), &0u64);
+        assert_eq!(result, Err(Ok(RegistryError::DuplicateCommitment)));
+
+        assert_eq!(client.count_by_rules(&v1), 6);
+        assert_eq!(client.count_by_rules(&v2), 5);
+        for (which, rules_hash) in [&v1, &v2].into_iter().enumerate() {
+            let listed = client.get_commits_by_rules(rules_hash, &0, &100);
+            assert_eq!(listed.iter().collect::<std::vec::Vec<_>>(), expected[which]);
+        }
+        assert_eq!(
+            client.get_commits_by_rules(&v2, &3, &100),
+            Vec::from_array(&env, [test_hash(&env, 10), test_hash(&env, 11)])
+        );
+        assert_eq!(client.get_commits_by_rules(&v1, &1, &2), Vec::from_array(&env, [test_hash(&env, 2), test_hash(&env, 4)]));
+
+        // Unknown rules hashes
+        assert_eq!(client.count_by_rules(&test_hash(&env, 172)), 0);
+        assert_eq!(client.get_commits_by_rules(&test_hash(&env, 172), &0, &100), Vec::new(&env));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_get_committer_matches_get_commit() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let (alice, bob) = (Address::generate(&env), Address::generate(&env));
+
+        client.commit_route(&alice, &test_hash(&env, 1), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        let mut batch = Vec::new(&env);
+        batch.push_back(entry(&env, 4, 0));
+        client.commit_routes(&bob, &batch);
+
+        for (seed, committer) in [(1u8, &alice), (4, &bob)] {
+            let route_hash = test_hash(&env, seed);
+            assert_eq!(client.get_committer(&route_hash), client.get_commit(&route_hash).committer);
+            assert_eq!(&client.get_committer(&route_hash), committer);
+        }
+        assert_eq!(client.try_get_committer(&test_hash(&env, 5)), Err(Ok(RegistryError::NotFound)));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_get_expiry_returns_raw_expiry() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        client.commit_route(&committer, &test_hash(&env, 1), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        client.commit_route(&committer, &test_hash(&env, 4), &test_hash(&env, 2), &test_hash(&env, 3), &1700001000u64);
+
+        assert_eq!(client.get_expiry(&test_hash(&env, 1)), 0);
+        assert_eq!(client.get_expiry(&test_hash(&env, 4)), 1700001000);
+        assert_eq!(client.get_expiry(&test_hash(&env, 4)), client.get_commit(&test_hash(&env, 4)).expiry);
+
+        // Still reported once the quote has lapsed
+        env.ledger().with_mut(|li| li.timestamp = 1700002000);
+        assert_eq!(client.get_expiry(&test_hash(&env, 4)), 1700001000);
+
+        assert_eq!(client.try_get_expiry(&test_hash(&env, 5)), Err(Ok(RegistryError::NotFound)));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_is_expired_boundary() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        let (never, expiring) = (test_hash(&env, 1), test_hash(&env, 4));
+
+        client.commit_route(&committer, &never, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        client.commit_route(&committer, &expiring, &test_hash(&env, 2), &test_hash(&env, 3), &1700001000u64);
+
+        for (now, expired) in [(1700000999u64, false), (1700001000, true), (1700001001, true)] {
+            env.ledger().with_mut(|li| li.timestamp = now);
+            assert_eq!(client.is_expired(&expiring), expired, "now={}", now);
+            assert!(!client.is_expired(&never));
+        }
+
+        // Same boundary commit_route applies: expiry == now is rejected as already expired
+        let result = client.try_commit_route(&committer, &test_hash(&env, 5), &test_hash(&env, 2), &test_hash(&env, 3), &1700001001u64);
+        assert_eq!(result, Err(Ok(RegistryError::ExpiredTimestamp)));
+
+        assert_eq!(client.try_is_expired(&test_hash(&env, 5)), Err(Ok(RegistryError::NotFound)));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_time_remaining_counts_down_to_zero() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        let (never, expiring) = (test_hash(&env, 1), test_hash(&env, 4));
+
+        client.commit_route(&committer, &never, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        client.commit_route(&committer, &expiring, &test_hash(&env, 2), &test_hash(&env, 3), &1700000300u64);
+
+        let mut last = u64::MAX;
+        for now in (1700000000u64..=1700000600).step_by(100) {
+            env.ledger().with_mut(|li| li.timestamp = now);
+            let remaining = client.time_remaining(&expiring);
+            assert_eq!(remaining, 1700000300u64.saturating_sub(now));
+            assert!(remaining <= last);
+            assert_eq!(remaining == 0, client.is_expired(&expiring));
+            assert_eq!(client.time_remaining(&never), u64::MAX);
+            last = remaining;
+        }
+        assert_eq!(last, 0);
+
+        assert_eq!(client.try_time_remaining(&test_hash(&env, 5)), Err(Ok(RegistryError::NotFound)));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_commit_summary_flag_combinations() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        let summary = |exists, expired, revoked, timestamp| CommitSummary { exists, expired, revoked, timestamp };
+
+        let (live, revoked) = (test_hash(&env, 1), test_hash(&env, 4));
+        client.commit_route(&committer, &live, &test_hash(&env, 2), &test_hash(&env, 3), &1700000100u64);
+        env.ledger().with_mut(|li| li.timestamp = 1700000010);
+        client.commit_route(&committer, &revoked, &test_hash(&env, 2), &test_hash(&env, 3), &1700000100u64);
+        client.revoke_commit(&revoked, &committer, &test_hash(&env, 5));
+
+        assert_eq!(client.get_commit_summary(&live), summary(true, false, false, 1700000000));
+        assert_eq!(client.get_commit_summary(&revoked), summary(true, false, true, 1700000010));
+
+        env.ledger().with_mut(|li| li.timestamp = 1700000100);
+        assert_eq!(client.get_commit_summary(&live), summary(true, true, false, 1700000000));
+        assert_eq!(client.get_commit_summary(&revoked), summary(true, true, true, 1700000010));
+
+        // Missing routes and existence-only records are zeroed, never an error
+        client.commit_existence(&committer, &test_hash(&env, 6));
+        for seed in [6u8, 7] {
+            assert_eq!(client.get_commit_summary(&test_hash(&env, seed)), summary(false, false, false, 0));
+        }
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_commits_in_window_walks_day_buckets() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        let day0 = 1700000000 / SECONDS_PER_DAY;
+
+        // Days day0..day0+4 get 1, 2, 0, 3, 1 commitments, at varying times of day
+        let mut seed = 1u8;
+        let mut by_day: [std::vec::Vec<BytesN<32>>; 5] = Default::default();
+        for (offset, count) in [(0u64, 1), (1, 2), (2, 0), (3, 3), (4, 1)] {
+            for i in 0..count {
+                let ts = (day0 + offset) * SECONDS_PER_DAY + 3_600 * (i + 1);
+                env.ledger().with_mut(|li| li.timestamp = ts);
+                client.commit_route(&committer, &test_hash(&env, seed), &test_hash(&env, 200), &test_hash(&env, 201), &0u64);
+                by_day[offset as usize].push(test_hash(&env, seed));
+                seed += 1;
+            }
+        }
+        let day_start = |offset: u64| (day0 + offset) * SECONDS_PER_DAY;
+        let listed = |start: u64, end: u64, offset: u32, limit: u32| {
+            client.get_commits_in_window(&start, &end, &offset, &limit).iter().collect::<std::vec::Vec<_>>()
+        };
+
+        // Partial days round out to whole buckets; the empty day is skipped
+        assert_eq!(listed(day_start(1) + 50_000, day_start(3) + 10, 0, 100), [&by_day[1][..], &by_day[3][..]].concat());
+        assert_eq!(listed(day_start(2), day_start(3) - 1, 0, 100), std::vec::Vec::<BytesN<32>>::new());
+        assert_eq!(listed(day_start(0), day_start(4), 0, 100), by_day.concat());
+
+        // Pages run across bucket boundaries
+        let all = by_day.concat();
+        let mut paged = std::vec::Vec::new();
+        loop {
+            let page = listed(day_start(0), day_start(4), paged.len() as u32, 2);
+            if page.is_empty() {
+                break;
+            }
+            assert!(page.len() <= 2);
+            paged.extend(page);
+        }
+        assert_eq!(paged, all);
+
+        // Window bounds
+        let max_days = client.get_limits().get(Symbol::new(&env, "max_window_days")).unwrap();
+        let result = client.try_get_commits_in_window(&day_start(0), &(day_start(max_days) - 1), &0, &100);
+        assert_eq!(result.map(|hashes| hashes.unwrap().len()), Ok(all.len() as u32));
+        let result = client.try_get_commits_in_window(&day_start(0), &day_start(max_days), &0, &100);
+        assert_eq!(result, Err(Ok(RegistryError::WindowTooLong)));
+        let result = client.try_get_commits_in_window(&day_start(1), &(day_start(1) - 1), &0, &100);
+        assert_eq!(result, Err(Ok(RegistryError::InvalidWindow)));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_daily_counts_follow_day_boundaries() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        let day0 = 1700000000 / SECONDS_PER_DAY + 1;
+        let mut seed = 1u8;
+        let mut commit_at = |ts: u64| {
+            env.ledger().with_mut(|li| li.timestamp = ts);
+            client.commit_route(&committer, &test_hash(&env, seed), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+            seed += 1;
+        };
+
+        // Last second of day0 - 1, first and last second of day0, first second of day0 + 2
+        commit_at(day0 * SECONDS_PER_DAY - 1);
+        commit_at(day0 * SECONDS_PER_DAY);
+        commit_at(day0 * SECONDS_PER_DAY + SECONDS_PER_DAY - 1);
+        commit_at((day0 + 2) * SECONDS_PER_DAY);
+
+        assert_eq!(client.commits_on_day(&(day0 - 1)), 1);
+        assert_eq!(client.commits_on_day(&day0), 2);
+        assert_eq!(client.commits_on_day(&(day0 + 1)), 0);
+        assert_eq!(client.commits_on_day(&(day0 + 2)), 1);
+
+        assert_eq!(client.commits_last_n_days(&4), Vec::from_array(&env, [1u64, 2, 0, 1]));
+        assert_eq!(client.commits_last_n_days(&1), Vec::from_array(&env, [1u64]));
+        assert_eq!(client.commits_last_n_days(&0), Vec::new(&env));
+
+        // Capped at max_stats_days, still ending today
+        let capped = client.commits_last_n_days(&1000);
+        assert_eq!(capped.len(), MAX_STATS_DAYS);
+        assert_eq!(capped.last(), Some(1));
+        assert_eq!(capped.iter().sum::<u64>(), 4);
+
+        // A new day starts at zero
+        env.ledger().with_mut(|li| li.timestamp = (day0 + 3) * SECONDS_PER_DAY);
+        assert_eq!(client.commits_last_n_days(&2), Vec::from_array(&env, [1u64, 0]));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_sample_day_is_seeded_and_distinct() {
+        use sha2::{Digest, Sha256};
+
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        let day = 1700000000 / SECONDS_PER_DAY;
+        let hashes: std::vec::Vec<BytesN<32>> = (100..110u8).map(|n| test_hash(&env, n)).collect();
+        for route_hash in &hashes {
+            client.commit_route(&committer, route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        }
+        let seed = test_hash(&env, 120);
+
+        // Recompute the documented draw off-chain
+        let mut drawn: std::vec::Vec<u64> = std::vec::Vec::new();
+        for i in 0..4u32 {
+            let mut hasher = Sha256::new();
+            hasher.update(seed.to_array());
+            hasher.update(i.to_be_bytes());
+            let digest: [u8; 32] = hasher.finalize().into();
+            let mut position = u64::from_be_bytes(digest[..8].try_into().unwrap()) % 10;
+            while drawn.contains(&position) {
+                position = (position + 1) % 10;
+            }
+            drawn.push(position);
+        }
+        let expected: std::vec::Vec<BytesN<32>> = drawn.iter().map(|&p| hashes[p as usize].clone()).collect();
+        let sample: std::vec::Vec<BytesN<32>> = client.sample_day(&day, &seed, &4).iter().collect();
+        assert_eq!(sample, expected);
+        assert_eq!(client.sample_day(&day, &seed, &4), client.sample_day(&day, &seed, &4));
+
+        // Draws never repeat a hash, even when they collide
+        let full: std::vec::Vec<BytesN<32>> = client.sample_day(&day, &seed, &9).iter().collect();
+        assert_eq!(full.len(), 9);
+        assert!(full.iter().all(|h| full.iter().filter(|other| *other == h).count() == 1));
+        assert_eq!(full[..4], sample[..]);
+
+        // Another seed draws another sample
+        assert_ne!(client.sample_day(&day, &test_hash(&env, 121), &4), client.sample_day(&day, &seed, &4));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_sample_day_returns_small_and_empty_days_whole() {
+        let env = setup_env();
+        env.budget().reset_unlimited();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        let day = 1700000000 / SECONDS_PER_DAY;
+        let seed = test_hash(&env, 120);
+
+        assert_eq!(client.sample_day(&day, &seed, &5), Vec::new(&env));
+
+        let mut hashes = Vec::new(&env);
+        for n in 0..40u32 {
+            let mut bytes = [0u8; 32];
+            bytes[..4].copy_from_slice(&(n + 1).to_be_bytes());
+            hashes.push_back(BytesN::from_array(&env, &bytes));
+        }
+        for route_hash in hashes.iter().take(3) {
+            client.commit_route(&committer, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        }
+        // A count covering the day returns it in storage order
+        assert_eq!(client.sample_day(&day, &seed, &50), hashes.slice(0..3));
+        assert_eq!(client.sample_day(&day, &seed, &0), Vec::new(&env));
+        assert_eq!(client.sample_day(&(day + 1), &seed, &5), Vec::new(&env));
+
+        // The sample is capped at max_sample_size, across index pages
+        for route_hash in hashes.iter().skip(3) {
+            client.commit_route(&committer, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        }
+        let capped = client.sample_day(&day, &seed, &u32::MAX);
+        assert_eq!(capped.len(), MAX_SAMPLE_SIZE);
+        assert!(capped.iter().all(|h| hashes.contains(&h)));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_committer_stats_track_first_and_last_commit() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let (alice, bob) = (Address::generate(&env), Address::generate(&env));
+        let stats = |count, first_ts, last_ts| Some(CommitterStats { count, first_ts, last_ts });
+
+        assert_eq!(client.get_committer_stats(&alice), None);
+
+        client.commit_route(&alice, &test_hash(&env, 1), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        assert_eq!(client.get_committer_stats(&alice), stats(1, 1700000000, 1700000000));
+
+        let mut batch = Vec::new(&env);
+        for seed in 4u8..=6 {
+            batch.push_back(entry(&env, seed, 0));
+        }
+        env.ledger().with_mut(|li| li.timestamp = 1700000500);
+        client.commit_routes(&alice, &batch);
+        env.ledger().with_mut(|li| li.timestamp = 1700090000);
+        client.commit_route(&alice, &test_hash(&env, 7), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        client.commit_route(&bob, &test_hash(&env, 8), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+
+        assert_eq!(client.get_committer_stats(&alice), stats(5, 1700000000, 1700090000));
+        assert_eq!(client.get_committer_stats(&bob), stats(1, 1700090000, 1700090000));
+        assert_eq!(client.get_committer_stats(&alice).unwrap().count, client.get_commit_count_by_committer(&alice));
+
+        // Failed commits leave the stats alone
+        env.ledger().with_mut(|li| li.timestamp = 1700095000);
+        let result = client.try_commit_route(&alice, &test_hash(&env, 7), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        assert_eq!(result, Err(Ok(RegistryError::DuplicateCommitment)));
+        assert_eq!(client.get_committer_stats(&alice), stats(5, 1700000000, 1700090000));
+        invariants::assert_registry_invariants(&env, &client);
+
+        // Checked count
+        env.as_contract(&contract_id, || {
+            let oversized = CommitterStats { count: u64::MAX, first_ts: 1700000000, last_ts: 1700090000 };
+            env.storage().persistent().set(&DataKey::CommitterStats(bob.clone()), &oversized);
+        });
+        let result = client.try_commit_route(&bob, &test_hash(&env, 9), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        assert_eq!(result, Err(Ok(RegistryError::CommitCountOverflow)));
+    }
+
+    #[test]
+    fn test_first_existing_stops_at_first_hit() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        client.commit_route(&committer, &test_hash(&env, 5), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        client.commit_route(&committer, &test_hash(&env, 9), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+
+        let candidates = |seeds: &[u8]| {
+            let mut hashes = Vec::new(&env);
+            for seed in seeds {
+                hashes.push_back(test_hash(&env, *seed));
+            }
+            hashes
+        };
+        assert_eq!(client.first_existing(&candidates(&[1, 9, 5])), Some(test_hash(&env, 9)));
+        assert!(client.exists_any(&candidates(&[1, 9, 5])));
+        assert_eq!(client.first_existing(&candidates(&[1, 4, 6])), None);
+        assert!(!client.exists_any(&candidates(&[1, 4, 6])));
+        assert!(!client.exists_any(&Vec::new(&env)));
+
+        // Existence-only records are not commitments
+        client.commit_existence(&committer, &test_hash(&env, 7));
+        assert_eq!(client.first_existing(&candidates(&[7, 5])), Some(test_hash(&env, 5)));
+
+        // A hit up front skips the remaining reads
+        let tail: std::vec::Vec<u8> = (20u8..20 + MAX_READ_BATCH_SIZE as u8 - 1).collect();
+        env.budget().reset_default();
+        client.exists_any(&candidates(&[[5u8].as_slice(), &tail].concat()));
+        let early = env.budget().cpu_instruction_cost();
+        env.budget().reset_default();
+        client.exists_any(&candidates(&[tail.as_slice(), &[5u8]].concat()));
+        let late = env.budget().cpu_instruction_cost();
+        assert!(early < late, "early={} late={}", early, late);
+
+        let oversized = candidates(&(0..=MAX_READ_BATCH_SIZE as u8).collect::<std::vec::Vec<_>>());
+        assert_eq!(client.try_exists_any(&oversized), Err(Ok(RegistryError::TooManyEntries)));
+        assert_eq!(client.try_first_existing(&oversized), Err(Ok(RegistryError::TooManyEntries)));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_latest_commit_by_committer_follows_each_committer() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let (alice, bob) = (Address::generate(&env), Address::generate(&env));
+        assert_eq!(client.latest_commit_by_committer(&alice), None);
+
+        client.commit_route(&alice, &test_hash(&env, 1), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        env.ledger().with_mut(|li| li.timestamp = 1700000100);
+        client.commit_route(&bob, &test_hash(&env, 4), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        assert_eq!(client.latest_commit_by_committer(&alice), Some((test_hash(&env, 1), 1700000000)));
+        assert_eq!(client.latest_commit_by_committer(&bob), Some((test_hash(&env, 4), 1700000100)));
+
+        // Batches: the last stored entry wins, skipped entries do not count
+        env.ledger().with_mut(|li| li.timestamp = 1700000200);
+        let mut batch = Vec::new(&env);
+        batch.push_back(entry(&env, 5, 0));
+        batch.push_back(entry(&env, 6, 0));
+        batch.push_back(entry(&env, 4, 0));
+        client.commit_routes_partial(&alice, &batch);
+        assert_eq!(client.latest_commit_by_committer(&alice), Some((test_hash(&env, 6), 1700000200)));
+        assert_eq!(client.latest_commit_by_committer(&bob), Some((test_hash(&env, 4), 1700000100)));
+
+        env.ledger().with_mut(|li| li.timestamp = 1700000300);
+        let mut batch = Vec::new(&env);
+        batch.push_back(entry(&env, 7, 0));
+        batch.push_back(entry(&env, 8, 0));
+        client.commit_routes(&bob, &batch);
+        assert_eq!(client.latest_commit_by_committer(&bob), Some((test_hash(&env, 8), 1700000300)));
+        assert_eq!(client.latest_commit_by_committer(&alice), Some((test_hash(&env, 6), 1700000200)));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_latest_commit_by_committer_reports_backdated_timestamp() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        let (rules, solver) = (test_hash(&env, 2), test_hash(&env, 3));
+
+        // Upgrade an old claim after a newer plain commit
+        client.commit_existence(&committer, &test_hash(&env, 10));
+        env.ledger().with_mut(|li| li.timestamp = 1700000100);
+        client.commit_route(&committer, &test_hash(&env, 11), &rules, &solver, &0u64);
+        env.ledger().with_mut(|li| li.timestamp = 1700000200);
+        client.upgrade_existence(&committer, &test_hash(&env, 10), &rules, &solver, &0u64);
+        assert_eq!(client.latest_commit_by_committer(&committer), Some((test_hash(&env, 10), 1700000000)));
+
+        // Likewise a reveal of a blinded commit older than the latest plain one
+        let salt = test_hash(&env, 12);
+        let blinded_hash = blinded_digest(&env, &test_hash(&env, 13), &core(&env), &salt);
+        env.ledger().with_mut(|li| li.timestamp = 1700000300);
+        client.commit_blinded(&committer, &blinded_hash, &0u64);
+        env.ledger().with_mut(|li| li.timestamp = 1700000400);
+        client.commit_route(&committer, &test_hash(&env, 14), &rules, &solver, &0u64);
+        env.ledger().with_mut(|li| li.timestamp = 1700000500);
+        client.reveal(&blinded_hash, &test_hash(&env, 13), &rules, &solver, &salt);
+        assert_eq!(client.latest_commit_by_committer(&committer), Some((test_hash(&env, 13), 1700000300)));
+        assert_eq!(client.get_commit(&test_hash(&env, 13)).timestamp, 1700000300);
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_committer_list_dedups_and_pages() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        assert_eq!(client.committer_count(), 0);
+        assert_eq!(client.get_committers(&0, &10), Vec::new(&env));
+
+        // The test host's per-call overhead grows with the whole test
+        // ledger; this test is about listing, not cost
+        env.budget().reset_unlimited();
+
+        // 150 committers; every third commits twice more, once through a batch
+        use sha2::{Digest as _, Sha256};
+        let committers: std::vec::Vec<Address> = (0..150).map(|_| Address::generate(&env)).collect();
+        for (i, committer) in committers.iter().enumerate() {
+            let route_hash = |n: u32| BytesN::from_array(&env, &Sha256::digest((i as u32 * 3 + n).to_be_bytes()).into());
+            client.commit_route(committer, &route_hash(0), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+            if i % 3 == 0 {
+                client.commit_route(committer, &route_hash(1), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+                let batch = Vec::from_array(&env, [CommitEntry { route_hash: route_hash(2), ..entry(&env, 1, 0) }]);
+                client.commit_routes(committer, &batch);
+            }
+        }
+        assert_eq!(client.committer_count(), 150);
+        assert_eq!(client.total_commits(), 250);
+
+        let mut listed = std::vec::Vec::new();
+        loop {
+            let page = client.get_committers(&(listed.len() as u32), &40);
+            if page.is_empty() {
+                break;
+            }
+            assert!(page.len() <= 40);
+            listed.extend(page);
+        }
+        assert_eq!(listed, committers);
+
+        // Pages are capped like the other listings
+        assert_eq!(client.get_committers(&0, &u32::MAX).len(), MAX_RANGE_COMMITS_LISTED);
+        assert_eq!(client.get_committers(&149, &10), Vec::from_array(&env, [committers[149].clone()]));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_solver_index_splits_commitments_by_solver_version() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        let (s1, s2) = (test_hash(&env, 173), test_hash(&env, 174));
+
+        // Same rules hash throughout, so only the solver index separates them
+        client.commit_route(&committer, &test_hash(&env, 1), &test_hash(&env, 2), &s1, &0u64);
+        client.commit_route(&committer, &test_hash(&env, 4), &test_hash(&env, 2), &s2, &0u64);
+        let mut batch = Vec::new(&env);
+        batch.push_back(CommitEntry { solver_version_hash: s1.clone(), ..entry(&env, 5, 0) });
+        batch.push_back(CommitEntry { solver_version_hash: s2.clone(), ..entry(&env, 6, 0) });
+        batch.push_back(CommitEntry { solver_version_hash: s1.clone(), ..entry(&env, 7, 0) });
+        client.commit_routes(&committer, &batch);
+
+        assert_eq!(client.count_by_rules(&test_hash(&env, 2)), 5);
+        assert_eq!(client.count_by_solver(&s1), 3);
+        assert_eq!(client.count_by_solver(&s2), 2);
+        assert_eq!(
+            client.get_commits_by_solver(&s1, &0, &100),
+            Vec::from_array(&env, [test_hash(&env, 1), test_hash(&env, 5), test_hash(&env, 7)])
+        );
+        assert_eq!(client.get_commits_by_solver(&s2, &1, &100), Vec::from_array(&env, [test_hash(&env, 6)]));
+        assert_eq!(client.get_commits_by_solver(&s1, &3, &100), Vec::new(&env));
+        assert_eq!(client.count_by_solver(&test_hash(&env, 175)), 0);
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_version_indexes_write_cost() {
+        let commit_cost = measure_cpu(|env, client, committer| {
+            client.commit_route(committer, &test_hash(env, 176), &test_hash(env, 2), &test_hash(env, 3), &0u64);
+        });
+
+        // The two index appends record_commitment makes, on their own
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let route_hash = test_hash(&env, 176);
+        env.budget().reset_default();
+        env.as_contract(&contract_id, || {
+            let (rules_hash, solver_hash) = (test_hash(&env, 2), test_hash(&env, 3));
+            let (rules_key, solver_key) = (DataKey::RulesCount(rules_hash.clone()), DataKey::SolverCount(solver_hash.clone()));
+            let seq = RouteIntegrityRegistry::next_index_seq(&env, &rules_key).unwrap();
+            RouteIntegrityRegistry::append_index(&env, rules_key, seq, &route_hash, |page| {
+                DataKey::RulesPage(rules_hash.clone(), page)
+            });
+            let seq = RouteIntegrityRegistry::next_index_seq(&env, &solver_key).unwrap();
+            RouteIntegrityRegistry::append_index(&env, solver_key, seq, &route_hash, |page| {
+                DataKey::SolverPage(solver_hash.clone(), page)
+            });
+        });
+        let index_cost = env.budget().cpu_instruction_cost();
+
+        // Both indexes together (two counters, two pages) stay well under a
+        // single commit; fail if that grows past a third
+        assert!(index_cost * 3 <= commit_cost, "commit={} index={}", commit_cost, index_cost);
+    }
+
+    #[test]
+    fn test_committer_sequences_are_contiguous() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+
+        let (alice, bob) = (Address::generate(&env), Address::generate(&env));
+        let mut expected: [std::vec::Vec<BytesN<32>>; 2] = Default::default();
+        for (seed, who) in (47u8..57).zip([0, 1, 0, 0, 1, 0, 1, 1, 1, 0]) {
+            let committer = [&alice, &bob][who];
+            let route_hash = test_hash(&env, seed);
+            let seq = client.commit_route(committer, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+            expected[who].push(route_hash.clone());
+            assert_eq!(seq, expected[who].len() as u64);
+            assert_eq!(client.get_commit(&route_hash).committer_seq, seq);
+
+            let (_, _, data) = env.events().all().last().unwrap();
+            let payload: (BytesN<32>, BytesN<32>, Address, u64, u64, u64, u32, u64, u64) = data.into_val(&env);
+            assert_eq!(payload.7, seq);
+        }
+
+        // Each committer walks 1..=n back to its own routes
+        for (who, committer) in [&alice, &bob].into_iter().enumerate() {
+            let count = client.get_commit_count_by_committer(committer);
+            assert_eq!(count, 5);
+            for seq in 1..=count {
+                assert_eq!(client.get_commit_by_committer_seq(committer, &seq), expected[who][seq as usize - 1]);
+            }
+            let past_end = client.try_get_commit_by_committer_seq(committer, &(count + 1));
+            assert_eq!(past_end, Err(Ok(RegistryError::NotFound)));
+            assert_eq!(client.try_get_commit_by_committer_seq(committer, &0), Err(Ok(RegistryError::NotFound)));
+        }
+
+        // Failed commits do not consume a sequence number
+        let duplicate = client.try_commit_route(&alice, &test_hash(&env, 47), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        assert_eq!(duplicate, Err(Ok(RegistryError::DuplicateCommitment)));
+        assert_eq!(client.commit_route(&alice, &test_hash(&env, 57), &test_hash(&env, 2), &test_hash(&env, 3), &0u64), 6);
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_revoke_commit_leaves_record_and_hash_check_intact() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        let (rules_hash, solver_hash) = (test_hash(&env, 2), test_hash(&env, 3));
+        let route_hash = test_hash(&env, 58);
+        client.commit_route(&committer, &route_hash, &rules_hash, &solver_hash, &0u64);
+        let before = client.get_commit(&route_hash);
+        assert!(client.verify_commit_active(&route_hash, &rules_hash, &solver_hash));
+        assert!(!client.is_revoked(&route_hash));
+
+        env.ledger().with_mut(|li| li.timestamp += 30);
+        client.revoke_commit(&route_hash, &committer, &test_hash(&env, 59));
+
+        let (_, topics, data) = env.events().all().last().unwrap();
+        assert_eq!(topics, (Symbol::new(&env, "withdrawn"), route_hash.clone()).into_val(&env));
+        let payload: (Address, BytesN<32>, u64) = data.into_val(&env);
+        assert_eq!(payload, (committer.clone(), test_hash(&env, 59), 1700000030));
+
+        assert!(client.is_revoked(&route_hash));
+        let expected = RevocationRecord { reason_hash: test_hash(&env, 59), timestamp: 1700000030 };
+        assert_eq!(client.get_revocation(&route_hash), Some(expected));
+        assert_eq!(client.get_commit(&route_hash), before);
+        assert!(client.verify_commit(&route_hash, &rules_hash, &solver_hash));
+        assert!(!client.verify_commit_active(&route_hash, &rules_hash, &solver_hash));
+
+        let again = client.try_revoke_commit(&route_hash, &committer, &test_hash(&env, 60));
+        assert_eq!(again, Err(Ok(RegistryError::AlreadyRevoked)));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_revoked_status_outranks_every_other() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        let user = Address::generate(&env);
+        let (rules_hash, solver_hash) = (test_hash(&env, 2), test_hash(&env, 3));
+
+        let (live, scheduled) = (test_hash(&env, 61), test_hash(&env, 62));
+        client.commit_route(&committer, &live, &rules_hash, &solver_hash, &1700000100u64);
+        client.commit_route_scheduled(&committer, &scheduled, &rules_hash, &solver_hash, &1700000050, &1700000100);
+        client.revoke_commit(&live, &committer, &test_hash(&env, 63));
+        client.revoke_commit(&scheduled, &committer, &test_hash(&env, 63));
+
+        // Revoked before, inside and after the validity window
+        for now in [1700000000u64, 1700000050, 1700000100] {
+            env.ledger().with_mut(|li| li.timestamp = now);
+            for route_hash in [&live, &scheduled] {
+                assert_eq!(client.get_status(route_hash), CommitmentStatus::Revoked, "now={}", now);
+                assert!(!client.is_active(route_hash), "now={}", now);
+                assert_eq!(
+                    client.try_mark_consumed(&user, route_hash, &test_hash(&env, 64)),
+                    Err(Ok(RegistryError::CommitmentNotActive))
+                );
+                assert_eq!(
+                    client.try_ack_route(route_hash, &user),
+                    Err(Ok(RegistryError::CommitmentNotActive))
+                );
+            }
+        }
+        assert!(client.get_consumptions(&live).is_empty());
+        assert_eq!(client.get_ack(&live, &user), None);
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_verify_commit_active_needs_match_and_live_quote() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        let (rules_hash, solver_hash, stale) = (test_hash(&env, 2), test_hash(&env, 3), test_hash(&env, 4));
+        let route_hash = test_hash(&env, 65);
+        client.commit_route(&committer, &route_hash, &rules_hash, &solver_hash, &1700000100u64);
+
+        // (hashes match, quote live) → verify_commit / verify_commit_active
+        let cases = [
+            (1700000099u64, &rules_hash, &solver_hash, true, true),
+            (1700000099, &stale, &solver_hash, false, false),
+            (1700000099, &rules_hash, &stale, false, false),
+            (1700000100, &rules_hash, &solver_hash, true, false),
+            (1700000100, &stale, &solver_hash, false, false),
+        ];
+        for (now, rules, solver, historical, active) in cases {
+            env.ledger().with_mut(|li| li.timestamp = now);
+            assert_eq!(client.verify_commit(&route_hash, rules, solver), historical, "now={}", now);
+            assert_eq!(client.verify_commit_active(&route_hash, rules, solver), active, "now={}", now);
+        }
+
+        assert!(!client.verify_commit_active(&test_hash(&env, 66), &rules_hash, &solver_hash));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_verify_commit_detailed_reports_first_failure() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        let (rules_hash, solver_hash, stale) = (test_hash(&env, 2), test_hash(&env, 3), test_hash(&env, 4));
+        let (expiring, scheduled, revoked) = (test_hash(&env, 67), test_hash(&env, 68), test_hash(&env, 69));
+        client.commit_route(&committer, &expiring, &rules_hash, &solver_hash, &1700000100u64);
+        client.commit_route_scheduled(&committer, &scheduled, &rules_hash, &solver_hash, &1700000050, &1700000100);
+        client.commit_route(&committer, &revoked, &rules_hash, &solver_hash, &1700000100u64);
+        client.revoke_commit(&revoked, &committer, &test_hash(&env, 70));
+        client.commit_existence(&committer, &test_hash(&env, 71));
+
+        let cases = [
+            (1700000000u64, &expiring, &rules_hash, &solver_hash, Ok(())),
+            (1700000000, &test_hash(&env, 72), &rules_hash, &solver_hash, Err(VerifyFailure::NotFound)),
+            (1700000000, &test_hash(&env, 71), &rules_hash, &solver_hash, Err(VerifyFailure::NotFound)),
+            (1700000000, &expiring, &stale, &stale, Err(VerifyFailure::RulesMismatch)),
+            (1700000000, &expiring, &rules_hash, &stale, Err(VerifyFailure::SolverMismatch)),
+            (1700000000, &scheduled, &rules_hash, &solver_hash, Err(VerifyFailure::Scheduled)),
+            (1700000050, &scheduled, &rules_hash, &solver_hash, Ok(())),
+            (1700000000, &revoked, &rules_hash, &solver_hash, Err(VerifyFailure::Revoked)),
+            // Expired outranks Scheduled, mismatches and revocation outrank Expired
+            (1700000100, &expiring, &rules_hash, &solver_hash, Err(VerifyFailure::Expired)),
+            (1700000100, &scheduled, &rules_hash, &solver_hash, Err(VerifyFailure::Expired)),
+            (1700000100, &revoked, &rules_hash, &solver_hash, Err(VerifyFailure::Revoked)),
+            (1700000100, &expiring, &rules_hash, &stale, Err(VerifyFailure::SolverMismatch)),
+        ];
+        for (now, route_hash, rules, solver, expected) in cases {
+            env.ledger().with_mut(|li| li.timestamp = now);
+            let result = client.try_verify_commit_detailed(route_hash, rules, solver);
+            assert_eq!(result.map(|ok| ok.unwrap()).map_err(|err| err.unwrap()), expected, "now={}", now);
+            assert_eq!(expected.is_ok(), client.verify_commit_active(route_hash, rules, solver));
+        }
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_revoke_commit_rejects_missing_and_foreign_commitments() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        let reason = test_hash(&env, 61);
+
+        assert_eq!(client.try_revoke_commit(&test_hash(&env, 62), &committer, &reason), Err(Ok(RegistryError::NotFound)));
+
+        let route_hash = test_hash(&env, 63);
+        client.commit_route(&committer, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        let stranger = Address::generate(&env);
+        assert_eq!(client.try_revoke_commit(&route_hash, &stranger, &reason), Err(Ok(RegistryError::Unauthorized)));
+
+        let zero = BytesN::from_array(&env, &[0u8; 32]);
+        assert_eq!(client.try_revoke_commit(&route_hash, &committer, &zero), Err(Ok(RegistryError::EmptyReasonHash)));
+        assert!(!client.is_revoked(&route_hash));
+
+        // Expired or scheduled commitments fail the active check even unrevoked
+        client.commit_route_scheduled(&committer, &test_hash(&env, 64), &test_hash(&env, 2), &test_hash(&env, 3), &1700000600, &0u64);
+        assert!(!client.verify_commit_active(&test_hash(&env, 64), &test_hash(&env, 2), &test_hash(&env, 3)));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_cancel_quote_stops_active_checks_only() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        let (rules_hash, solver_hash) = (test_hash(&env, 2), test_hash(&env, 3));
+        let route_hash = test_hash(&env, 84);
+        client.commit_route(&committer, &route_hash, &rules_hash, &solver_hash, &1700000100u64);
+        let before = client.get_commit(&route_hash);
+
+        assert_eq!(client.try_cancel_quote(&committer, &test_hash(&env, 85)), Err(Ok(RegistryError::NotFound)));
+        let stranger = Address::generate(&env);
+        assert_eq!(client.try_cancel_quote(&stranger, &route_hash), Err(Ok(RegistryError::Unauthorized)));
+        assert!(!client.is_cancelled(&route_hash));
+
+        env.ledger().with_mut(|li| li.timestamp += 30);
+        client.cancel_quote(&committer, &route_hash);
+
+        let (_, topics, data) = env.events().all().last().unwrap();
+        assert_eq!(topics, (Symbol::new(&env, "cancelled"), route_hash.clone()).into_val(&env));
+        let payload: (Address, u64) = data.into_val(&env);
+        assert_eq!(payload, (committer.clone(), 1700000030));
+
+        assert!(client.is_cancelled(&route_hash));
+        assert_eq!(client.get_cancellation(&route_hash), Some(1700000030));
+        assert_eq!(client.get_status(&route_hash), CommitmentStatus::Cancelled);
+        assert_eq!(client.get_commit(&route_hash), before);
+        assert!(client.verify_commit(&route_hash, &rules_hash, &solver_hash));
+        assert!(!client.is_active(&route_hash));
+        assert!(!client.verify_commit_active(&route_hash, &rules_hash, &solver_hash));
+        assert_eq!(
+            client.try_verify_commit_detailed(&route_hash, &rules_hash, &solver_hash),
+            Err(Ok(VerifyFailure::Cancelled))
+        );
+        assert!(!client.is_revoked(&route_hash));
+
+        assert_eq!(client.try_cancel_quote(&committer, &route_hash), Err(Ok(RegistryError::AlreadyCancelled)));
+        replay::ReplayModel::from_events(&env, &contract_id).assert_matches(&env, &client);
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_cancel_quote_ordering_against_expiry_and_revocation() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        let (rules_hash, solver_hash, reason) = (test_hash(&env, 2), test_hash(&env, 3), test_hash(&env, 86));
+        let [cancel_first, expire_first, revoke_first, revoke_after, scheduled] =
+            [87u8, 88, 89, 90, 91].map(|n| test_hash(&env, n));
+        for route_hash in [&cancel_first, &expire_first, &revoke_first, &revoke_after] {
+            client.commit_route(&committer, route_hash, &rules_hash, &solver_hash, &1700000100u64);
+        }
+        client.commit_route_scheduled(&committer, &scheduled, &rules_hash, &solver_hash, &1700000050, &1700000100);
+
+        // Cancel, then expire: the cancellation stands
+        client.cancel_quote(&committer, &cancel_first);
+        // A scheduled quote can be cancelled ahead of its window
+        client.cancel_quote(&committer, &scheduled);
+        // Revoke, then cancel: already inactive
+        client.revoke_commit(&revoke_first, &committer, &reason);
+        assert_eq!(client.try_cancel_quote(&committer, &revoke_first), Err(Ok(RegistryError::AlreadyInactive)));
+        // Cancel, then revoke: the revocation outranks the cancellation
+        client.cancel_quote(&committer, &revoke_after);
+        client.revoke_commit(&revoke_after, &committer, &reason);
+        assert_eq!(client.get_status(&revoke_after), CommitmentStatus::Revoked);
+
+        env.ledger().with_mut(|li| li.timestamp = 1700000100);
+        assert_eq!(client.get_status(&cancel_first), CommitmentStatus::Cancelled);
+        assert_eq!(client.get_status(&scheduled), CommitmentStatus::Cancelled);
+        assert_eq!(client.get_status(&revoke_after), CommitmentStatus::Revoked);
+        // Expire, then cancel: already inactive
+        assert_eq!(client.get_status(&expire_first), CommitmentStatus::Expired);
+        assert_eq!(client.try_cancel_quote(&committer, &expire_first), Err(Ok(RegistryError::AlreadyInactive)));
+        assert!(!client.is_cancelled(&expire_first));
+        assert!(!client.is_cancelled(&revoke_first));
+
+        // A cancelled quote cannot be acknowledged or consumed
+        env.ledger().with_mut(|li| li.timestamp = 1700000060);
+        let user = Address::generate(&env);
+        assert_eq!(client.try_ack_route(&cancel_first, &user), Err(Ok(RegistryError::CommitmentNotActive)));
+        assert_eq!(
+            client.try_mark_consumed(&user, &scheduled, &test_hash(&env, 92)),
+            Err(Ok(RegistryError::CommitmentNotActive))
+        );
+        replay::ReplayModel::from_events(&env, &contract_id).assert_matches(&env, &client);
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_supersede_commit_links_both_directions() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        let (first, second, third) = (test_hash(&env, 65), test_hash(&env, 66), test_hash(&env, 67));
+
+        client.commit_route(&committer, &first, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        let seq = client.supersede_commit(&first, &second, &test_hash(&env, 4), &test_hash(&env, 5), &0u64, &committer);
+        assert_eq!(seq, 2);
+        assert_eq!(client.get_commit(&second).rules_hash, test_hash(&env, 4));
+        assert_eq!(client.get_successor(&first), Some(second.clone()));
+        assert_eq!(client.get_predecessor(&second), Some(first.clone()));
+        assert_eq!(client.get_predecessor(&first), None);
+
+        // The chain stays linear: each commitment is superseded at most once
+        let again = client.try_supersede_commit(&first, &third, &test_hash(&env, 2), &test_hash(&env, 3), &0u64, &committer);
+        assert_eq!(again, Err(Ok(RegistryError::AlreadySuperseded)));
+        assert!(!client.has_commit(&third));
+
+        client.supersede_commit(&second, &third, &test_hash(&env, 2), &test_hash(&env, 3), &0u64, &committer);
+        assert_eq!(client.get_successor(&second), Some(third.clone()));
+        assert_eq!(client.get_successor(&third), None);
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_supersede_commit_rejects_missing_and_foreign_commitments() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        let (old, new) = (test_hash(&env, 68), test_hash(&env, 69));
+
+        let missing = client.try_supersede_commit(&old, &new, &test_hash(&env, 2), &test_hash(&env, 3), &0u64, &committer);
+        assert_eq!(missing, Err(Ok(RegistryError::NotFound)));
+
+        client.commit_route(&committer, &old, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        let stranger = Address::generate(&env);
+        let foreign = client.try_supersede_commit(&old, &new, &test_hash(&env, 2), &test_hash(&env, 3), &0u64, &stranger);
+        assert_eq!(foreign, Err(Ok(RegistryError::Unauthorized)));
+
+        // A failed commit of the new route leaves no link behind
+        let duplicate = client.try_supersede_commit(&old, &old, &test_hash(&env, 2), &test_hash(&env, 3), &0u64, &committer);
+        assert_eq!(duplicate, Err(Ok(RegistryError::DuplicateCommitment)));
+        assert_eq!(client.get_successor(&old), None);
+        assert!(!client.has_commit(&new));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_commit_child_routes_links_family_both_ways() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        let parent = test_hash(&env, 150);
+        client.commit_route(&committer, &parent, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+
+        let first = Vec::from_array(&env, [entry(&env, 151, 0), entry(&env, 152, 1700003600)]);
+        client.commit_child_routes(&committer, &parent, &first);
+        let (_, topics, data) = env.events().all().last().unwrap();
+        assert_eq!(topics, (symbol_short!("split"), parent.clone()).into_val(&env));
+        let payload: (Address, Vec<BytesN<32>>) = data.into_val(&env);
+        assert_eq!(payload, (committer.clone(), Vec::from_array(&env, [test_hash(&env, 151), test_hash(&env, 152)])));
+
+        // Later splits add to the family, and children split again
+        client.commit_child_routes(&committer, &parent, &Vec::from_array(&env, [entry(&env, 153, 0)]));
+        client.commit_child_routes(&committer, &test_hash(&env, 151), &Vec::from_array(&env, [entry(&env, 154, 0)]));
+
+        let children = Vec::from_array(&env, [test_hash(&env, 151), test_hash(&env, 152), test_hash(&env, 153)]);
+        assert_eq!(client.get_children(&parent), children);
+        for child in children.iter() {
+            assert_eq!(client.get_parent(&child), Some(parent.clone()));
+            assert_eq!(client.get_commit(&child).committer, committer);
+        }
+        assert_eq!(client.get_commit(&test_hash(&env, 152)).expiry, 1700003600);
+        assert_eq!(client.get_children(&test_hash(&env, 151)), Vec::from_array(&env, [test_hash(&env, 154)]));
+        assert_eq!(client.get_parent(&test_hash(&env, 154)), Some(test_hash(&env, 151)));
+        assert_eq!(client.get_parent(&parent), None);
+        assert_eq!(client.get_children(&test_hash(&env, 154)), Vec::new(&env));
+        replay::ReplayModel::from_events(&env, &contract_id).assert_matches(&env, &client);
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_commit_child_routes_enforces_owner_and_family_limits() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        let parent = test_hash(&env, 160);
+        client.commit_route(&committer, &parent, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        let one = |seed: u8| Vec::from_array(&env, [entry(&env, seed, 0)]);
+
+        let missing = client.try_commit_child_routes(&committer, &test_hash(&env, 161), &one(162));
+        assert_eq!(missing, Err(Ok(RegistryError::NotFound)));
+        let stranger = Address::generate(&env);
+        let foreign = client.try_commit_child_routes(&stranger, &parent, &one(162));
+        assert_eq!(foreign, Err(Ok(RegistryError::Unauthorized)));
+        let empty = client.try_commit_child_routes(&committer, &parent, &Vec::new(&env));
+        assert_eq!(empty, Err(Ok(RegistryError::EmptyBatch)));
+        // A failing child leaves no family behind
+        let duplicate = Vec::from_array(&env, [entry(&env, 162, 0), entry(&env, 160, 0)]);
+        let result = client.try_commit_child_routes(&committer, &parent, &duplicate);
+        assert_eq!(result, Err(Ok(RegistryError::DuplicateCommitment)));
+        assert!(!client.has_commit(&test_hash(&env, 162)));
+        assert_eq!(client.get_children(&parent), Vec::new(&env));
+
+        // Up to max_children_per_route children, across calls
+        let mut children = Vec::new(&env);
+        for seed in 0..MAX_CHILDREN_PER_ROUTE as u8 - 1 {
+            children.push_back(entry(&env, 170 + seed, 0));
+        }
+        let written = write_entries(&env, || client.commit_child_routes(&committer, &parent, &children));
+        assert!(written <= TX_MAX_WRITE_ENTRIES, "written={}", written);
+        client.commit_child_routes(&committer, &parent, &one(169));
+        let over = client.try_commit_child_routes(&committer, &parent, &one(168));
+        assert_eq!(over, Err(Ok(RegistryError::FamilyLimitExceeded)));
+        assert!(!client.has_commit(&test_hash(&env, 168)));
+        assert_eq!(client.get_children(&parent).len(), MAX_CHILDREN_PER_ROUTE);
+
+        // Down to max_route_depth ancestors
+        let mut leaf = test_hash(&env, 169);
+        for depth in 2..=MAX_ROUTE_DEPTH as u8 {
+            client.commit_child_routes(&committer, &leaf, &one(200 + depth));
+            leaf = test_hash(&env, 200 + depth);
+        }
+        let too_deep = client.try_commit_child_routes(&committer, &leaf, &one(220));
+        assert_eq!(too_deep, Err(Ok(RegistryError::FamilyLimitExceeded)));
+        assert!(!client.has_commit(&test_hash(&env, 220)));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_rotated_keys_share_one_canonical_history() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let (a, b) = (Address::generate(&env), Address::generate(&env));
+        let (rules, solver) = (test_hash(&env, 2), test_hash(&env, 3));
+
+        client.commit_route(&a, &test_hash(&env, 70), &rules, &solver, &0u64);
+        client.commit_route(&a, &test_hash(&env, 71), &rules, &solver, &0u64);
+        client.register_successor(&a, &b);
+        let authorized: std::vec::Vec<Address> = env.auths().into_iter().map(|(addr, _)| addr).collect();
+        assert_eq!(authorized, std::vec![a.clone()]);
+        client.commit_route(&b, &test_hash(&env, 72), &rules, &solver, &0u64);
+
+        assert_eq!(client.resolve_committer(&a), b);
+        assert_eq!(client.resolve_committer(&b), b);
+
+        // Either key finds the whole history, old key's commits first
+        let expected = Vec::from_array(&env, [test_hash(&env, 70), test_hash(&env, 71), test_hash(&env, 72)]);
+        assert_eq!(client.get_canonical_commits(&a), expected);
+        assert_eq!(client.get_canonical_commits(&b), expected);
+        assert_eq!(client.get_canonical_commit_count(&a), 3);
+
+        // Per-key indexes are unchanged
+        assert_eq!(client.get_commit_count_by_committer(&a), 2);
+        assert_eq!(client.get_commit(&test_hash(&env, 70)).committer, a);
+
+        assert!(client.verify_commit_canonical(&test_hash(&env, 70), &rules, &solver, &b));
+        assert!(client.verify_commit_canonical(&test_hash(&env, 72), &rules, &solver, &a));
+        let stranger = Address::generate(&env);
+        assert!(!client.verify_commit_canonical(&test_hash(&env, 72), &rules, &solver, &stranger));
+        assert!(!client.verify_commit_canonical(&test_hash(&env, 72), &solver, &rules, &b));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_register_successor_keeps_chains_linear_and_bounded() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let (a, b, c) = (Address::generate(&env), Address::generate(&env), Address::generate(&env));
+
+        assert_eq!(client.try_register_successor(&a, &a), Err(Ok(RegistryError::SelfReferentialAddress)));
+
+        client.register_successor(&a, &b);
+        assert_eq!(client.try_register_successor(&a, &c), Err(Ok(RegistryError::AlreadyRotated)));
+        assert_eq!(client.try_register_successor(&c, &b), Err(Ok(RegistryError::AlreadyRotated)));
+        // Rotating back into a retired key would close a loop
+        assert_eq!(client.try_register_successor(&b, &a), Err(Ok(RegistryError::AlreadyRotated)));
+
+        let mut newest = b;
+        for _ in 1..MAX_ROTATION_HOPS {
+            let next = Address::generate(&env);
+            client.register_successor(&newest, &next);
+            newest = next;
+        }
+        assert_eq!(client.resolve_committer(&a), newest);
+        let one_more = client.try_register_successor(&newest, &Address::generate(&env));
+        assert_eq!(one_more, Err(Ok(RegistryError::TooManyRotations)));
+    }
+
+    #[test]
+    fn test_ack_route_is_recorded_once() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        let user = Address::generate(&env);
+        let route_hash = test_hash(&env, 73);
+        client.commit_route(&committer, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &1700003600);
+
+        assert_eq!(client.get_ack(&route_hash, &user), None);
+        client.ack_route(&route_hash, &user);
+        let authorized: std::vec::Vec<Address> = env.auths().into_iter().map(|(addr, _)| addr).collect();
+        assert_eq!(authorized, std::vec![user.clone()]);
+        let event_count = env.events().all().len();
+
+        // Acking again keeps the first timestamp and emits nothing
+        env.ledger().with_mut(|li| li.timestamp += 60);
+        client.ack_route(&route_hash, &user);
+        assert_eq!(client.get_ack(&route_hash, &user), Some(1700000000));
+        assert_eq!(env.events().all().len(), event_count);
+        assert_eq!(client.get_ack(&route_hash, &Address::generate(&env)), None);
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_ack_route_rejects_missing_and_expired_quotes() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        let user = Address::generate(&env);
+
+        assert_eq!(client.try_ack_route(&test_hash(&env, 74), &user), Err(Ok(RegistryError::NotFound)));
+
+        let route_hash = test_hash(&env, 75);
+        client.commit_route(&committer, &route_hash, &test_hash(&env, 2), &test_hash(&env, 3), &1700003600);
+        env.ledger().with_mut(|li| li.timestamp = 1700003600);
+        assert_eq!(client.try_ack_route(&route_hash, &user), Err(Ok(RegistryError::QuoteExpired)));
+        assert_eq!(client.get_ack(&route_hash, &user), None);
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_verify_for_user_checks_binding() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        let (rules, solver) = (test_hash(&env, 2), test_hash(&env, 3));
+        let (alice, bob) = (test_hash(&env, 76), test_hash(&env, 77));
+
+        let bound = test_hash(&env, 78);
+        client.commit_route_bound(&committer, &bound, &rules, &solver, &0u64, &alice);
+        assert_eq!(client.get_user_binding(&bound), Some(alice.clone()));
+        assert!(client.verify_for_user(&bound, &rules, &solver, &alice));
+        assert!(!client.verify_for_user(&bound, &rules, &solver, &bob));
+        assert!(!client.verify_for_user(&bound, &solver, &rules, &alice));
+        // The plain hash check does not look at the binding
+        assert!(client.verify_commit(&bound, &rules, &solver));
+
+        // Unbound commitments, from either entry point, verify for anyone
+        let unbound = test_hash(&env, 79);
+        client.commit_route_bound(&committer, &unbound, &rules, &solver, &0u64, &zero_hash(&env));
+        assert_eq!(client.get_user_binding(&unbound), None);
+        let legacy = test_hash(&env, 80);
+        client.commit_route(&committer, &legacy, &rules, &solver, &0u64);
+        for route_hash in [&unbound, &legacy] {
+            assert!(client.verify_for_user(route_hash, &rules, &solver, &alice));
+            assert!(client.verify_for_user(route_hash, &rules, &solver, &bob));
+        }
+        assert!(!client.verify_for_user(&test_hash(&env, 81), &rules, &solver, &alice));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    /// Result of one authorization matrix cell
+    #[derive(Debug, PartialEq)]
+    enum Outcome {
+        Allowed,
+        Rejected(RegistryError),
+        /// The host refused the call before the contract ran (missing auth)
+        Trapped,
+    }
+
+    fn outcome<T, E, I>(result: Result<Result<T, E>, Result<RegistryError, I>>) -> Outcome {
+        match result {
+            Ok(_) => Outcome::Allowed,
+            Err(Ok(err)) => Outcome::Rejected(err),
+            Err(Err(_)) => Outcome::Trapped,
+        }
+    }
+
+    /// Fresh registry holding one commitment, one existence claim, one delegation,
+    /// one offline signing key and one corridor, all the committer's
+    struct AuthFixture {
+        env: Env,
+        client: RouteIntegrityRegistryClient<'static>,
+        committer: Address,
+        delegate: Address,
+        stranger: Address,
+        signer: BytesN<32>,
+    }
+
+    impl AuthFixture {
+        fn new() -> Self {
+            let env = setup_env();
+            let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+            let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+            let (committer, delegate, stranger) =
+                (Address::generate(&env), Address::generate(&env), Address::generate(&env));
+            client.commit_route(&committer, &test_hash(&env, 82), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+            client.commit_existence(&committer, &test_hash(&env, 83));
+            client.approve_delegate(&committer, &delegate);
+            let (signer, proof) = sign_registration(&env, &contract_id, &signing_key(), &committer);
+            client.register_signer(&committer, &signer, &proof);
+            client.register_corridor(&committer, &Symbol::new(&env, "USDC_PHP"), &test_hash(&env, 190), &test_hash(&env, 191));
+            AuthFixture { env, client, committer, delegate, stranger, signer }
+        }
+    }
+
+    type AuthCall = fn(&AuthFixture, &Address) -> Outcome;
+
+    /// Every entry point whose outcome depends on who the caller is
+    fn auth_matrix_calls() -> [(&'static str, AuthCall); 12] {
+        [
+            ("revoke_commit", |f, caller| {
+                outcome(f.client.try_revoke_commit(&test_hash(&f.env, 82), caller, &test_hash(&f.env, 84)))
+            }),
+            ("supersede_commit", |f, caller| {
+                let (rules, solver) = (test_hash(&f.env, 2), test_hash(&f.env, 3));
+                outcome(f.client.try_supersede_commit(&test_hash(&f.env, 82), &test_hash(&f.env, 85), &rules, &solver, &0u64, caller))
+            }),
+            ("upgrade_existence", |f, caller| {
+                let (rules, solver) = (test_hash(&f.env, 2), test_hash(&f.env, 3));
+                outcome(f.client.try_upgrade_existence(caller, &test_hash(&f.env, 83), &rules, &solver, &0u64))
+            }),
+            ("commit_as", |f, caller| {
+                let (rules, solver) = (test_hash(&f.env, 2), test_hash(&f.env, 3));
+                outcome(f.client.try_commit_as(&f.committer, caller, &test_hash(&f.env, 86), &rules, &solver, &0u64))
+            }),
+            ("revoke_delegate", |f, caller| outcome(f.client.try_revoke_delegate(caller, &f.delegate))),
+            ("endorse_commit", |f, caller| outcome(f.client.try_endorse_commit(&test_hash(&f.env, 82), caller))),
+            ("ack_route", |f, caller| outcome(f.client.try_ack_route(&test_hash(&f.env, 82), caller))),
+            ("mark_consumed", |f, caller| {
+                outcome(f.client.try_mark_consumed(caller, &test_hash(&f.env, 82), &test_hash(&f.env, 87)))
+            }),
+            ("cancel_quote", |f, caller| outcome(f.client.try_cancel_quote(caller, &test_hash(&f.env, 82)))),
+            ("deactivate_signer", |f, caller| outcome(f.client.try_deactivate_signer(caller, &f.signer))),
+            ("commit_child_routes", |f, caller| {
+                let children = Vec::from_array(&f.env, [entry(&f.env, 88, 0)]);
+                outcome(f.client.try_commit_child_routes(caller, &test_hash(&f.env, 82), &children))
+            }),
+            ("commit_as_scoped", |f, caller| {
+                let scope = CommitScope { namespace: Symbol::new(&f.env, "payouts"), corridor: Symbol::new(&f.env, "USDC_PHP") };
+                outcome(f.client.try_commit_as_scoped(&f.committer, caller, &scope, &entry(&f.env, 86, 0)))
+            }),
+        ]
+    }
+
+    #[test]
+    fn test_auth_matrix() {
+        use Outcome::{Allowed, Rejected};
+        use RegistryError::{NotFound, SelfReferentialAddress, Unauthorized};
+
+        // Expected outcome per caller role: (committer, delegate, stranger)
+        let expected = [
+            ("revoke_commit", [Allowed, Rejected(Unauthorized), Rejected(Unauthorized)]),
+            ("supersede_commit", [Allowed, Rejected(Unauthorized), Rejected(Unauthorized)]),
+            ("upgrade_existence", [Allowed, Rejected(Unauthorized), Rejected(Unauthorized)]),
+            ("commit_as", [Rejected(Unauthorized), Allowed, Rejected(Unauthorized)]),
+            ("revoke_delegate", [Allowed, Rejected(NotFound), Rejected(NotFound)]),
+            ("endorse_commit", [Rejected(SelfReferentialAddress), Allowed, Allowed]),
+            ("ack_route", [Allowed, Allowed, Allowed]),
+            ("mark_consumed", [Allowed, Allowed, Allowed]),
+            ("cancel_quote", [Allowed, Rejected(Unauthorized), Rejected(Unauthorized)]),
+            ("deactivate_signer", [Allowed, Rejected(Unauthorized), Rejected(Unauthorized)]),
+            ("commit_child_routes", [Allowed, Rejected(Unauthorized), Rejected(Unauthorized)]),
+            ("commit_as_scoped", [Rejected(Unauthorized), Allowed, Rejected(Unauthorized)]),
+        ];
+
+        let calls = auth_matrix_calls();
+        assert_eq!(calls.len(), expected.len());
+        for ((name, call), (expected_name, row)) in calls.iter().zip(expected) {
+            assert_eq!(name, &expected_name);
+            for (role, want) in row.into_iter().enumerate() {
+                let f = AuthFixture::new();
+                let caller = [&f.committer, &f.delegate, &f.stranger][role];
+                assert_eq!(call(&f, caller), want, "{} called by role {}", name, role);
+            }
+        }
+    }
+
+    #[test]
+    fn test_auth_matrix_without_signatures_traps() {
+        // Even the role each call is meant for cannot act without signing
+        let allowed_role = [0, 0, 0, 1, 0, 1, 0, 0, 0, 0, 0, 1];
+        for ((name, call), role) in auth_matrix_calls().iter().zip(allowed_role) {
+            let f = AuthFixture::new();
+            f.env.set_auths(&[]);
+            let caller = [&f.committer, &f.delegate, &f.stranger][role];
+            assert_eq!(call(&f, caller), Outcome::Trapped, "{} without auth", name);
+        }
+    }
+
+    fn entry(env: &Env, seed: u8, expiry: u64) -> CommitEntry {
+        CommitEntry {
+            route_hash: test_hash(env, seed),
+            rules_hash: test_hash(env, 2),
+            solver_version_hash: test_hash(env, 3),
+            expiry,
+        }
+    }
+
+    #[test]
+    fn test_commit_routes_records_every_entry() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        let entries = Vec::from_array(&env, [entry(&env, 88, 0), entry(&env, 89, 1700003600), entry(&env, 90, 0)]);
+        let events_before = env.events().all().len();
+        client.commit_routes(&committer, &entries);
+
+        assert_eq!(env.events().all().len(), events_before + 3);
+        for (seq, entry) in entries.iter().enumerate() {
+            let commitment = client.get_commit(&entry.route_hash);
+            assert_eq!(commitment.committer, committer);
+            assert_eq!(commitment.expiry, entry.expiry);
+            assert_eq!(commitment.committer_seq, seq as u64 + 1);
+        }
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_commit_routes_is_all_or_nothing() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        client.commit_route(&committer, &test_hash(&env, 91), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+
+        let cases = [
+            (Vec::from_array(&env, [entry(&env, 92, 0), entry(&env, 91, 0)]), RegistryError::DuplicateCommitment),
+            (Vec::from_array(&env, [entry(&env, 92, 0), entry(&env, 92, 0)]), RegistryError::DuplicateCommitment),
+            (Vec::from_array(&env, [entry(&env, 92, 0), entry(&env, 0, 0)]), RegistryError::EmptyRouteHash),
+            (Vec::from_array(&env, [entry(&env, 92, 0), entry(&env, 93, 1699999999)]), RegistryError::ExpiredTimestamp),
+        ];
+        for (entries, err) in cases {
+            assert_eq!(client.try_commit_routes(&committer, &entries), Err(Ok(err)));
+            assert!(!client.has_commit(&test_hash(&env, 92)));
+            assert_eq!(client.get_commit_count_by_committer(&committer), 1);
+        }
+
+        let mut oversized = Vec::new(&env);
+        for seed in 0..=MAX_BATCH_SIZE {
+            oversized.push_back(entry(&env, 100 + seed as u8, 0));
+        }
+        assert_eq!(client.try_commit_routes(&committer, &oversized), Err(Ok(RegistryError::TooManyEntries)));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_commit_routes_partial_reports_each_entry() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        client.commit_route(&committer, &test_hash(&env, 94), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+
+        let committed = CommitResult::Committed(1700000000);
+        let failed = |err: RegistryError| CommitResult::Failed(err as u32);
+        let batches = [
+            (
+                [entry(&env, 95, 0), entry(&env, 94, 0), entry(&env, 0, 0)],
+                [committed, failed(RegistryError::DuplicateCommitment), failed(RegistryError::EmptyRouteHash)],
+            ),
+            (
+                [entry(&env, 96, 1699999999), entry(&env, 97, 1700003600), entry(&env, 95, 0)],
+                [failed(RegistryError::ExpiredTimestamp), committed, failed(RegistryError::DuplicateCommitment)],
+            ),
+        ];
+        for (entries, expected) in batches {
+            let results = client.commit_routes_partial(&committer, &Vec::from_array(&env, entries));
+            assert_eq!(results, Vec::from_array(&env, expected));
+        }
+
+        assert!(client.has_commit(&test_hash(&env, 95)));
+        assert!(client.has_commit(&test_hash(&env, 97)));
+        assert!(!client.has_commit(&test_hash(&env, 96)));
+        // Failed entries never took a sequence number
+        assert_eq!(client.get_commit_count_by_committer(&committer), 3);
+        assert_eq!(client.get_commit(&test_hash(&env, 97)).committer_seq, 3);
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_commit_routes_partial_overflow_leaves_no_writes() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        // A solver counter with no room left: the entry's earlier counters
+        // (committer, id, rules) would all have room
+        let full_solver = test_hash(&env, 98);
+        env.as_contract(&contract_id, || {
+            env.storage().persistent().set(&DataKey::SolverCount(full_solver.clone()), &u64::MAX);
+        });
+
+        let overflowing = CommitEntry { solver_version_hash: full_solver, ..entry(&env, 95, 0) };
+        let results = client.commit_routes_partial(
+            &committer,
+            &Vec::from_array(&env, [overflowing, entry(&env, 96, 0), entry(&env, 97, 0)]),
+        );
+        let committed = CommitResult::Committed(1700000000);
+        assert_eq!(
+            results,
+            Vec::from_array(&env, [CommitResult::Failed(RegistryError::CommitCountOverflow as u32), committed, committed])
+        );
+
+        // Nothing of the failed entry was counted, listed or indexed
+        assert!(!client.has_commit(&test_hash(&env, 95)));
+        assert_eq!(client.get_commit_count_by_committer(&committer), 2);
+        assert_eq!(client.committer_count(), 1);
+        assert_eq!(client.total_commits(), 2);
+        assert_eq!(client.count_by_rules(&test_hash(&env, 2)), 2);
+        assert_eq!(client.commits_on_day(&(1700000000 / SECONDS_PER_DAY)), 2);
+        assert_eq!(client.get_commit_hashes_range(&1, &10), Vec::from_array(&env, [test_hash(&env, 96), test_hash(&env, 97)]));
+        assert_eq!(client.get_commits_by_committer(&committer, &0, &10).len(), 2);
+        assert_eq!(client.get_commit(&test_hash(&env, 96)).committer_seq, 1);
+        assert_eq!(client.get_ledger_commits(&env.ledger().sequence()).len(), 2);
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_commit_routes_full_batch_fits_budget() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+
+        // A realistic batch shares one rules and solver release. Worst case
+        // otherwise: a first-time committer, and an earlier commit that
+        // leaves every shared index one short of a page boundary
+        client.commit_routes(&Address::generate(&env), &Vec::from_array(&env, [entry(&env, 99, 0)]));
+        env.as_contract(&contract_id, || {
+            let boundary = INDEX_PAGE_SIZE as u64 - 1;
+            env.storage().instance().set(&DataKey::TotalCommits, &boundary);
+            for key in [DataKey::RulesCount(test_hash(&env, 2)), DataKey::SolverCount(test_hash(&env, 3))] {
+                env.storage().persistent().set(&key, &boundary);
+            }
+            env.storage().persistent().set(&DataKey::DayCount(1700000000 / SECONDS_PER_DAY), &boundary);
+            env.storage().persistent().set(&DataKey::CommitterListCount, &boundary);
+            env.storage().temporary().set(&DataKey::LedgerCount(env.ledger().sequence()), &(INDEX_PAGE_SIZE - 1));
+        });
+        
+        let mut entries = Vec::new(&env);
+        for seed in 0..MAX_BATCH_SIZE as u8 {
+            entries.push_back(entry(&env, 100 + seed, 0));
+        }
+        env.budget().reset_default();
+        let written = write_entries(&env, || {
+            client.commit_routes(&Address::generate(&env), &entries);
+        });
+        const _: () = assert!(MAX_BATCH_SIZE >= 25, "batches must hold a realistic 25 entries");
+        assert!(client.has_commit(&test_hash(&env, 100 + MAX_BATCH_SIZE as u8 - 1)));
+        assert!(written <= TX_MAX_WRITE_ENTRIES, "written={}", written);
+    }
+
+    #[test]
+    fn test_get_commits_is_positional() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        let (found_a, found_b) = (test_hash(&env, 98), test_hash(&env, 99));
+        let (missing_a, missing_b) = (test_hash(&env, 100), test_hash(&env, 101));
+        client.commit_route(&committer, &found_a, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        client.commit_route(&committer, &found_b, &test_hash(&env, 2), &test_hash(&env, 3), &1700003600);
+
+        let all_found = client.get_commits(&Vec::from_array(&env, [found_b.clone(), found_a.clone()]));
+        assert_eq!(
+            all_found,
+            Vec::from_array(&env, [Some(client.get_commit(&found_b)), Some(client.get_commit(&found_a))])
+        );
+
+        let all_missing = client.get_commits(&Vec::from_array(&env, [missing_a.clone(), missing_b.clone()]));
+        assert_eq!(all_missing, Vec::from_array(&env, [None, None]));
+
+        let mixed = client.get_commits(&Vec::from_array(&env, [missing_a, found_a.clone(), missing_b, found_a.clone()]));
+        let found = Some(client.get_commit(&found_a));
+        assert_eq!(mixed, Vec::from_array(&env, [None, found.clone(), None, found]));
+
+        assert_eq!(client.get_commits(&Vec::new(&env)), Vec::new(&env));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_get_commits_rejects_oversized_input() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+
+        let mut route_hashes = Vec::new(&env);
+        for _ in 0..MAX_READ_BATCH_SIZE {
+            route_hashes.push_back(test_hash(&env, 102));
+        }
+        assert_eq!(client.get_commits(&route_hashes).len(), MAX_READ_BATCH_SIZE);
+        route_hashes.push_back(test_hash(&env, 102));
+        assert_eq!(client.try_get_commits(&route_hashes), Err(Ok(RegistryError::TooManyEntries)));
+    }
+
+    #[test]
+    fn test_has_commits_is_positional_and_cheap() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        // A full batch of hashes, every other one committed. Unmetered: the
+        // test host charges every call for the whole test ledger
+        env.budget().reset_unlimited();
+        let mut route_hashes = Vec::new(&env);
+        let mut expected = Vec::new(&env);
+        for seed in 103..103 + MAX_READ_BATCH_SIZE as u8 {
+            let committed = seed % 2 == 0;
+            if committed {
+                client.commit_route(&committer, &test_hash(&env, seed), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+            }
+            route_hashes.push_back(test_hash(&env, seed));
+            expected.push_back(committed);
+        }
+
+        // Touch every key once so neither side pays for first access
+        client.has_commits(&route_hashes);
+
+        env.budget().reset_unlimited();
+        assert_eq!(client.has_commits(&route_hashes), expected);
+        let batch_cost = env.budget().cpu_instruction_cost();
+
+        env.budget().reset_unlimited();
+        for route_hash in route_hashes.iter() {
+            let _ = client.try_get_commit(&route_hash);
+        }
+        let single_cost = env.budget().cpu_instruction_cost();
+        assert!(batch_cost * 2 < single_cost, "batch {} vs single {}", batch_cost, single_cost);
+
+        route_hashes.append(&route_hashes.clone());
+        route_hashes.push_back(test_hash(&env, 103));
+        assert_eq!(client.try_has_commits(&route_hashes), Err(Ok(RegistryError::TooManyEntries)));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_verify_commits_matches_verify_commit() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        let (rules, solver) = (test_hash(&env, 2), test_hash(&env, 3));
+        client.commit_route(&committer, &test_hash(&env, 153), &rules, &solver, &0u64);
+        client.commit_route(&committer, &test_hash(&env, 154), &rules, &solver, &0u64);
+
+        let item = |seed: u8, rules: &BytesN<32>, solver: &BytesN<32>| VerifyItem {
+            route_hash: test_hash(&env, seed),
+            expected_rules_hash: rules.clone(),
+            expected_solver_hash: solver.clone(),
+        };
+        let batches = [
+            (
+                Vec::from_array(&env, [item(153, &rules, &solver), item(155, &rules, &solver), item(154, &test_hash(&env, 4), &solver)]),
+                [true, false, false],
+            ),
+            (
+                Vec::from_array(&env, [item(154, &rules, &solver), item(153, &rules, &test_hash(&env, 4)), item(156, &rules, &solver)]),
+                [true, false, false],
+            ),
+        ];
+        for (items, expected) in batches {
+            let results = client.verify_commits(&items);
+            assert_eq!(results, Vec::from_array(&env, expected));
+            for (item, result) in items.iter().zip(results.iter()) {
+                assert_eq!(client.verify_commit(&item.route_hash, &item.expected_rules_hash, &item.expected_solver_hash), result);
+            }
         }
-        
-        // Validate: commitment must not already exist
-        let key = CommitKey { route_hash: route_hash.clone() };
-        if env.storage().persistent().has(&key) {
-            log!(&env, "Rejected: duplicate commitment for route_hash");
-            return Err(RegistryError::DuplicateCommitment);
+
+        let mut oversized = Vec::new(&env);
+        for _ in 0..=MAX_READ_BATCH_SIZE {
+            oversized.push_back(item(153, &rules, &solver));
         }
-        
-        // Validate: expiry (if non-zero) must be in the future
-        if expiry != 0 {
-            if expiry <= timestamp {
-                log!(&env, "Rejected: expiry {} is not after timestamp {}", expiry, timestamp);
-                return Err(RegistryError::ExpiredTimestamp);
+        assert_eq!(client.try_verify_commits(&oversized), Err(Ok(RegistryError::TooManyEntries)));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    #[test]
+    fn test_every_batch_entry_point_enforces_max_batch_size() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        assert_eq!(client.max_batch_size(), MAX_BATCH_SIZE);
+        assert_eq!(client.get_limits().get(Symbol::new(&env, "max_batch_size")), Some(MAX_BATCH_SIZE as u64));
+        assert_eq!(client.max_read_batch_size(), MAX_READ_BATCH_SIZE);
+        assert_eq!(client.get_limits().get(Symbol::new(&env, "max_read_batch_size")), Some(MAX_READ_BATCH_SIZE as u64));
+
+        // Inputs of length n, with distinct route hashes starting at `first`
+        let entries = |first: u8, n: u32| {
+            let mut v = Vec::new(&env);
+            for i in 0..n {
+                v.push_back(entry(&env, first + i as u8, 0));
             }
-            
-            // Sanity check: expiry not too far in future
-            if expiry > timestamp + MAX_EXPIRY_DURATION {
-                log!(&env, "Rejected: expiry too far in future");
-                return Err(RegistryError::ExpiryTooFar);
+            v
+        };
+        let hashes = |n: u32| {
+            let mut v = Vec::new(&env);
+            for i in 0..n {
+                v.push_back(test_hash(&env, 1 + i as u8));
             }
-        }
-        
-        // Get committer address (transaction source)
-        let committer = env.current_contract_address();
-        
-        // Create commitment struct
-        let commitment = RouteCommitment {
-            rules_hash: rules_hash.clone(),
-            solver_version_hash: solver_version_hash.clone(),
-            committer: committer.clone(),
-            timestamp,
-            expiry,
+            v
         };
-        
-        // Store commitment (persistent storage for long-term retention)
-        env.storage().persistent().set(&key, &commitment);
-        
-        // Emit RouteCommitted event for indexers and auditors
-        env.events().publish(
-            (symbol_short!("commit"), route_hash.clone()),
-            (
-                rules_hash,
-                solver_version_hash,
-                committer,
-                timestamp,
-                expiry,
-            ),
-        );
-        
-        log!(&env, "RouteCommitted: hash={:?}, timestamp={}", route_hash, timestamp);
-        
-        Ok(())
-    }
-    
-    /// Retrieve commitment metadata for a route hash.
-    ///
-    /// # Arguments
-    ///
-    /// * `env` - Soroban environment
-    /// * `route_hash` - SHA-256 hash of the route to look up
-    ///
-    /// # Returns
-    ///
-    /// * `Ok(RouteCommitment)` - Full commitment metadata
-    /// * `Err(RegistryError::NotFound)` - No commitment exists for this hash
-    ///
-    /// # Usage
-    ///
-    /// ```text
-    /// let commit = contract.get_commit(route_hash)?;
-    /// assert!(commit.rules_hash == expected_rules_hash);
-    /// assert!(commit.solver_version_hash == known_solver_commit);
-    /// ```
-    pub fn get_commit(
-        env: Env,
-        route_hash: BytesN<32>,
-    ) -> Result<RouteCommitment, RegistryError> {
-        let key = CommitKey { route_hash };
-        
-        env.storage()
-            .persistent()
-            .get(&key)
-            .ok_or(RegistryError::NotFound)
-    }
-    
-    /// Check if a route hash has been committed.
-    ///
-    /// Gas-efficient existence check without loading full commitment data.
-    ///
-    /// # Arguments
-    ///
-    /// * `env` - Soroban environment
-    /// * `route_hash` - SHA-256 hash to check
-    ///
-    /// # Returns
-    ///
-    /// * `true` if commitment exists
-    /// * `false` otherwise
-    pub fn has_commit(env: Env, route_hash: BytesN<32>) -> bool {
-        let key = CommitKey { route_hash };
-        env.storage().persistent().has(&key)
-    }
-    
-    /// Verify that a commitment matches expected values.
-    ///
-    /// Convenience function for on-chain verification by other contracts.
-    ///
-    /// # Arguments
-    ///
-    /// * `env` - Soroban environment
-    /// * `route_hash` - Route hash to verify
-    /// * `expected_rules_hash` - Expected rules hash
-    /// * `expected_solver_hash` - Expected solver version hash
-    ///
-    /// # Returns
-    ///
-    /// * `true` if commitment exists AND all hashes match
-    /// * `false` otherwise
-    pub fn verify_commit(
-        env: Env,
-        route_hash: BytesN<32>,
-        expected_rules_hash: BytesN<32>,
-        expected_solver_hash: BytesN<32>,
-    ) -> bool {
-        match Self::get_commit(env, route_hash) {
-            Ok(commit) => {
-                commit.rules_hash == expected_rules_hash
-                    && commit.solver_version_hash == expected_solver_hash
+        let items = |n: u32| {
+            let mut v = Vec::new(&env);
+            for i in 0..n {
+                v.push_back(VerifyItem {
+                    route_hash: test_hash(&env, 1 + i as u8),
+                    expected_rules_hash: test_hash(&env, 2),
+                    expected_solver_hash: test_hash(&env, 3),
+                });
             }
-            Err(_) => false,
+            v
+        };
+        let (max, over) = (MAX_BATCH_SIZE, MAX_BATCH_SIZE + 1);
+        let (read_max, read_over) = (MAX_READ_BATCH_SIZE, MAX_READ_BATCH_SIZE + 1);
+        let all_found = || {
+            let mut v = Vec::new(&env);
+            for _ in 0..read_max {
+                v.push_back(true);
+            }
+            v
+        };
+        let too_many = Outcome::Rejected(RegistryError::TooManyEntries);
+
+        // Unmetered: the test host charges every call for the whole test
+        // ledger, which grows with each full batch. The cost of one full
+        // batch is checked by test_commit_routes_full_batch_fits_budget.
+        env.budget().reset_unlimited();
+
+        assert_eq!(client.try_commit_routes(&committer, &entries(1, 0)), Ok(Ok(())));
+        assert_eq!(outcome(client.try_commit_routes(&committer, &entries(1, over))), too_many);
+        client.commit_routes(&committer, &entries(1, max));
+        assert_eq!(client.get_commit_count_by_committer(&committer), max as u64);
+
+        assert_eq!(client.commit_routes_partial(&committer, &entries(101, 0)), Vec::new(&env));
+        assert_eq!(outcome(client.try_commit_routes_partial(&committer, &entries(101, over))), too_many);
+        assert_eq!(client.commit_routes_partial(&committer, &entries(101, max)).len(), max);
+
+        // Reads take the larger cap; commit the rest of a full read batch first
+        for first in (max + 1..=read_max).step_by(max as usize) {
+            client.commit_routes(&committer, &entries(first as u8, max.min(read_max + 1 - first)));
         }
+
+        assert_eq!(client.get_commits(&hashes(0)), Vec::new(&env));
+        assert_eq!(outcome(client.try_get_commits(&hashes(read_over))), too_many);
+        assert_eq!(client.get_commits(&hashes(read_max)).len(), read_max);
+
+        assert_eq!(client.has_commits(&hashes(0)), Vec::new(&env));
+        assert_eq!(outcome(client.try_has_commits(&hashes(read_over))), too_many);
+        assert_eq!(client.has_commits(&hashes(read_max)), all_found());
+
+        assert!(!client.exists_any(&hashes(0)));
+        assert_eq!(outcome(client.try_exists_any(&hashes(read_over))), too_many);
+        assert!(client.exists_any(&hashes(read_max)));
+
+        assert_eq!(client.first_existing(&hashes(0)), None);
+        assert_eq!(outcome(client.try_first_existing(&hashes(read_over))), too_many);
+        assert_eq!(client.first_existing(&hashes(read_max)), Some(test_hash(&env, 1)));
+
+        assert_eq!(client.verify_commits(&items(0)), Vec::new(&env));
+        assert_eq!(outcome(client.try_verify_commits(&items(read_over))), too_many);
+        assert_eq!(client.verify_commits(&items(read_max)), all_found());
+
+        let attester = Address::generate(&env);
+        assert_eq!(client.attest_batch(&attester, &hashes(0)), Vec::new(&env));
+        assert_eq!(outcome(client.try_attest_batch(&attester, &hashes(read_over))), too_many);
+        assert_eq!(client.attest_batch(&attester, &hashes(read_max)), all_found());
+
+        assert_eq!(client.extend_ttls(&hashes(0), &10_000), 0);
+        assert_eq!(outcome(client.try_extend_ttls(&hashes(over), &10_000)), too_many);
+        assert_eq!(client.extend_ttls(&hashes(max), &10_000), max);
+
+        let reason_hash = test_hash(&env, 4);
+        assert_eq!(client.revoke_commits(&committer, &hashes(0), &reason_hash), Vec::new(&env));
+        assert_eq!(outcome(client.try_revoke_commits(&committer, &hashes(over), &reason_hash)), too_many);
+        assert_eq!(
+            client.revoke_commits(&committer, &hashes(max), &reason_hash),
+            Vec::from_array(&env, [true; MAX_BATCH_SIZE as usize])
+        );
+        invariants::assert_registry_invariants(&env, &client);
     }
-    
-    // ─────────────────────────────────────────────────────────────────
-    // Internal helpers
-    // ─────────────────────────────────────────────────────────────────
-    
-    /// Check if a 32-byte hash is all zeros
-    fn is_zero_hash(hash: &BytesN<32>) -> bool {
-        let bytes = hash.to_array();
-        bytes.iter().all(|&b| b == 0)
+
+    #[test]
+    fn test_revoke_commits_flags_each_route() {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        let other = Address::generate(&env);
+        let reason_hash = test_hash(&env, 4);
+        client.commit_route(&committer, &test_hash(&env, 82), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        client.commit_route(&committer, &test_hash(&env, 83), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        client.commit_route(&other, &test_hash(&env, 84), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        client.revoke_commit(&test_hash(&env, 83), &committer, &reason_hash);
+
+        // Own, already revoked, foreign, missing
+        let route_hashes = Vec::from_array(
+            &env,
+            [test_hash(&env, 82), test_hash(&env, 83), test_hash(&env, 84), test_hash(&env, 85)],
+        );
+        let before = env.events().all().len();
+        let flags = client.revoke_commits(&committer, &route_hashes, &reason_hash);
+        assert_eq!(flags, Vec::from_array(&env, [true, false, false, false]));
+        let authorized: std::vec::Vec<Address> = env.auths().into_iter().map(|(addr, _)| addr).collect();
+        assert_eq!(authorized, std::vec![committer.clone()]);
+
+        // The event matches single revoke_commit
+        let events = env.events().all();
+        assert_eq!(events.len(), before + 1);
+        let (_, topics, data) = events.last().unwrap();
+        assert_eq!(topics, (symbol_short!("withdrawn"), test_hash(&env, 82)).into_val(&env));
+        let data: (Address, BytesN<32>, u64) = data.into_val(&env);
+        assert_eq!(data, (committer.clone(), reason_hash.clone(), 1700000000));
+        assert!(client.is_revoked(&test_hash(&env, 82)));
+        assert!(!client.is_revoked(&test_hash(&env, 84)));
+
+        // Own again, now already revoked
+        let again = Vec::from_array(&env, [test_hash(&env, 82)]);
+        assert_eq!(client.revoke_commits(&committer, &again, &reason_hash), Vec::from_array(&env, [false]));
+
+        let result = client.try_revoke_commits(&committer, &route_hashes, &zero_hash(&env));
+        assert_eq!(result, Err(Ok(RegistryError::EmptyReasonHash)));
+        invariants::assert_registry_invariants(&env, &client);
     }
-}
 
-// ═══════════════════════════════════════════════════════════════════════
-// UNIT TESTS
-// ═══════════════════════════════════════════════════════════════════════
+    #[test]
+    fn test_extend_ttls_keeps_commitments_alive() {
+        use soroban_sdk::testutils::storage::Persistent as _;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use soroban_sdk::testutils::{Address as _, Ledger, LedgerInfo};
-    use soroban_sdk::{vec, Env};
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        let kept = test_hash(&env, 79);
+        let lapsed = test_hash(&env, 80);
+        client.commit_route(&committer, &kept, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        client.commit_route(&committer, &lapsed, &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        let ttl = |route_hash: &BytesN<32>| {
+            env.as_contract(&contract_id, || env.storage().persistent().get_ttl(&DataKey::Commit(route_hash.clone())))
+        };
+        let initial_ttl = ttl(&kept);
 
-    fn setup_env() -> Env {
-        let env = Env::default();
+        let route_hashes = Vec::from_array(&env, [kept.clone(), test_hash(&env, 81)]);
+        assert_eq!(client.extend_ttls(&route_hashes, &500_000), 1);
+        assert_eq!(ttl(&kept), 500_000);
+        assert_eq!(ttl(&lapsed), initial_ttl);
+
+        // Past the original TTL only the extended commitment is still live
+        env.as_contract(&contract_id, || env.storage().instance().extend_ttl(500_000, 500_000));
         env.ledger().set(LedgerInfo {
-            timestamp: 1700000000, // Fixed timestamp for tests
+            timestamp: 1700000000 + 5 * 10_000,
             protocol_version: 21,
-            sequence_number: 100,
+            sequence_number: 100 + 10_000,
             network_id: [0u8; 32],
             base_reserve: 10,
             min_temp_entry_ttl: 100,
             min_persistent_entry_ttl: 100,
             max_entry_ttl: 1000000,
         });
-        env
-    }
-
-    fn test_hash(seed: u8) -> BytesN<32> {
-        let mut bytes = [0u8; 32];
-        bytes[0] = seed;
-        bytes[31] = seed;
-        BytesN::from_array(&Env::default(), &bytes)
-    }
+        assert!(initial_ttl < 10_000);
+        assert!(client.has_commit(&kept));
+        assert_eq!(client.get_commit(&kept).committer, committer);
+        assert_eq!(ttl(&kept), 500_000 - 10_000);
 
-    fn zero_hash() -> BytesN<32> {
-        BytesN::from_array(&Env::default(), &[0u8; 32])
+        // The network cap is enforced
+        let max_ttl = env.as_contract(&contract_id, || env.storage().max_ttl());
+        assert_eq!(client.try_extend_ttls(&route_hashes, &(max_ttl + 1)), Err(Ok(RegistryError::TtlTooLong)));
+        assert_eq!(client.extend_ttls(&route_hashes, &max_ttl), 1);
     }
 
     #[test]
-    fn test_successful_commit() {
+    fn test_extend_ttls_keeps_revocation_and_indexes_alive() {
         let env = setup_env();
         let contract_id = env.register_contract(None, RouteIntegrityRegistry);
         let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        let endorser = Address::generate(&env);
+        let route_hash = test_hash(&env, 82);
+        let rules_hash = test_hash(&env, 2);
+        client.commit_route(&committer, &route_hash, &rules_hash, &test_hash(&env, 3), &0u64);
+        client.endorse_commit(&route_hash, &endorser);
+        client.revoke_commit(&route_hash, &committer, &test_hash(&env, 83));
+        let day = env.ledger().timestamp() / 86_400;
 
-        let route_hash = test_hash(1);
-        let rules_hash = test_hash(2);
-        let solver_hash = test_hash(3);
-        let expiry = 1700001000u64; // 1000 seconds in future
-
-        // Commit should succeed
-        let result = client.commit_route(&route_hash, &rules_hash, &solver_hash, &expiry);
-        assert!(result.is_ok());
+        assert_eq!(client.extend_ttls(&Vec::from_array(&env, [route_hash.clone()]), &500_000), 1);
 
-        // Verify commitment stored correctly
-        let commit = client.get_commit(&route_hash).unwrap();
-        assert_eq!(commit.rules_hash, rules_hash);
-        assert_eq!(commit.solver_version_hash, solver_hash);
-        assert_eq!(commit.expiry, expiry);
-        assert_eq!(commit.timestamp, 1700000000);
+        // Every entry the commitment's reads touch outlives the original TTL
+        env.as_contract(&contract_id, || env.storage().instance().extend_ttl(500_000, 500_000));
+        env.ledger().set(LedgerInfo {
+            timestamp: 1700000000 + 5 * 10_000,
+            protocol_version: 21,
+            sequence_number: 100 + 10_000,
+            network_id: [0u8; 32],
+            base_reserve: 10,
+            min_temp_entry_ttl: 100,
+            min_persistent_entry_ttl: 100,
+            max_entry_ttl: 1000000,
+        });
+        assert!(client.is_revoked(&route_hash));
+        assert!(client.is_endorsed_by(&route_hash, &endorser));
+        assert_eq!(client.get_endorsements(&route_hash), Vec::from_array(&env, [endorser]));
+        assert_eq!(client.get_commit_by_id(&1).committer, committer);
+        assert_eq!(client.get_commits_by_committer(&committer, &0, &10), Vec::from_array(&env, [route_hash.clone()]));
+        assert_eq!(client.get_commits_by_rules(&rules_hash, &0, &10), Vec::from_array(&env, [route_hash.clone()]));
+        assert_eq!(client.count_by_rules(&rules_hash), 1);
+        assert_eq!(client.commits_on_day(&day), 1);
     }
 
     #[test]
-    fn test_commit_no_expiry() {
+    fn test_attest_batch_skips_missing_routes() {
         let env = setup_env();
         let contract_id = env.register_contract(None, RouteIntegrityRegistry);
         let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        let attester = Address::generate(&env);
+        client.commit_route(&committer, &test_hash(&env, 76), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
+        client.commit_route(&committer, &test_hash(&env, 78), &test_hash(&env, 2), &test_hash(&env, 3), &0u64);
 
-        let route_hash = test_hash(10);
-        let rules_hash = test_hash(20);
-        let solver_hash = test_hash(30);
-        let expiry = 0u64; // No expiry
+        let route_hashes = Vec::from_array(&env, [test_hash(&env, 76), test_hash(&env, 77), test_hash(&env, 78)]);
+        let before = env.events().all().len();
+        let flags = client.attest_batch(&attester, &route_hashes);
+        assert_eq!(flags, Vec::from_array(&env, [true, false, true]));
+        let authorized: std::vec::Vec<Address> = env.auths().into_iter().map(|(addr, _)| addr).collect();
+        assert_eq!(authorized, std::vec![attester.clone()]);
 
-        let result = client.commit_route(&route_hash, &rules_hash, &solver_hash, &expiry);
-        assert!(result.is_ok());
+        // One event per recorded attestation
+        let events = env.events().all();
+        assert_eq!(events.len(), before + 2);
+        for (i, route_hash) in [test_hash(&env, 76), test_hash(&env, 78)].into_iter().enumerate() {
+            let (_, topics, data) = events.get(before + i as u32).unwrap();
+            assert_eq!(topics, (symbol_short!("attested"), route_hash).into_val(&env));
+            let data: (Address, u64) = data.into_val(&env);
+            assert_eq!(data, (attester.clone(), 1700000000));
+        }
+        assert_eq!(client.get_attestation(&test_hash(&env, 76), &attester), Some(1700000000));
+        assert_eq!(client.get_attestation(&test_hash(&env, 77), &attester), None);
 
-        let commit = client.get_commit(&route_hash).unwrap();
-        assert_eq!(commit.expiry, 0);
+        // Re-attesting refreshes the timestamp; single attest rejects missing routes
+        env.ledger().with_mut(|li| li.timestamp += SECONDS_PER_DAY);
+        client.attest(&test_hash(&env, 78), &attester);
+        assert_eq!(client.get_attestation(&test_hash(&env, 78), &attester), Some(1700000000 + SECONDS_PER_DAY));
+        assert_eq!(client.try_attest(&test_hash(&env, 77), &attester), Err(Ok(RegistryError::NotFound)));
+        invariants::assert_registry_invariants(&env, &client);
     }
 
     #[test]
-    fn test_reject_duplicate_route_hash() {
+    fn test_commit_batch_root_stores_batch() {
         let env = setup_env();
         let contract_id = env.register_contract(None, RouteIntegrityRegistry);
         let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        let batch_root = test_hash(&env, 157);
 
-        let route_hash = test_hash(5);
-        let rules_hash = test_hash(6);
-        let solver_hash = test_hash(7);
-        let expiry = 1700001000u64;
+        client.commit_batch_root(&committer, &batch_root, &test_hash(&env, 2), &test_hash(&env, 3), &1700003600, &1000);
 
-        // First commit succeeds
-        assert!(client.commit_route(&route_hash, &rules_hash, &solver_hash, &expiry).is_ok());
+        let batch = client.get_batch(&batch_root);
+        assert_eq!(
+            batch,
+            BatchCommitment {
+                rules_hash: test_hash(&env, 2),
+                solver_version_hash: test_hash(&env, 3),
+                committer: committer.clone(),
+                timestamp: 1700000000,
+                expiry: 1700003600,
+                leaf_count: 1000,
+            }
+        );
+        let (_, topics, data) = env.events().all().last().unwrap();
+        assert_eq!(topics, (symbol_short!("batch"), batch_root.clone()).into_val(&env));
+        let data: (BytesN<32>, BytesN<32>, Address, u64, u64, u32) = data.into_val(&env);
+        assert_eq!(data.5, 1000);
 
-        // Second commit with same route_hash fails
-        let result = client.commit_route(&route_hash, &rules_hash, &solver_hash, &expiry);
-        assert_eq!(result, Err(RegistryError::DuplicateCommitment));
+        // Batch roots do not count as route commitments
+        assert!(!client.has_commit(&batch_root));
+        assert_eq!(client.try_get_batch(&test_hash(&env, 158)), Err(Ok(RegistryError::NotFound)));
+        invariants::assert_registry_invariants(&env, &client);
     }
 
     #[test]
-    fn test_reject_empty_route_hash() {
+    fn test_commit_batch_root_validates_like_commit_route() {
         let env = setup_env();
         let contract_id = env.register_contract(None, RouteIntegrityRegistry);
         let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+        let batch_root = test_hash(&env, 159);
+        client.commit_batch_root(&committer, &batch_root, &test_hash(&env, 2), &test_hash(&env, 3), &0u64, &4);
 
-        let route_hash = zero_hash();
-        let rules_hash = test_hash(2);
-        let solver_hash = test_hash(3);
-        let expiry = 1700001000u64;
+        let (rules, solver) = (test_hash(&env, 2), test_hash(&env, 3));
+        let cases = [
+            (zero_hash(&env), rules.clone(), solver.clone(), 0u64, 4u32, RegistryError::EmptyRouteHash),
+            (test_hash(&env, 160), zero_hash(&env), solver.clone(), 0, 4, RegistryError::EmptyRulesHash),
+            (test_hash(&env, 160), rules.clone(), zero_hash(&env), 0, 4, RegistryError::EmptySolverHash),
+            (test_hash(&env, 160), rules.clone(), solver.clone(), 0, 0, RegistryError::EmptyBatch),
+            (batch_root, rules.clone(), solver.clone(), 0, 4, RegistryError::DuplicateCommitment),
+            (test_hash(&env, 160), rules.clone(), solver.clone(), 1699999999, 4, RegistryError::ExpiredTimestamp),
+            (test_hash(&env, 160), rules, solver, 1700000000 + MAX_EXPIRY_DURATION + 1, 4, RegistryError::ExpiryTooFar),
+        ];
+        for (root, rules, solver, expiry, leaf_count, err) in cases {
+            assert_eq!(
+                client.try_commit_batch_root(&committer, &root, &rules, &solver, &expiry, &leaf_count),
+                Err(Ok(err))
+            );
+        }
+        assert_eq!(client.try_get_batch(&test_hash(&env, 160)), Err(Ok(RegistryError::NotFound)));
+        invariants::assert_registry_invariants(&env, &client);
+    }
 
-        let result = client.commit_route(&route_hash, &rules_hash, &solver_hash, &expiry);
-        assert_eq!(result, Err(RegistryError::EmptyRouteHash));
+    /// Off-chain Merkle tree over `leaves`, as levels from the leaves up to
+    /// the root. An odd level pairs its last node with itself.
+    fn offchain_merkle_levels(leaves: &[[u8; 32]]) -> std::vec::Vec<std::vec::Vec<[u8; 32]>> {
+        use sha2::{Digest, Sha256};
+        let mut levels = std::vec![leaves.to_vec()];
+        while levels.last().unwrap().len() > 1 {
+            let level = levels.last().unwrap();
+            let parents = level
+                .chunks(2)
+                .map(|pair| {
+                    let mut hasher = Sha256::new();
+                    hasher.update(pair[0]);
+                    hasher.update(pair.get(1).unwrap_or(&pair[0]));
+                    hasher.finalize().into()
+                })
+                .collect();
+            levels.push(parents);
+        }
+        levels
+    }
+
+    /// Off-chain inclusion proof for leaf `index`
+    fn offchain_merkle_proof(env: &Env, levels: &[std::vec::Vec<[u8; 32]>], index: u32) -> Vec<BytesN<32>> {
+        let mut proof = Vec::new(env);
+        let mut i = index as usize;
+        for level in &levels[..levels.len() - 1] {
+            let sibling = level.get(i ^ 1).unwrap_or(&level[i]);
+            proof.push_back(BytesN::from_array(env, sibling));
+            i /= 2;
+        }
+        proof
     }
 
     #[test]
-    fn test_reject_expired_timestamp() {
+    fn test_verify_inclusion_matches_offchain_tree() {
         let env = setup_env();
         let contract_id = env.register_contract(None, RouteIntegrityRegistry);
         let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
 
-        let route_hash = test_hash(8);
-        let rules_hash = test_hash(9);
-        let solver_hash = test_hash(10);
-        let expiry = 1699999999u64; // In the past
+        // Vectors generated with Python's hashlib over test_hash(170..=177)
+        // and test_hash(180..=184)
+        let even: std::vec::Vec<[u8; 32]> = (170..178).map(|seed| test_hash(&env, seed).to_array()).collect();
+        let odd: std::vec::Vec<[u8; 32]> = (180..185).map(|seed| test_hash(&env, seed).to_array()).collect();
+        let even_levels = offchain_merkle_levels(&even);
+        let odd_levels = offchain_merkle_levels(&odd);
+        let even_root = hex32("b214b90a9049caf6d7d59e35becb243696e3c99a4edb6f09947365f194ea506e");
+        let odd_root = hex32("8f0cfcc4af9a7a8f2a89143a67c8fbfc5fce32487ea7790980df64163b2407c2");
+        assert_eq!(even_levels.last().unwrap()[0], even_root);
+        assert_eq!(odd_levels.last().unwrap()[0], odd_root);
+
+        let even_root = BytesN::from_array(&env, &even_root);
+        let odd_root = BytesN::from_array(&env, &odd_root);
+        client.commit_batch_root(&committer, &even_root, &test_hash(&env, 2), &test_hash(&env, 3), &0u64, &8);
+        client.commit_batch_root(&committer, &odd_root, &test_hash(&env, 2), &test_hash(&env, 3), &0u64, &5);
+
+        for (index, leaf) in even.iter().enumerate() {
+            let proof = offchain_merkle_proof(&env, &even_levels, index as u32);
+            assert!(client.verify_inclusion(&even_root, &BytesN::from_array(&env, leaf), &proof, &(index as u32)));
+        }
+        for (index, leaf) in odd.iter().enumerate() {
+            let proof = offchain_merkle_proof(&env, &odd_levels, index as u32);
+            assert!(client.verify_inclusion(&odd_root, &BytesN::from_array(&env, leaf), &proof, &(index as u32)));
+        }
 
-        let result = client.commit_route(&route_hash, &rules_hash, &solver_hash, &expiry);
-        assert_eq!(result, Err(RegistryError::ExpiredTimestamp));
+        // Last leaf of the odd tree is paired with itself twice on its way up
+        let last_proof = Vec::from_array(
+            &env,
+            [
+                test_hash(&env, 184),
+                BytesN::from_array(&env, &hex32("89f0245f0eb04b9d672a7cf99fb098b845fcddc3261aa87ca983eca9ea998cb1")),
+                BytesN::from_array(&env, &hex32("8ac154cf73a0fe808221a26feaf38812bde13e0d1b6606e44299569d25dddeec")),
+            ],
+        );
+        assert_eq!(last_proof, offchain_merkle_proof(&env, &odd_levels, 4));
+        assert!(client.verify_inclusion(&odd_root, &test_hash(&env, 184), &last_proof, &4));
+        invariants::assert_registry_invariants(&env, &client);
     }
 
     #[test]
-    fn test_reject_expiry_too_far() {
+    fn test_verify_inclusion_rejects_bad_proofs() {
         let env = setup_env();
         let contract_id = env.register_contract(None, RouteIntegrityRegistry);
         let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
 
-        let route_hash = test_hash(11);
-        let rules_hash = test_hash(12);
-        let solver_hash = test_hash(13);
-        // More than 10 years in future
-        let expiry = 1700000000u64 + MAX_EXPIRY_DURATION + 1;
+        let leaves: std::vec::Vec<[u8; 32]> = (186..191).map(|seed| test_hash(&env, seed).to_array()).collect();
+        let levels = offchain_merkle_levels(&leaves);
+        let root = BytesN::from_array(&env, &levels.last().unwrap()[0]);
+        client.commit_batch_root(&committer, &root, &test_hash(&env, 2), &test_hash(&env, 3), &1700003600, &5);
+
+        let leaf = test_hash(&env, 187);
+        let proof = offchain_merkle_proof(&env, &levels, 1);
+        assert!(client.verify_inclusion(&root, &leaf, &proof, &1));
+
+        // Wrong index, wrong leaf, unknown root
+        assert!(!client.verify_inclusion(&root, &leaf, &proof, &0));
+        assert!(!client.verify_inclusion(&root, &test_hash(&env, 186), &proof, &1));
+        assert!(!client.verify_inclusion(&test_hash(&env, 191), &leaf, &proof, &1));
 
-        let result = client.commit_route(&route_hash, &rules_hash, &solver_hash, &expiry);
-        assert_eq!(result, Err(RegistryError::ExpiryTooFar));
+        // Tampered, truncated, padded and empty proofs
+        let mut tampered = proof.clone();
+        tampered.set(1, test_hash(&env, 192));
+        assert!(!client.verify_inclusion(&root, &leaf, &tampered, &1));
+        let mut truncated = proof.clone();
+        truncated.pop_back();
+        assert!(!client.verify_inclusion(&root, &leaf, &truncated, &1));
+        let mut padded = proof.clone();
+        padded.push_back(test_hash(&env, 192));
+        assert!(!client.verify_inclusion(&root, &leaf, &padded, &1));
+        assert!(!client.verify_inclusion(&root, &leaf, &Vec::new(&env), &1));
+
+        // The duplicated slot past the last leaf is not a leaf
+        let last = test_hash(&env, 190);
+        let last_proof = offchain_merkle_proof(&env, &levels, 4);
+        assert!(client.verify_inclusion(&root, &last, &last_proof, &4));
+        assert!(!client.verify_inclusion(&root, &last, &last_proof, &5));
+        assert!(!client.verify_inclusion(&root, &leaf, &proof, &u32::MAX));
+
+        // Expired batches no longer prove inclusion
+        env.ledger().with_mut(|li| li.timestamp = 1700003600);
+        assert!(!client.verify_inclusion(&root, &leaf, &proof, &1));
+        invariants::assert_registry_invariants(&env, &client);
     }
 
     #[test]
-    fn test_get_nonexistent_commit() {
-        let env = setup_env();
-        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
-        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+    #[ignore = "reads the wasm named by STELLA_CONTRACT_WASM; CI runs it after the wasm build"]
+    fn test_interface_matches_snapshot() {
+        interface::check_against_snapshot();
+    }
 
-        let route_hash = test_hash(99);
-        let result = client.get_commit(&route_hash);
-        assert_eq!(result, Err(RegistryError::NotFound));
+    fn core(env: &Env) -> CoreHashes {
+        CoreHashes { rules_hash: test_hash(env, 2), solver_version_hash: test_hash(env, 3) }
     }
 
     #[test]
-    fn test_has_commit() {
+    fn test_commit_rejects_conflicting_options() {
         let env = setup_env();
         let contract_id = env.register_contract(None, RouteIntegrityRegistry);
         let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
 
-        let route_hash = test_hash(15);
-        let rules_hash = test_hash(16);
-        let solver_hash = test_hash(17);
-
-        // Before commit
+        let committer = Address::generate(&env);
+        let route_hash = test_hash(&env, 230);
+        let options = CommitOptions {
+            expiry: Expiry::At(1700003600),
+            ttl: TtlOption::Seconds(3_600),
+            ..Default::default()
+        };
+        let result = client.try_commit(&committer, &route_hash, &core(&env), &options);
+        assert_eq!(result, Err(Ok(RegistryError::ConflictingOptions)));
         assert!(!client.has_commit(&route_hash));
 
-        // After commit
-        client.commit_route(&route_hash, &rules_hash, &solver_hash, &0u64).unwrap();
-        assert!(client.has_commit(&route_hash));
+        // ttl alone resolves against the commit's ledger time
+        let options = CommitOptions { ttl: TtlOption::Seconds(3_600), ..Default::default() };
+        client.commit(&committer, &route_hash, &core(&env), &options);
+        let commitment = client.get_commit(&route_hash);
+        assert_eq!(commitment.expiry, 1700003600);
+        assert_eq!(commitment.committer, committer);
+
+        let options = CommitOptions { ttl: TtlOption::Seconds(0), ..Default::default() };
+        let result = client.try_commit(&committer, &test_hash(&env, 231), &core(&env), &options);
+        assert_eq!(result, Err(Ok(RegistryError::ExpiredTimestamp)));
+        invariants::assert_registry_invariants(&env, &client);
     }
 
     #[test]
-    fn test_verify_commit() {
+    fn test_wrappers_match_consolidated_commit() {
         let env = setup_env();
         let contract_id = env.register_contract(None, RouteIntegrityRegistry);
         let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
 
-        let route_hash = test_hash(20);
-        let rules_hash = test_hash(21);
-        let solver_hash = test_hash(22);
+        let next_ledger = || env.ledger().with_mut(|li| li.sequence_number += 1);
+        let (rules_hash, solver_hash) = (test_hash(&env, 2), test_hash(&env, 3));
 
-        client.commit_route(&route_hash, &rules_hash, &solver_hash, &0u64).unwrap();
+        // Each wrapper call paired with the equivalent consolidated call
+        client.commit_route(&committer, &test_hash(&env, 232), &rules_hash, &solver_hash, &1700003600);
+        next_ledger();
+        let options = CommitOptions { expiry: Expiry::At(1700003600), ..Default::default() };
+        client.commit(&committer, &test_hash(&env, 233), &core(&env), &options);
+        next_ledger();
 
-        // Correct hashes
-        assert!(client.verify_commit(&route_hash, &rules_hash, &solver_hash));
+        client.commit_route_scheduled(&committer, &test_hash(&env, 234), &rules_hash, &solver_hash, &1700000600, &0);
+        next_ledger();
+        let options = CommitOptions { schedule: ScheduleOption::From(1700000600), ..Default::default() };
+        client.commit(&committer, &test_hash(&env, 235), &core(&env), &options);
+        next_ledger();
 
-        // Wrong rules hash
-        assert!(!client.verify_commit(&route_hash, &test_hash(99), &solver_hash));
+        client.commit_route_expiring(&committer, &test_hash(&env, 236), &rules_hash, &solver_hash, &Expiry::Never);
+        next_ledger();
+        client.commit(&committer, &test_hash(&env, 237), &core(&env), &CommitOptions::default());
+        next_ledger();
 
-        // Wrong solver hash
-        assert!(!client.verify_commit(&route_hash, &rules_hash, &test_hash(99)));
+        for (wrapped, consolidated) in [(232, 233), (234, 235), (236, 237)] {
+            assert_eq!(
+                without_seq(client.get_commit(&test_hash(&env, wrapped))),
+                without_seq(client.get_commit(&test_hash(&env, consolidated)))
+            );
+        }
 
-        // Nonexistent route
-        assert!(!client.verify_commit(&test_hash(99), &rules_hash, &solver_hash));
+        let (sha, keccak) = manifest_digests(&env, DUAL_MANIFEST);
+        client.commit_route_dual(&committer, &sha, &keccak, &rules_hash, &solver_hash, &0);
+        let wrapped = client.get_commit(&sha);
+        next_ledger();
+        let (sha2_, keccak2) = manifest_digests(&env, b"second manifest");
+        let options = CommitOptions { secondary_hash: SecondaryHashOption::Keccak(keccak2.clone()), ..Default::default() };
+        client.commit(&committer, &sha2_, &core(&env), &options);
+        assert_eq!(without_seq(client.get_commit(&sha2_)), without_seq(wrapped));
+        assert_eq!(client.get_secondary_hash(&sha2_), Some(keccak2));
+        invariants::assert_registry_invariants(&env, &client);
+    }
+
+    /// Soroban's limit on ledger entries one transaction may write
+    /// (`tx_max_write_ledger_entries`)
+    const TX_MAX_WRITE_ENTRIES: usize = 50;
+
+    /// Number of ledger entries `call` writes, read from the host's recorded footprint
+    fn write_entries(env: &Env, call: impl FnOnce()) -> usize {
+        env.host()
+            .with_mut_storage(|storage| {
+                storage.footprint = Footprint::default();
+                Ok(())
+            })
+            .unwrap();
+        call();
+        let budget = env.host().budget_cloned();
+        env.host()
+            .with_mut_storage(|storage| {
+                let entries = storage.footprint.0.iter(&budget)?;
+                Ok(entries.filter(|(_, access)| *access == AccessType::ReadWrite).count())
+            })
+            .unwrap()
+    }
+
+    /// CPU cost of one call, measured in a fresh registry so first-write effects match
+    fn measure_cpu(call: impl Fn(&Env, &RouteIntegrityRegistryClient, &Address)) -> u64 {
+        let env = setup_env();
+        let contract_id = env.register_contract(None, RouteIntegrityRegistry);
+        let client = RouteIntegrityRegistryClient::new(&env, &contract_id);
+        let committer = Address::generate(&env);
+
+        env.budget().reset_default();
+        call(&env, &client, &committer);
+        env.budget().cpu_instruction_cost()
+    }
+
+    #[test]
+    fn test_commit_costs_close_to_dedicated_variant() {
+        let dedicated = measure_cpu(|env, client, committer| {
+            client.commit_route_dual(committer, &test_hash(env, 238), &test_hash(env, 239), &test_hash(env, 2), &test_hash(env, 3), &0u64);
+        });
+        let consolidated = measure_cpu(|env, client, committer| {
+            let options = CommitOptions { secondary_hash: SecondaryHashOption::Keccak(test_hash(env, 239)), ..Default::default() };
+            client.commit(committer, &test_hash(env, 238), &core(env), &options);
+        });
+
+        // Both share one write path; the remaining gap is decoding the
+        // CoreHashes / CommitOptions map arguments, kept under 10%
+        assert!(consolidated * 10 <= dedicated * 11, "dedicated={} consolidated={}", dedicated, consolidated);
     }
 }
@@ -0,0 +1,105 @@
+//! Every numeric limit the registry enforces.
+//!
+//! Limits are declared through `limits!` so each constant is also listed in
+//! [`ALL`] under a stable name. `get_limits` is built from that list, which
+//! means a limit cannot be added here without being discoverable on-chain.
+
+macro_rules! limits {
+    ($( $(#[$doc:meta])* $name:ident: $ty:ty = $value:expr, $key:literal; )*) => {
+        $(
+            $(#[$doc])*
+            pub(crate) const $name: $ty = $value;
+        )*
+        
+        /// Every limit as `(stable name, value)`, in declaration order
+        pub(crate) const ALL: &[(&str, u64)] = &[$(($key, $name as u64)),*];
+    };
+}
+
+limits! {
+    /// Maximum age for a commitment (10 years in seconds) - sanity check
+    MAX_EXPIRY_DURATION: u64 = 315_360_000, "max_expiry_duration";
+    
+    /// TTL (in ledgers) kept on committer day digests: ~8 days at 5s ledgers.
+    /// Digests only serve recent reconciliation, so they live in temporary storage.
+    DAY_DIGEST_TTL_LEDGERS: u32 = 138_240, "day_digest_ttl_ledgers";
+    
+    /// TTL (in ledgers) of sandbox commitments: ~1 hour at 5s ledgers
+    SANDBOX_TTL_LEDGERS: u32 = 720, "sandbox_ttl_ledgers";
+    
+    /// TTL (in ledgers) of the per-ledger commit ordering entries: ~1 day at 5s ledgers
+    LEDGER_ORDER_TTL_LEDGERS: u32 = 17_280, "ledger_order_ttl_ledgers";
+    
+    /// Maximum anomaly reports kept per subject (bounds the per-subject list)
+    MAX_ANOMALIES_PER_SUBJECT: u32 = 20, "max_anomalies_per_subject";
+    
+    /// Maximum route hashes returned by one `get_ledger_commits` call
+    MAX_LEDGER_COMMITS_LISTED: u32 = 200, "max_ledger_commits_listed";
+    
+    /// Maximum consumption markers kept per commitment (bounds the per-route list)
+    MAX_CONSUMPTIONS_PER_ROUTE: u32 = 20, "max_consumptions_per_route";
+    
+    /// Maximum endorsers kept per commitment (bounds the per-route list)
+    MAX_ENDORSEMENTS_PER_ROUTE: u32 = 20, "max_endorsements_per_route";
+    
+    /// Maximum length in bytes of a committer profile's metadata URI
+    MAX_PROFILE_URI_BYTES: u32 = 256, "max_profile_uri_bytes";
+    
+    /// Maximum rotations in one committer key chain (bounds `resolve_committer`)
+    MAX_ROTATION_HOPS: u32 = 8, "max_rotation_hops";
+    
+    /// Maximum child routes linked to one parent. Sized so one full split
+    /// fits Soroban's per-transaction write-entry limit (50): each child
+    /// writes its record and parent link, on top of the parent's child
+    /// list and the shared index pages and counters.
+    MAX_CHILDREN_PER_ROUTE: u32 = 12, "max_children_per_route";
+    
+    /// Maximum ancestors of a child route (bounds the walk up its family)
+    MAX_ROUTE_DEPTH: u32 = 4, "max_route_depth";
+    
+    /// Maximum offline signing keys a committer can ever register; deactivated
+    /// keys keep their slot (bounds the per-committer list)
+    MAX_SIGNERS_PER_COMMITTER: u32 = 10, "max_signers_per_committer";
+    
+    /// Maximum route hashes returned by one `get_canonical_commits` call
+    MAX_CANONICAL_COMMITS_LISTED: u32 = 200, "max_canonical_commits_listed";
+    
+    /// Maximum entries in the input of a batch write (`commit_routes`,
+    /// `commit_routes_partial`, `revoke_commits`, `extend_ttls`). Sized so a
+    /// full batch commit fits Soroban's per-transaction write-entry limit
+    /// (50): each commitment writes its own record, on top of at most 21
+    /// index pages and counters shared by the whole call. Every distinct
+    /// rules or solver hash in a batch adds up to three entries more.
+    MAX_BATCH_SIZE: u32 = 25, "max_batch_size";
+    
+    /// Maximum entries in the input of a batch read (`get_commits`,
+    /// `has_commits`, `verify_commits`, `exists_any`, `first_existing`,
+    /// `attest_batch`). Reads append to no shared index, so the cap is
+    /// larger; `attest_batch` still writes one entry per hash, so the
+    /// network's write-entry limit (50) bounds it too.
+    MAX_READ_BATCH_SIZE: u32 = 100, "max_read_batch_size";
+    
+    /// Entries per storage entry of a sequence-keyed index. Appends fill
+    /// the newest page, so a batch writes one page per index, not one entry
+    /// per commitment.
+    INDEX_PAGE_SIZE: u32 = 32, "index_page_size";
+    
+    /// Maximum entries returned by one page of a sequence-keyed listing
+    /// (`get_commit_hashes_range`, `get_commits_by_*`, `get_commits_in_window`,
+    /// `get_committers`)
+    MAX_RANGE_COMMITS_LISTED: u32 = 100, "max_range_commits_listed";
+    
+    /// Number of most recent commitments `get_recent` can return
+    RECENT_CAPACITY: u32 = 100, "recent_capacity";
+    
+    /// Maximum days one `get_commits_in_window` query may span (bounds the buckets walked)
+    MAX_WINDOW_DAYS: u32 = 31, "max_window_days";
+    
+    /// Maximum days of counts one `commits_last_n_days` call returns
+    MAX_STATS_DAYS: u32 = 90, "max_stats_days";
+    
+    /// Maximum route hashes one `sample_day` call returns. Every sampled
+    /// hash may sit on its own index page, so this keeps a sample within
+    /// Soroban's per-transaction read-entry limit (40).
+    MAX_SAMPLE_SIZE: u32 = 32, "max_sample_size";
+}
@@ -0,0 +1,545 @@
+//! Rebuilds registry state from the event stream alone.
+//!
+//! Test support for the claim that auditors can reconstruct everything by
+//! consuming events. [`ReplayModel::from_events`] folds the contract's events
+//! into an independent model, and [`ReplayModel::assert_matches`] diffs that
+//! model against the contract's own reads and persistent storage. A write
+//! that does not emit enough data to be rebuilt fails the diff.
+//!
+//! Intentionally not reconstructed: the per-ledger commit listing
+//! (`DataKey::LedgerCount` / `DataKey::LedgerPage`). It is keyed by ledger
+//! sequence, which is ledger metadata rather than event data, and is a
+//! short-lived temporary index; each record's `intra_ledger_index` is
+//! checked instead.
+
+extern crate std;
+
+use std::vec::Vec as StdVec;
+
+use sha2::{Digest as _, Sha256};
+use soroban_sdk::testutils::storage::Persistent as _;
+use soroban_sdk::testutils::Events as _;
+use soroban_sdk::{Address, Bytes, BytesN, Env, IntoVal, Symbol, Val, Vec};
+
+use crate::{
+    AnomalyReport, AnomalySubject, BatchCommitment, BlindedRecord, CommitScope, CommitterProfile, CommitterStats, ConsumptionRecord,
+    CorridorEntry, DelegationGrant, RevocationRecord, RouteCommitment, RouteIntegrityRegistryClient, SignerRegistration,
+    INDEX_PAGE_SIZE, MAX_RANGE_COMMITS_LISTED, SECONDS_PER_DAY, UNLIMITED_COMMITS,
+};
+
+/// Registry state rebuilt from events
+#[derive(Default)]
+pub(crate) struct ReplayModel {
+    commits: StdVec<(BytesN<32>, RouteCommitment)>,
+    sandbox: StdVec<(BytesN<32>, RouteCommitment)>,
+    batches: StdVec<(BytesN<32>, BatchCommitment)>,
+    secondary: StdVec<(BytesN<32>, BytesN<32>)>,
+    relayers: StdVec<(BytesN<32>, Address)>,
+    signers: StdVec<(BytesN<32>, BytesN<32>)>,
+    bindings: StdVec<(BytesN<32>, BytesN<32>)>,
+    existence: StdVec<(BytesN<32>, (Address, u64))>,
+    blinded: StdVec<(BytesN<32>, BlindedRecord)>,
+    anomalies: StdVec<(AnomalySubject, StdVec<AnomalyReport>)>,
+    corridors: StdVec<(Symbol, CorridorEntry)>,
+    consumptions: StdVec<(BytesN<32>, StdVec<ConsumptionRecord>)>,
+    delegations: StdVec<((Address, Address), DelegationGrant)>,
+    scopes: StdVec<(BytesN<32>, CommitScope)>,
+    endorsements: StdVec<(BytesN<32>, StdVec<Address>)>,
+    profiles: StdVec<(Address, CommitterProfile)>,
+    commit_counts: StdVec<(Address, u64)>,
+    committer_stats: StdVec<(Address, CommitterStats)>,
+    rules_counts: StdVec<(BytesN<32>, u64)>,
+    solver_counts: StdVec<(BytesN<32>, u64)>,
+    day_counts: StdVec<(u64, u64)>,
+    revocations: StdVec<(BytesN<32>, RevocationRecord)>,
+    cancellations: StdVec<(BytesN<32>, u64)>,
+    signer_keys: StdVec<(BytesN<32>, SignerRegistration)>,
+    signer_deactivations: StdVec<(BytesN<32>, u64)>,
+    successors: StdVec<(BytesN<32>, BytesN<32>)>,
+    children: StdVec<(BytesN<32>, StdVec<BytesN<32>>)>,
+    rotations: StdVec<(Address, Address)>,
+    acks: StdVec<((BytesN<32>, Address), u64)>,
+    attestations: StdVec<((BytesN<32>, Address), u64)>,
+    day_digests: StdVec<((Address, u64), [u8; 32])>,
+    event_only_count: u64,
+    /// Upgraded or revealed commitments, whose `timestamp` predates storage
+    backdated: StdVec<BytesN<32>>,
+    total_commits: u64,
+}
+
+/// Replace the value for `key`, or append it
+fn upsert<K: PartialEq, V>(entries: &mut StdVec<(K, V)>, key: K, value: V) {
+    match entries.iter_mut().find(|(k, _)| *k == key) {
+        Some(entry) => entry.1 = value,
+        None => entries.push((key, value)),
+    }
+}
+
+/// Storage entries holding `count` entries of a paged index
+fn pages(count: u64) -> usize {
+    count.div_ceil(INDEX_PAGE_SIZE as u64) as usize
+}
+
+/// Storage entries of a family of paged indexes: one counter each, plus its pages
+fn counted_index<K>(counts: &StdVec<(K, u64)>) -> usize {
+    counts.iter().map(|(_, count)| 1 + pages(*count)).sum()
+}
+
+fn lookup<'a, K: PartialEq, V>(entries: &'a mut StdVec<(K, V)>, key: &K) -> Option<&'a mut V> {
+    entries.iter_mut().find(|(k, _)| k == key).map(|(_, v)| v)
+}
+
+impl ReplayModel {
+    /// Fold every event `contract_id` has emitted so far, in order
+    pub(crate) fn from_events(env: &Env, contract_id: &Address) -> Self {
+        let mut model = Self::default();
+        let events = env.events().all();
+        // The upgrade or reveal event follows its commit event, so find those first
+        for (source, topics, data) in events.iter() {
+            if source != *contract_id {
+                continue;
+            }
+            let kind: Symbol = topics.get(0).unwrap().into_val(env);
+            if kind == Symbol::new(env, "upgrade") {
+                model.backdated.push(topics.get(1).unwrap().into_val(env));
+            } else if kind == Symbol::new(env, "revealed") {
+                let (route_hash, _): (BytesN<32>, u64) = data.into_val(env);
+                model.backdated.push(route_hash);
+            }
+        }
+        for (source, topics, data) in events.iter() {
+            if source != *contract_id {
+                continue;
+            }
+            model.apply(env, topics, data);
+        }
+        model
+    }
+
+    fn apply(&mut self, env: &Env, topics: Vec<Val>, data: Val) {
+        let kind: Symbol = topics.get(0).unwrap().into_val(env);
+        let subject = topics.get(1).unwrap();
+        let is = |name: &str| kind == Symbol::new(env, name);
+
+        if is("commit") {
+            let route_hash: BytesN<32> = subject.into_val(env);
+            let (
+                rules_hash,
+                solver_version_hash,
+                committer,
+                timestamp,
+                expiry,
+                active_from,
+                intra_ledger_index,
+                committer_seq,
+                commit_id,
+            ): (BytesN<32>, BytesN<32>, Address, u64, u64, u64, u32, u64, u64) = data.into_val(env);
+            // Indexes follow the storage time; backdated commitments activate when stored
+            let stored_at = if self.backdated.contains(&route_hash) { active_from } else { timestamp };
+            self.fold_day_digest(committer.clone(), stored_at, &route_hash);
+            let count = lookup(&mut self.commit_counts, &committer).copied().unwrap_or(0);
+            assert_eq!(committer_seq, count + 1, "committer sequence skipped");
+            upsert(&mut self.commit_counts, committer.clone(), committer_seq);
+            let stats = match lookup(&mut self.committer_stats, &committer) {
+                Some(stats) => CommitterStats {
+                    count: stats.count + 1,
+                    first_ts: stats.first_ts.min(stored_at),
+                    last_ts: stats.last_ts.max(stored_at),
+                },
+                None => CommitterStats { count: 1, first_ts: stored_at, last_ts: stored_at },
+            };
+            upsert(&mut self.committer_stats, committer.clone(), stats);
+            let rules_count = lookup(&mut self.rules_counts, &rules_hash).copied().unwrap_or(0);
+            upsert(&mut self.rules_counts, rules_hash.clone(), rules_count + 1);
+            let solver_count = lookup(&mut self.solver_counts, &solver_version_hash).copied().unwrap_or(0);
+            upsert(&mut self.solver_counts, solver_version_hash.clone(), solver_count + 1);
+            let day_count = lookup(&mut self.day_counts, &(stored_at / SECONDS_PER_DAY)).copied().unwrap_or(0);
+            upsert(&mut self.day_counts, stored_at / SECONDS_PER_DAY, day_count + 1);
+            assert_eq!(commit_id, self.total_commits + 1, "commit id skipped");
+            self.total_commits = commit_id;
+            upsert(
+                &mut self.commits,
+                route_hash,
+                RouteCommitment {
+                    rules_hash,
+                    solver_version_hash,
+                    committer,
+                    timestamp,
+                    expiry,
+                    active_from,
+                    intra_ledger_index,
+                    committer_seq,
+                    commit_id,
+                },
+            );
+        } else if is("sandbox") {
+            let route_hash: BytesN<32> = subject.into_val(env);
+            let (rules_hash, solver_version_hash, committer, timestamp, expiry): (
+                BytesN<32>,
+                BytesN<32>,
+                Address,
+                u64,
+                u64,
+            ) = data.into_val(env);
+            upsert(
+                &mut self.sandbox,
+                route_hash,
+                RouteCommitment {
+                    rules_hash,
+                    solver_version_hash,
+                    committer,
+                    timestamp,
+                    expiry,
+                    active_from: timestamp,
+                    intra_ledger_index: 0,
+                    committer_seq: 0,
+                    commit_id: 0,
+                },
+            );
+        } else if is("batch") {
+            let (rules_hash, solver_version_hash, committer, timestamp, expiry, leaf_count): (
+                BytesN<32>,
+                BytesN<32>,
+                Address,
+                u64,
+                u64,
+                u32,
+            ) = data.into_val(env);
+            upsert(
+                &mut self.batches,
+                subject.into_val(env),
+                BatchCommitment { rules_hash, solver_version_hash, committer, timestamp, expiry, leaf_count },
+            );
+        } else if is("dual") {
+            upsert(&mut self.secondary, subject.into_val(env), data.into_val(env));
+        } else if is("relayed") {
+            let (relayer, _committer): (Address, Address) = data.into_val(env);
+            upsert(&mut self.relayers, subject.into_val(env), relayer);
+        } else if is("signed") {
+            upsert(&mut self.signers, subject.into_val(env), data.into_val(env));
+        } else if is("bound") {
+            upsert(&mut self.bindings, subject.into_val(env), data.into_val(env));
+        } else if is("exist") {
+            upsert(&mut self.existence, subject.into_val(env), data.into_val(env));
+        } else if is("upgrade") {
+            let route_hash: BytesN<32> = subject.into_val(env);
+            let (_committer, original_timestamp): (Address, u64) = data.into_val(env);
+            self.existence.retain(|(k, _)| *k != route_hash);
+            let commitment = lookup(&mut self.commits, &route_hash).expect("upgrade event without a preceding commit event");
+            assert_eq!(commitment.timestamp, original_timestamp, "upgrade disagrees with its commit event");
+        } else if is("blinded") {
+            let (committer, timestamp, expiry): (Address, u64, u64) = data.into_val(env);
+            upsert(&mut self.blinded, subject.into_val(env), BlindedRecord { committer, timestamp, expiry });
+        } else if is("revealed") {
+            let blinded_hash: BytesN<32> = subject.into_val(env);
+            let (route_hash, original_timestamp): (BytesN<32>, u64) = data.into_val(env);
+            self.blinded.retain(|(k, _)| *k != blinded_hash);
+            let commitment = lookup(&mut self.commits, &route_hash).expect("revealed event without a preceding commit event");
+            assert_eq!(commitment.timestamp, original_timestamp, "reveal disagrees with its commit event");
+        } else if is("commit_eo") {
+            let (_, _, _, _, count): (BytesN<32>, BytesN<32>, Address, u64, u64) = data.into_val(env);
+            assert_eq!(count, self.event_only_count + 1, "event-only counter skipped");
+            self.event_only_count = count;
+        } else if is("anomaly") {
+            let reporter: Address = subject.into_val(env);
+            let (subject, evidence_hash, timestamp): (AnomalySubject, BytesN<32>, u64) = data.into_val(env);
+            let report = AnomalyReport { reporter, evidence_hash, timestamp };
+            match lookup(&mut self.anomalies, &subject) {
+                Some(reports) => reports.push(report),
+                None => self.anomalies.push((subject, std::vec![report])),
+            }
+        } else if is("corridor") {
+            let (registrar, source_asset_hash, dest_asset_hash, registered_at): (
+                Address,
+                BytesN<32>,
+                BytesN<32>,
+                u64,
+            ) = data.into_val(env);
+            upsert(
+                &mut self.corridors,
+                subject.into_val(env),
+                CorridorEntry { registrar, source_asset_hash, dest_asset_hash, registered_at },
+            );
+        } else if is("consumed") {
+            let route_hash: BytesN<32> = subject.into_val(env);
+            let (consumer, consumption_ref, at): (Address, BytesN<32>, u64) = data.into_val(env);
+            let record = ConsumptionRecord { consumer, consumption_ref, at };
+            match lookup(&mut self.consumptions, &route_hash) {
+                Some(records) => records.push(record),
+                None => self.consumptions.push((route_hash, std::vec![record])),
+            }
+        } else if is("endorsed") {
+            let route_hash: BytesN<32> = subject.into_val(env);
+            let (endorser, _timestamp): (Address, u64) = data.into_val(env);
+            match lookup(&mut self.endorsements, &route_hash) {
+                Some(endorsers) => endorsers.push(endorser),
+                None => self.endorsements.push((route_hash, std::vec![endorser])),
+            }
+        } else if is("withdrawn") {
+            let (_committer, reason_hash, timestamp): (Address, BytesN<32>, u64) = data.into_val(env);
+            upsert(&mut self.revocations, subject.into_val(env), RevocationRecord { reason_hash, timestamp });
+        } else if is("cancelled") {
+            let (_committer, timestamp): (Address, u64) = data.into_val(env);
+            upsert(&mut self.cancellations, subject.into_val(env), timestamp);
+        } else if is("signer") {
+            let (pubkey, registered_at): (BytesN<32>, u64) = data.into_val(env);
+            upsert(&mut self.signer_keys, pubkey, SignerRegistration { committer: subject.into_val(env), registered_at });
+        } else if is("signeroff") {
+            let (pubkey, deactivated_at): (BytesN<32>, u64) = data.into_val(env);
+            upsert(&mut self.signer_deactivations, pubkey, deactivated_at);
+        } else if is("supersede") {
+            let (new_route_hash, _committer): (BytesN<32>, Address) = data.into_val(env);
+            upsert(&mut self.successors, subject.into_val(env), new_route_hash);
+        } else if is("split") {
+            let parent: BytesN<32> = subject.into_val(env);
+            let (_committer, added): (Address, Vec<BytesN<32>>) = data.into_val(env);
+            match lookup(&mut self.children, &parent) {
+                Some(children) => children.extend(added.iter()),
+                None => self.children.push((parent, added.iter().collect())),
+            }
+        } else if is("rotated") {
+            upsert(&mut self.rotations, subject.into_val(env), data.into_val(env));
+        } else if is("acked") {
+            let (user, timestamp): (Address, u64) = data.into_val(env);
+            upsert(&mut self.acks, (subject.into_val(env), user), timestamp);
+        } else if is("attested") {
+            let (attester, timestamp): (Address, u64) = data.into_val(env);
+            upsert(&mut self.attestations, (subject.into_val(env), attester), timestamp);
+        } else if is("profile") {
+            let (name_hash, metadata_uri, updated_at): (BytesN<32>, Bytes, u64) = data.into_val(env);
+            let profile = CommitterProfile { name_hash, metadata_uri, updated_at };
+            upsert(&mut self.profiles, subject.into_val(env), profile);
+        } else if is("delegate") {
+            let owner: Address = subject.into_val(env);
+            let (delegate, approved_at): (Address, u64) = data.into_val(env);
+            let grant = DelegationGrant {
+                delegate: delegate.clone(),
+                approved_at,
+                expires_at: 0,
+                allowed_namespace: None,
+                allowed_corridor: None,
+                remaining_commits: UNLIMITED_COMMITS,
+            };
+            upsert(&mut self.delegations, (owner, delegate), grant);
+        } else if is("grant") {
+            let grant: DelegationGrant = data.into_val(env);
+            upsert(&mut self.delegations, (subject.into_val(env), grant.delegate.clone()), grant);
+        } else if is("delegated") {
+            let route_hash: BytesN<32> = subject.into_val(env);
+            let (delegate, remaining_commits, scope): (Address, u32, Option<CommitScope>) = data.into_val(env);
+            let owner = lookup(&mut self.commits, &route_hash).expect("delegated commit without its commit event").committer.clone();
+            lookup(&mut self.delegations, &(owner, delegate)).expect("delegated commit without a grant").remaining_commits = remaining_commits;
+            if let Some(scope) = scope {
+                upsert(&mut self.scopes, route_hash, scope);
+            }
+        } else if is("grant_exhausted") {
+            let key = (subject.into_val(env), data.into_val(env));
+            let grant = lookup(&mut self.delegations, &key).expect("exhausted grant never made");
+            assert_eq!(grant.remaining_commits, 0, "grant announced exhausted with commits left");
+        } else if is("revoked") {
+            let key = (subject.into_val(env), data.into_val(env));
+            self.delegations.retain(|(k, _)| *k != key);
+        } else {
+            panic!("replay: unhandled event kind {:?}", kind);
+        }
+    }
+
+    fn fold_day_digest(&mut self, committer: Address, timestamp: u64, route_hash: &BytesN<32>) {
+        let key = (committer, timestamp / SECONDS_PER_DAY);
+        let prev = lookup(&mut self.day_digests, &key).copied().unwrap_or([0u8; 32]);
+        let mut hasher = Sha256::new();
+        hasher.update(prev);
+        hasher.update(route_hash.to_array());
+        upsert(&mut self.day_digests, key, hasher.finalize().into());
+    }
+
+    /// Committers with registered signing keys, each with its keys in order
+    fn signer_committers(&self) -> StdVec<(Address, StdVec<BytesN<32>>)> {
+        let mut committers: StdVec<(Address, StdVec<BytesN<32>>)> = StdVec::new();
+        for (pubkey, registration) in &self.signer_keys {
+            match lookup(&mut committers, &registration.committer) {
+                Some(keys) => keys.push(pubkey.clone()),
+                None => committers.push((registration.committer.clone(), std::vec![pubkey.clone()])),
+            }
+        }
+        committers
+    }
+
+    /// Persistent entries the model accounts for
+    fn persistent_entry_count(&self) -> u32 {
+        let pairs = self.corridors.len();
+        // The id, committer, rules, solver and day indexes store one entry per
+        // page; rules, solver and day each also keep a counter
+        (self.commits.len()
+            + pages(self.total_commits)
+            + self.commit_counts.iter().map(|(_, count)| pages(*count)).sum::<usize>()
+            + self.batches.len()
+            + self.secondary.len()
+            + self.relayers.len()
+            + self.signers.len()
+            + self.bindings.len()
+            + self.existence.len()
+            + self.blinded.len()
+            + self.anomalies.len()
+            + self.corridors.len()
+            + pairs
+            + self.consumptions.len()
+            + self.delegations.len()
+            + self.scopes.len()
+            + self.profiles.len()
+            // Stats entries and the committer list's pages, plus its counter
+            + self.committer_stats.len()
+            + pages(self.committer_stats.len() as u64)
+            + (!self.committer_stats.is_empty()) as usize
+            + counted_index(&self.rules_counts)
+            + counted_index(&self.solver_counts)
+            + counted_index(&self.day_counts)
+            + self.revocations.len()
+            + self.cancellations.len()
+            // Each key's registration, plus one key list per committer
+            + self.signer_keys.len()
+            + self.signer_committers().len()
+            + self.signer_deactivations.len()
+            // Each link is stored in both directions
+            + self.successors.len() * 2
+            // A child list per parent, and a parent link per child
+            + self.children.iter().map(|(_, children)| 1 + children.len()).sum::<usize>()
+            + self.rotations.len() * 2
+            + self.acks.len()
+            + self.attestations.len()
+            + self.endorsements.iter().map(|(_, endorsers)| 1 + endorsers.len()).sum::<usize>()) as u32
+    }
+
+    /// Diff the rebuilt model against the live contract
+    pub(crate) fn assert_matches(&self, env: &Env, client: &RouteIntegrityRegistryClient) {
+        for (route_hash, commitment) in &self.commits {
+            assert_eq!(&client.get_commit(route_hash), commitment, "commitment diverges from events");
+            let by_seq = client.get_commit_by_committer_seq(&commitment.committer, &commitment.committer_seq);
+            assert_eq!(&by_seq, route_hash, "sequence lookup diverges from events");
+            assert_eq!(&client.get_commit_by_id(&commitment.commit_id), commitment, "id lookup diverges from events");
+        }
+        for (route_hash, commitment) in &self.sandbox {
+            assert_eq!(&client.get_sandbox_commit(route_hash), commitment, "sandbox diverges from events");
+        }
+        for (batch_root, batch) in &self.batches {
+            assert_eq!(&client.get_batch(batch_root), batch, "batch diverges from events");
+        }
+        for (route_hash, keccak) in &self.secondary {
+            assert_eq!(client.get_secondary_hash(route_hash).as_ref(), Some(keccak));
+        }
+        for (route_hash, relayer) in &self.relayers {
+            assert_eq!(client.get_relayer(route_hash).as_ref(), Some(relayer));
+        }
+        for (route_hash, pubkey) in &self.signers {
+            assert_eq!(client.get_signer(route_hash).as_ref(), Some(pubkey));
+        }
+        for (route_hash, user_binding) in &self.bindings {
+            assert_eq!(client.get_user_binding(route_hash).as_ref(), Some(user_binding));
+        }
+        for (blinded_hash, record) in &self.blinded {
+            assert_eq!(client.get_blinded(blinded_hash).as_ref(), Some(record));
+        }
+        for (route_hash, record) in &self.existence {
+            assert_eq!(client.get_existence(route_hash).as_ref(), Some(record));
+        }
+        for (subject, reports) in &self.anomalies {
+            let stored: StdVec<AnomalyReport> = client.get_anomalies(subject).iter().collect();
+            assert_eq!(&stored, reports, "anomaly reports diverge from events");
+        }
+        for (corridor, entry) in &self.corridors {
+            assert_eq!(client.resolve_corridor(corridor).as_ref(), Some(entry));
+            let found = client.find_corridor_by_pair(&entry.source_asset_hash, &entry.dest_asset_hash);
+            assert_eq!(found.as_ref(), Some(corridor));
+        }
+        for (route_hash, records) in &self.consumptions {
+            let stored: StdVec<ConsumptionRecord> = client.get_consumptions(route_hash).iter().collect();
+            assert_eq!(&stored, records, "consumption markers diverge from events");
+        }
+        for (route_hash, endorsers) in &self.endorsements {
+            let stored: StdVec<Address> = client.get_endorsements(route_hash).iter().collect();
+            assert_eq!(&stored, endorsers, "endorsements diverge from events");
+            for endorser in endorsers {
+                assert!(client.is_endorsed_by(route_hash, endorser));
+            }
+        }
+        for (route_hash, record) in &self.revocations {
+            assert_eq!(client.get_revocation(route_hash).as_ref(), Some(record), "revocation diverges from events");
+        }
+        for (route_hash, timestamp) in &self.cancellations {
+            assert_eq!(client.get_cancellation(route_hash), Some(*timestamp), "cancellation diverges from events");
+        }
+        for (pubkey, registration) in &self.signer_keys {
+            assert_eq!(client.get_signer_registration(pubkey).as_ref(), Some(registration), "signer diverges from events");
+        }
+        for (pubkey, deactivated_at) in &self.signer_deactivations {
+            assert_eq!(client.get_signer_deactivation(pubkey), Some(*deactivated_at), "signer deactivation diverges from events");
+        }
+        for (committer, keys) in self.signer_committers() {
+            let stored: StdVec<BytesN<32>> = client.get_signers(&committer).iter().collect();
+            assert_eq!(stored, keys, "signer list diverges from events");
+        }
+        for (old_route_hash, new_route_hash) in &self.successors {
+            assert_eq!(client.get_successor(old_route_hash).as_ref(), Some(new_route_hash), "successor diverges from events");
+            assert_eq!(client.get_predecessor(new_route_hash).as_ref(), Some(old_route_hash), "predecessor diverges from events");
+        }
+        for (parent, children) in &self.children {
+            let stored: StdVec<BytesN<32>> = client.get_children(parent).iter().collect();
+            assert_eq!(&stored, children, "children diverge from events");
+            for child in children {
+                assert_eq!(client.get_parent(child).as_ref(), Some(parent), "parent diverges from events");
+            }
+        }
+        for (old, new) in &self.rotations {
+            assert_eq!(client.resolve_committer(old), client.resolve_committer(new), "rotation diverges from events");
+        }
+        for ((route_hash, user), timestamp) in &self.acks {
+            assert_eq!(client.get_ack(route_hash, user), Some(*timestamp), "ack diverges from events");
+        }
+        for ((route_hash, attester), timestamp) in &self.attestations {
+            assert_eq!(client.get_attestation(route_hash, attester), Some(*timestamp), "attestation diverges from events");
+        }
+        for (rules_hash, count) in &self.rules_counts {
+            assert_eq!(client.count_by_rules(rules_hash), *count, "rules count diverges from events");
+        }
+        for (solver_hash, count) in &self.solver_counts {
+            assert_eq!(client.count_by_solver(solver_hash), *count, "solver count diverges from events");
+        }
+        for (day, count) in &self.day_counts {
+            assert_eq!(client.commits_on_day(day), *count, "day count diverges from events");
+            let start = day * SECONDS_PER_DAY;
+            let listed = client.get_commits_in_window(&start, &start, &0, &u32::MAX).len() as u64;
+            assert_eq!(listed, (*count).min(MAX_RANGE_COMMITS_LISTED as u64), "day bucket diverges from events");
+        }
+        for (committer, stats) in &self.committer_stats {
+            assert_eq!(client.get_committer_stats(committer), Some(*stats), "committer stats diverge from events");
+        }
+        assert_eq!(client.committer_count(), self.committer_stats.len() as u64, "committer count diverges from events");
+        for (position, (committer, _)) in self.committer_stats.iter().enumerate() {
+            let listed = client.get_committers(&(position as u32), &1).get(0);
+            assert_eq!(listed.as_ref(), Some(committer), "committer list diverges from events");
+        }
+        for (committer, count) in &self.commit_counts {
+            assert_eq!(client.get_commit_count_by_committer(committer), *count, "commit count diverges from events");
+        }
+        for (committer, profile) in &self.profiles {
+            assert_eq!(client.get_profile(committer).as_ref(), Some(profile), "profile diverges from events");
+        }
+        for ((owner, delegate), grant) in &self.delegations {
+            assert_eq!(client.get_grant(owner, delegate).as_ref(), Some(grant), "delegation diverges from events");
+        }
+        for (route_hash, scope) in &self.scopes {
+            assert_eq!(client.get_scope(route_hash).as_ref(), Some(scope), "scope diverges from events");
+        }
+        for ((committer, day), digest) in &self.day_digests {
+            let stored = client.get_committer_day_digest(committer, day).map(|d| d.to_array());
+            assert_eq!(stored, Some(*digest), "day digest diverges from events");
+        }
+
+        assert_eq!(client.get_event_only_count(), self.event_only_count);
+        assert_eq!(client.total_commits(), self.total_commits, "total commits diverge from events");
+
+        // Every persistent entry must be explained by some event
+        let stored = env.as_contract(&client.address, || env.storage().persistent().all().len());
+        assert_eq!(stored, self.persistent_entry_count(), "persistent entry written without an event");
+    }
+}